@@ -0,0 +1,270 @@
+//! The endpoints. Each takes the request body as JSON and answers with JSON, by calling the same
+//! library code as the playground.
+
+use std::collections::BTreeMap;
+
+use language::{
+  ast::{exp::Exp, sequent::Sequent},
+  latex,
+  parser::{parse_sentence, sequent::sequent},
+  semantics::{counterexample, truth_table},
+};
+use language_derivation_rule::{
+  document::{DocumentRow, ProofDocument},
+  rule_set::{NameError, Profile, System},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Why a request failed, with the HTTP status to answer with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiError {
+  pub status: u16,
+  pub message: String,
+}
+
+impl ApiError {
+  fn bad_request(message: impl Into<String>) -> Self {
+    ApiError {
+      status: 400,
+      message: message.into(),
+    }
+  }
+}
+
+/// Answer a request. The body of a successful answer is the returned value.
+pub fn handle(method: &str, path: &str, body: &str) -> Result<Value, ApiError> {
+  let endpoint = match path {
+    "/formula" => formula,
+    "/check" => check,
+    "/truth-table" => truth_table_endpoint,
+    "/entailment" => entailment,
+    _ => {
+      return Err(ApiError {
+        status: 404,
+        message: format!("no endpoint at {}", path),
+      })
+    }
+  };
+  if method != "POST" {
+    return Err(ApiError {
+      status: 405,
+      message: format!("{} takes POST", path),
+    });
+  }
+  endpoint(body)
+}
+
+fn request<T: DeserializeOwned>(body: &str) -> Result<T, ApiError> {
+  serde_json::from_str(body).map_err(|err| ApiError::bad_request(format!("invalid request: {}", err)))
+}
+
+fn response<T: Serialize>(value: T) -> Result<Value, ApiError> {
+  Ok(serde_json::to_value(value).expect("responses serialize"))
+}
+
+fn parse_exp(s: &str) -> Result<Exp, ApiError> {
+  parse_sentence(s).ok_or_else(|| ApiError::bad_request(format!("invalid sentence: {}", s)))
+}
+
+fn parse_sequent(s: &str) -> Result<Sequent, ApiError> {
+  match sequent(s.trim()) {
+    Ok(("", sequent)) => Ok(sequent),
+    _ => Err(ApiError::bad_request(format!("invalid sequent: {}", s))),
+  }
+}
+
+#[derive(Deserialize)]
+struct FormulaRequest {
+  formula: String,
+}
+
+#[derive(Serialize)]
+struct FormulaResponse {
+  formula: String,
+  latex: String,
+}
+
+/// `{"formula": "P -> Q"}`: the sentence written with its representative symbols, and as LaTeX.
+fn formula(body: &str) -> Result<Value, ApiError> {
+  let FormulaRequest { formula } = request(body)?;
+  let exp = parse_exp(&formula)?;
+  response(FormulaResponse {
+    formula: exp.to_string(),
+    latex: latex::exp(&exp),
+  })
+}
+
+#[derive(Deserialize)]
+struct CheckRequest {
+  #[serde(default, alias = "system")]
+  rule_system: System,
+  /// The name of a `Profile`; classical when left out.
+  #[serde(default)]
+  profile: Option<String>,
+  #[serde(default)]
+  sequent: Option<String>,
+  rows: Vec<DocumentRow>,
+}
+
+/// `{"rule_system": "Gentzen", "profile": "classical", "sequent": "P ⊢ P", "rows": [...]}`: whether
+/// each row is valid, what it depends on and what is wrong with it. The rows are those of a proof
+/// document.
+fn check(body: &str) -> Result<Value, ApiError> {
+  let CheckRequest {
+    rule_system,
+    profile,
+    sequent,
+    rows,
+  } = request(body)?;
+  let profile = match profile {
+    Some(name) => name
+      .parse()
+      .map_err(|err: NameError| ApiError::bad_request(err.to_string()))?,
+    None => Profile::default(),
+  };
+  let document = ProofDocument {
+    sequent,
+    ..ProofDocument::new(rule_system, rows)
+  };
+  let report = document
+    .check(profile)
+    .map_err(|err| ApiError::bad_request(err.to_string()))?;
+  response(report)
+}
+
+#[derive(Deserialize)]
+struct TruthTableRequest {
+  formulas: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TruthTableResponse {
+  letters: Vec<String>,
+  rows: Vec<TruthTableRow>,
+}
+
+#[derive(Serialize)]
+struct TruthTableRow {
+  valuation: Vec<bool>,
+  values: Vec<bool>,
+}
+
+/// `{"formulas": ["(P → Q)", "¬P"]}`: the value of each formula under every valuation of its letters.
+fn truth_table_endpoint(body: &str) -> Result<Value, ApiError> {
+  let TruthTableRequest { formulas } = request(body)?;
+  let exps = formulas
+    .iter()
+    .map(|formula| parse_exp(formula))
+    .collect::<Result<Vec<_>, _>>()?;
+  let table = truth_table(&exps).map_err(|err| ApiError::bad_request(err.to_string()))?;
+  response(TruthTableResponse {
+    letters: table.letters,
+    rows: table
+      .rows
+      .into_iter()
+      .map(|row| TruthTableRow {
+        valuation: row.valuation,
+        values: row.values,
+      })
+      .collect(),
+  })
+}
+
+#[derive(Deserialize)]
+struct EntailmentRequest {
+  sequent: String,
+}
+
+#[derive(Serialize)]
+struct EntailmentResponse {
+  valid: bool,
+  /// A valuation making the premises true and the conclusion false, when there is one
+  counterexample: Option<BTreeMap<String, bool>>,
+}
+
+/// `{"sequent": "(P → Q), P ⊢ Q"}`: whether the premises entail the conclusion.
+fn entailment(body: &str) -> Result<Value, ApiError> {
+  let EntailmentRequest { sequent } = request(body)?;
+  let sequent = parse_sequent(&sequent)?;
+  let counterexample = counterexample(&sequent).map_err(|err| ApiError::bad_request(err.to_string()))?;
+  response(EntailmentResponse {
+    valid: counterexample.is_none(),
+    counterexample,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use serde_json::json;
+
+  #[rstest]
+  #[case("/formula", json!({"formula": "P -> -Q"}), json!({"formula": "(P → ¬Q)", "latex": "(P \\to \\neg Q)"}))]
+  #[case(
+    "/truth-table",
+    json!({"formulas": ["(P ∨ Q)"]}),
+    json!({"letters": ["P", "Q"], "rows": [
+      {"valuation": [true, true], "values": [true]},
+      {"valuation": [true, false], "values": [true]},
+      {"valuation": [false, true], "values": [true]},
+      {"valuation": [false, false], "values": [false]},
+    ]})
+  )]
+  #[case("/entailment", json!({"sequent": "(P → Q), P ⊢ Q"}), json!({"valid": true, "counterexample": null}))]
+  #[case(
+    "/entailment",
+    json!({"sequent": "(P → Q), Q ⊢ P"}),
+    json!({"valid": false, "counterexample": {"P": false, "Q": true}})
+  )]
+  fn api_endpoints(#[case] path: &str, #[case] body: Value, #[case] expected: Value) {
+    assert_eq!(handle("POST", path, &body.to_string()), Ok(expected));
+  }
+
+  #[test]
+  fn api_check() {
+    let body = json!({
+      "rule_system": "Gentzen",
+      "sequent": "(P → Q), P ⊢ Q",
+      "rows": [
+        {"sentence": "(P → Q)", "derivation": "P"},
+        {"s": "P", "d": "P"},
+        {"sentence": "Q", "derivation": "1, 2 →E"},
+      ],
+    });
+    let answer = handle("POST", "/check", &body.to_string()).unwrap();
+    assert_eq!(answer["proved"], json!(true));
+    assert_eq!(
+      answer["rows"][2],
+      json!({"line": 3, "valid": true, "dependencies": [1, 2], "complete": true, "errors": []})
+    );
+
+    let body = json!({
+      "sequent": "P ⊢ Q",
+      "rows": [
+        {"sentence": "P", "derivation": "P"},
+        {"sentence": "Q", "derivation": "1 &E"},
+        {"sentence": "(Q", "derivation": "3 &E"},
+      ],
+    });
+    let answer = handle("POST", "/check", &body.to_string()).unwrap();
+    assert_eq!(answer["proved"], json!(false));
+    assert_eq!(
+      answer["rows"][1]["errors"],
+      json!(["the rule does not derive this sentence"])
+    );
+    assert_eq!(answer["rows"][2]["errors"][0], json!("invalid sentence"));
+  }
+
+  #[rstest]
+  #[case("POST", "/nothing", "{}", 404)]
+  #[case("GET", "/formula", "", 405)]
+  #[case("POST", "/formula", "not json", 400)]
+  #[case("POST", "/formula", r#"{"formula": "(P →"}"#, 400)]
+  #[case("POST", "/check", r#"{"profile": "modal", "rows": []}"#, 400)]
+  #[case("POST", "/truth-table", r#"{"formulas": ["(x)Fx"]}"#, 400)]
+  fn api_errors(#[case] method: &str, #[case] path: &str, #[case] body: &str, #[case] status: u16) {
+    assert_eq!(handle(method, path, body).map_err(|err| err.status), Err(status));
+  }
+}
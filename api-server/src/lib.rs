@@ -0,0 +1,139 @@
+//! A JSON API over HTTP for checking derivations and sentences from other services, such as a
+//! course's LMS. Every endpoint takes a POST with a JSON body:
+//!
+//! - `/formula`: parse a sentence and print it back
+//! - `/check`: check a derivation, row by row
+//! - `/truth-table`: the truth table of some sentences
+//! - `/entailment`: whether the premises of a sequent entail its conclusion
+//!
+//! Failures answer with a 4xx status and `{"error": "..."}`.
+
+pub mod api;
+
+use std::io::Read;
+
+use serde_json::json;
+use tiny_http::{Header, Request, Response, Server};
+
+/// Requests larger than this are refused.
+pub const MAX_BODY: u64 = 1 << 20;
+
+/// Answer requests until the server is closed.
+pub fn serve(server: &Server) {
+  for request in server.incoming_requests() {
+    respond(request);
+  }
+}
+
+/// Answer a request with the JSON `api::handle` gives. The query string plays no part in routing.
+pub fn respond(mut request: Request) {
+  let (status, value) = match read_body(&mut request) {
+    Ok(body) => {
+      let url = request.url();
+      let path = url.split_once('?').map_or(url, |(path, _)| path);
+      match api::handle(request.method().as_str(), path, &body) {
+        Ok(value) => (200, value),
+        Err(err) => (err.status, json!({ "error": err.message })),
+      }
+    }
+    Err((status, message)) => (status, json!({ "error": message })),
+  };
+  let content_type = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+  let response = Response::from_string(value.to_string())
+    .with_status_code(status)
+    .with_header(content_type);
+  // the client may have gone away; there is no one left to tell
+  let _ = request.respond(response);
+}
+
+/// The body, or the status and message to refuse it with.
+fn read_body(request: &mut Request) -> Result<String, (u16, String)> {
+  let too_large = (413, format!("the body is larger than {} bytes", MAX_BODY));
+  if request.body_length().is_some_and(|len| len as u64 > MAX_BODY) {
+    return Err(too_large);
+  }
+  // one byte more than allowed tells a body without a length that is too large
+  let mut body = vec![];
+  if request.as_reader().take(MAX_BODY + 1).read_to_end(&mut body).is_err() {
+    return Err((400, "the body could not be read".to_owned()));
+  }
+  if body.len() as u64 > MAX_BODY {
+    return Err(too_large);
+  }
+  String::from_utf8(body).map_err(|_| (400, "the body is not UTF-8".to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{
+    io::Write,
+    net::{SocketAddr, TcpStream},
+    thread,
+  };
+
+  /// Send one request to a server on a free local port and return the raw response.
+  fn exchange(request: impl FnOnce(SocketAddr) -> String) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let handle = thread::spawn(move || respond(server.recv().unwrap()));
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request(addr).as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    handle.join().unwrap();
+    response
+  }
+
+  #[test]
+  fn server_answers_json() {
+    let body = r#"{"sequent": "P ⊢ (P ∨ Q)"}"#;
+    let response = exchange(|addr| {
+      format!(
+        "POST /entailment HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        addr,
+        body.len(),
+        body
+      )
+    });
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.contains("Content-Type: application/json"));
+    assert!(response.ends_with(r#"{"counterexample":null,"valid":true}"#));
+  }
+
+  #[test]
+  fn server_answers_errors() {
+    let response = exchange(|addr| format!("GET /check HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", addr));
+    assert!(response.starts_with("HTTP/1.1 405"));
+    assert!(response.ends_with(r#"{"error":"/check takes POST"}"#));
+  }
+
+  #[test]
+  fn server_ignores_query() {
+    let body = r#"{"formula": "P"}"#;
+    let response = exchange(|addr| {
+      format!(
+        "POST /formula?lang=en HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        addr,
+        body.len(),
+        body
+      )
+    });
+    assert!(response.starts_with("HTTP/1.1 200"));
+  }
+
+  #[test]
+  fn server_refuses_large_bodies() {
+    let body = "x".repeat(MAX_BODY as usize + 1);
+    let response = exchange(|addr| {
+      format!(
+        "POST /formula HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        addr,
+        body.len(),
+        body
+      )
+    });
+    assert!(response.starts_with("HTTP/1.1 413"));
+  }
+}
@@ -0,0 +1,17 @@
+use std::{env, process};
+
+use tiny_http::Server;
+
+/// `api-server [ADDRESS]`, listening on `127.0.0.1:8080` by default.
+fn main() {
+  let addr = env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8080".to_owned());
+  let server = match Server::http(&addr) {
+    Ok(server) => server,
+    Err(err) => {
+      eprintln!("cannot listen on {}: {}", addr, err);
+      process::exit(1);
+    }
+  };
+  eprintln!("listening on http://{}", addr);
+  api_server::serve(&server);
+}
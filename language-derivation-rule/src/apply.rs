@@ -0,0 +1,159 @@
+//! Forward application: the sentences a rule licenses from the lines it cites, for suggesting what
+//! to write on a line once its rule is written.
+
+use std::fmt;
+
+use language::ast::exp::Exp;
+
+/// Placeholder for a sentence the student chooses, as a sentence letter.
+pub const SENTENCE: &str = "φ";
+/// Placeholder for an individual the student chooses.
+pub const INDIVIDUAL: &str = "β";
+
+/// A sentence a rule application licenses, or a schema of them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Licensed {
+  Exp(Exp),
+  /// Any sentence may be written for the letter `SENTENCE`: `(P ∨ φ)` by `1 ∨I`.
+  AnySentence(Exp),
+  /// An individual is written for `INDIVIDUAL`: the instance of `∀E`, or the variable bound by `∀I` and
+  /// `∃I`.
+  AnyIndividual(Exp),
+}
+
+impl Licensed {
+  pub fn schema(&self) -> &Exp {
+    match self {
+      Licensed::Exp(exp) | Licensed::AnySentence(exp) | Licensed::AnyIndividual(exp) => exp,
+    }
+  }
+}
+
+impl fmt::Display for Licensed {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.schema())
+  }
+}
+
+pub(crate) fn sentence() -> Exp {
+  Exp::Atom {
+    predicate: SENTENCE.to_owned(),
+    individuals: vec![],
+  }
+}
+
+/// `lhs` and `rhs` joined by `join` in both orders, once if they are the same.
+pub(crate) fn both_orders(lhs: &Exp, rhs: &Exp, join: fn(Box<Exp>, Box<Exp>) -> Exp) -> Vec<Exp> {
+  let mut exps = vec![join(Box::new(lhs.clone()), Box::new(rhs.clone()))];
+  if lhs != rhs {
+    exps.push(join(Box::new(rhs.clone()), Box::new(lhs.clone())));
+  }
+  exps
+}
+
+/// `exp` generalized by `quantify` over each of its individuals in turn, bound as `INDIVIDUAL`.
+pub(crate) fn generalizations(exp: &Exp, quantify: fn(String, Box<Exp>) -> Exp) -> Vec<Licensed> {
+  exp
+    .free_variables()
+    .iter()
+    .map(|individual| {
+      Licensed::AnyIndividual(quantify(
+        INDIVIDUAL.to_owned(),
+        Box::new(exp.var_replaced(individual, INDIVIDUAL)),
+      ))
+    })
+    .collect()
+}
+
+/// The instance of the universal `exp` at `INDIVIDUAL`.
+pub(crate) fn instance(exp: &Exp) -> Vec<Licensed> {
+  match exp {
+    Exp::UnivGenr { variable, form } => vec![Licensed::AnyIndividual(form.var_replaced(variable, INDIVIDUAL))],
+    _ => vec![],
+  }
+}
+
+pub(crate) fn univ(variable: String, form: Box<Exp>) -> Exp {
+  Exp::UnivGenr { variable, form }
+}
+
+pub(crate) fn exist(variable: String, form: Box<Exp>) -> Exp {
+  Exp::ExistGenr { variable, form }
+}
+
+pub(crate) fn and(lhs: Box<Exp>, rhs: Box<Exp>) -> Exp {
+  Exp::And { lhs, rhs }
+}
+
+pub(crate) fn or(lhs: Box<Exp>, rhs: Box<Exp>) -> Exp {
+  Exp::Or { lhs, rhs }
+}
+
+pub(crate) fn iff(lhs: Box<Exp>, rhs: Box<Exp>) -> Exp {
+  Exp::Iff { lhs, rhs }
+}
+
+pub(crate) fn cond(antecedent: Box<Exp>, consequent: Box<Exp>) -> Exp {
+  Exp::Cond { antecedent, consequent }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    derived::{DerivedRule, Registry},
+    fixtures::rows,
+    rule_set::{Gentzen, System},
+    theorem::{Library, BASIC},
+  };
+  use rstest::rstest;
+
+  fn applied(system: System, derivation: &[(&str, &str)], lemmas: &Registry, theorems: &Library) -> Vec<String> {
+    system
+      .apply(&rows(derivation), derivation.len(), lemmas, theorems)
+      .iter()
+      .map(|licensed| licensed.to_string())
+      .collect()
+  }
+
+  #[rstest]
+  #[case(System::Gentzen, &[("(P → Q)", "P"), ("P", "P"), ("", "1, 2 →E")], &["Q"])]
+  #[case(System::Gentzen, &[("¬P", "P"), ("P", "P"), ("", "1, 2 →E")], &["⊥"])]
+  #[case(System::Gentzen, &[("(P & Q)", "P"), ("", "1 &E")], &["P", "Q"])]
+  #[case(System::Gentzen, &[("P", "P"), ("", "1 ∨I")], &["(P ∨ φ)", "(φ ∨ P)"])]
+  #[case(System::Gentzen, &[("P", "P"), ("Q", "1 &E"), ("", "1-2 →I")], &["(P → Q)"])]
+  #[case(System::Gentzen, &[("(P ↔ Q)", "P"), ("", "1 ↔E")], &["(P → Q)", "(Q → P)"])]
+  #[case(System::Gentzen, &[("⊥", "P"), ("", "1 ⊥")], &["φ"])]
+  #[case(System::Gentzen, &[("¬P", "P"), ("⊥", "P"), ("", "1-2 ¬E")], &["P"])]
+  #[case(System::Gentzen, &[("(x)(Fx → Gx)", "P"), ("", "1 ()E")], &["(Fβ → Gβ)"])]
+  #[case(System::Gentzen, &[("Rab", "P"), ("", "1 ∃I")], &["(∃β)Rβb", "(∃β)Raβ"])]
+  #[case(System::Gentzen, &[("P", "P"), ("", "2 &E")], &[])]
+  #[case(System::Lemmon, &[("(P → Q)", "A"), ("¬Q", "A"), ("", "1, 2 MTT")], &["¬P"])]
+  #[case(System::Lemmon, &[("¬¬P", "A"), ("", "1 DN")], &["P", "¬¬¬¬P"])]
+  #[case(System::Lemmon, &[("Fa", "A"), ("", "1 UG")], &["(β)Fβ"])]
+  fn apply(#[case] system: System, #[case] derivation: &[(&str, &str)], #[case] expected: &[&str]) {
+    assert_eq!(applied(system, derivation, &Registry::new(), &Library::new()), expected);
+  }
+
+  #[test]
+  fn apply_derived() {
+    let lemma = DerivedRule::from_derivation(
+      "MT",
+      &rows(&[
+        ("(P → Q)", "P"),
+        ("¬Q", "P"),
+        ("P", "P"),
+        ("Q", "1, 3 →E"),
+        ("⊥", "2, 4 →E"),
+        ("¬P", "3-5 ¬I"),
+      ]),
+      &Gentzen::default(),
+    )
+    .unwrap();
+    let lemmas = Registry::from_iter([lemma]);
+    let theorems: Library = BASIC.parse().unwrap();
+    let derivation = [("(¬R → (S & T))", "P"), ("¬(S & T)", "P"), ("", "1, 2 MT")];
+    assert_eq!(applied(System::Gentzen, &derivation, &lemmas, &theorems), ["¬¬R"]);
+    let derivation = [("", "TI (T4)")];
+    assert_eq!(applied(System::Gentzen, &derivation, &lemmas, &theorems), ["(P ∨ ¬P)"]);
+  }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+
+/// Rules of Lemmon's *Beginning Logic*. Discharged assumptions are cited by line, not by range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LemmonRule {
+  /// A
+  Assumption,
+  /// m, n MPP: from `A → B` (m) and `A` (n), `B`
+  Mpp(usize, usize),
+  /// m, n MTT: from `A → B` (m) and `¬B` (n), `¬A`
+  Mtt(usize, usize),
+  /// m DN: `A` from `¬¬A`, or `¬¬A` from `A`
+  Dn(usize),
+  /// m, n CP: from the assumption `A` (m) and `B` (n), `A → B`
+  Cp(usize, usize),
+  /// m, n &I
+  AndIntro(usize, usize),
+  /// m &E
+  AndExclude(usize),
+  /// m ∨I
+  OrIntro(usize),
+  /// k, l, m, n, o ∨E: from `A ∨ B` (k), the assumption `A` (l) to `C` (m) and the assumption `B` (n)
+  /// to `C` (o), `C`
+  OrExclude(usize, usize, usize, usize, usize),
+  /// m, n RAA: from the assumption `A` (m) and `B & ¬B` (n), `¬A`
+  Raa(usize, usize),
+  /// m UI: universal instantiation
+  Ui(usize),
+  /// m UG: universal generalization
+  Ug(usize),
+  /// m EG: existential generalization
+  Eg(usize),
+  /// k, l, m EI: from `(∃x)Fx` (k), the assumption `Fa` (l) and `C` (m), `C`
+  Ei(usize, usize, usize),
+}
+
+impl fmt::Display for LemmonRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LemmonRule::Assumption => write!(f, "A"),
+      LemmonRule::Mpp(m, n) => write!(f, "{}, {} MPP", m, n),
+      LemmonRule::Mtt(m, n) => write!(f, "{}, {} MTT", m, n),
+      LemmonRule::Dn(m) => write!(f, "{} DN", m),
+      LemmonRule::Cp(m, n) => write!(f, "{}, {} CP", m, n),
+      LemmonRule::AndIntro(m, n) => write!(f, "{}, {} &I", m, n),
+      LemmonRule::AndExclude(m) => write!(f, "{} &E", m),
+      LemmonRule::OrIntro(m) => write!(f, "{} ∨I", m),
+      LemmonRule::OrExclude(k, l, m, n, o) => write!(f, "{}, {}, {}, {}, {} ∨E", k, l, m, n, o),
+      LemmonRule::Raa(m, n) => write!(f, "{}, {} RAA", m, n),
+      LemmonRule::Ui(m) => write!(f, "{} UI", m),
+      LemmonRule::Ug(m) => write!(f, "{} UG", m),
+      LemmonRule::Eg(m) => write!(f, "{} EG", m),
+      LemmonRule::Ei(k, l, m) => write!(f, "{}, {}, {} EI", k, l, m),
+    }
+  }
+}
+
+impl LemmonRule {
+  /// Line numbers this rule refers to, in the order they are written.
+  pub fn cited_lines(&self) -> Vec<usize> {
+    match *self {
+      LemmonRule::Assumption => vec![],
+      LemmonRule::Dn(m)
+      | LemmonRule::AndExclude(m)
+      | LemmonRule::OrIntro(m)
+      | LemmonRule::Ui(m)
+      | LemmonRule::Ug(m)
+      | LemmonRule::Eg(m) => vec![m],
+      LemmonRule::Mpp(m, n)
+      | LemmonRule::Mtt(m, n)
+      | LemmonRule::Cp(m, n)
+      | LemmonRule::AndIntro(m, n)
+      | LemmonRule::Raa(m, n) => vec![m, n],
+      LemmonRule::OrExclude(k, l, m, n, o) => vec![k, l, m, n, o],
+      LemmonRule::Ei(k, l, m) => vec![k, l, m],
+    }
+  }
+
+  /// The same rule citing `renumber(k)` for each line `k` it cites.
+  pub fn renumbered(&self, renumber: impl Fn(usize) -> usize) -> LemmonRule {
+    let f = renumber;
+    match *self {
+      LemmonRule::Assumption => LemmonRule::Assumption,
+      LemmonRule::Mpp(m, n) => LemmonRule::Mpp(f(m), f(n)),
+      LemmonRule::Mtt(m, n) => LemmonRule::Mtt(f(m), f(n)),
+      LemmonRule::Dn(m) => LemmonRule::Dn(f(m)),
+      LemmonRule::Cp(m, n) => LemmonRule::Cp(f(m), f(n)),
+      LemmonRule::AndIntro(m, n) => LemmonRule::AndIntro(f(m), f(n)),
+      LemmonRule::AndExclude(m) => LemmonRule::AndExclude(f(m)),
+      LemmonRule::OrIntro(m) => LemmonRule::OrIntro(f(m)),
+      LemmonRule::OrExclude(k, l, m, n, o) => LemmonRule::OrExclude(f(k), f(l), f(m), f(n), f(o)),
+      LemmonRule::Raa(m, n) => LemmonRule::Raa(f(m), f(n)),
+      LemmonRule::Ui(m) => LemmonRule::Ui(f(m)),
+      LemmonRule::Ug(m) => LemmonRule::Ug(f(m)),
+      LemmonRule::Eg(m) => LemmonRule::Eg(f(m)),
+      LemmonRule::Ei(k, l, m) => LemmonRule::Ei(f(k), f(l), f(m)),
+    }
+  }
+
+  /// Assumption lines this rule discharges.
+  pub fn discharged_lines(&self) -> Vec<usize> {
+    match *self {
+      LemmonRule::Cp(m, _) | LemmonRule::Raa(m, _) | LemmonRule::Ei(_, m, _) => vec![m],
+      LemmonRule::OrExclude(_, l, _, n, _) => vec![l, n],
+      _ => vec![],
+    }
+  }
+}
@@ -1 +1,2 @@
+pub mod lemmon_rule;
 pub mod rule;
@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Rule {
   Premise,
@@ -16,31 +18,144 @@ pub enum Rule {
   UnivQuntExclude(usize),
   ExisQuntIntro(usize),
   ExisQuntExclude(usize, (usize, usize)),
+  /// A lemma registered in `derived::Registry`, citing a line for each of its premises.
+  Derived(String, Vec<usize>),
+  /// `TI (T12)` or `1, 2 SI (T7)`: a theorem or sequent of `theorem::Library`, citing a line for each of
+  /// its premises.
+  SequentIntro(Vec<usize>, String),
 }
 
-impl Rule {
-  pub fn to_string(&self) -> String {
+impl fmt::Display for Rule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
-      Rule::Premise => format!("P"),
-      Rule::AndIntro(dep1, dep2) => format!("{}, {} &I", dep1, dep2),
-      Rule::AndExclude(dep1) => format!("{} &E", dep1),
-      Rule::OrIntro(dep1, Some(dep2)) => format!("{}, {} ∨I", dep1, dep2),
-      Rule::OrIntro(dep1, None) => format!("{} ∨I", dep1),
+      Rule::Premise => write!(f, "P"),
+      Rule::AndIntro(dep1, dep2) => write!(f, "{}, {} &I", dep1, dep2),
+      Rule::AndExclude(dep1) => write!(f, "{} &E", dep1),
+      Rule::OrIntro(dep1, Some(dep2)) => write!(f, "{}, {} ∨I", dep1, dep2),
+      Rule::OrIntro(dep1, None) => write!(f, "{} ∨I", dep1),
       Rule::OrExclude(dep1, (dep2b, dep2e), (dep3b, dep3e)) => {
-        format!("{}, {}-{}, {}-{} ∨E", dep1, dep2b, dep2e, dep3b, dep3e)
+        write!(f, "{}, {}-{}, {}-{} ∨E", dep1, dep2b, dep2e, dep3b, dep3e)
+      }
+      Rule::IfIntro((Some(dep1b), dep1e)) => write!(f, "{}-{} →I", dep1b, dep1e),
+      Rule::IfIntro((None, dep1)) => write!(f, "{} →I", dep1),
+      Rule::IfExclude(dep1, dep2) => write!(f, "{}, {} →E", dep1, dep2),
+      Rule::IffIntro(dep1, dep2) => write!(f, "{}, {} ↔I", dep1, dep2),
+      Rule::IffExclude(dep1) => write!(f, "{} ↔E", dep1),
+      Rule::Falsum(dep1) => write!(f, "{} ⊥", dep1),
+      Rule::NegIntro((dep1b, dep1e)) => write!(f, "{}-{} ¬I", dep1b, dep1e),
+      Rule::NegExclude((dep1b, dep1e)) => write!(f, "{}-{} ¬E", dep1b, dep1e),
+      Rule::UnivQuntIntro(dep1) => write!(f, "{} ()I", dep1),
+      Rule::UnivQuntExclude(dep1) => write!(f, "{} ()E", dep1),
+      Rule::ExisQuntIntro(dep1) => write!(f, "{} ∃I", dep1),
+      Rule::ExisQuntExclude(dep1, (dep2b, dep2e)) => write!(f, "{}, {}-{} ∃E", dep1, dep2b, dep2e),
+      Rule::SequentIntro(deps, name) if deps.is_empty() => write!(f, "TI ({})", name),
+      Rule::SequentIntro(deps, name) => {
+        let deps: Vec<String> = deps.iter().map(|dep| dep.to_string()).collect();
+        write!(f, "{} SI ({})", deps.join(", "), name)
       }
-      Rule::IfIntro((Some(dep1b), dep1e)) => format!("{}-{} →I", dep1b, dep1e),
-      Rule::IfIntro((None, dep1)) => format!("{} →I", dep1),
-      Rule::IfExclude(dep1, dep2) => format!("{}, {} →E", dep1, dep2),
-      Rule::IffIntro(dep1, dep2) => format!("{}, {} ↔I", dep1, dep2),
-      Rule::IffExclude(dep1) => format!("{} ↔E", dep1),
-      Rule::Falsum(dep1) => format!("{} ⊥", dep1),
-      Rule::NegIntro((dep1b, dep1e)) => format!("{}-{} ¬I", dep1b, dep1e),
-      Rule::NegExclude((dep1b, dep1e)) => format!("{}-{} ¬E", dep1b, dep1e),
-      Rule::UnivQuntIntro(dep1) => format!("{} ()I", dep1),
-      Rule::UnivQuntExclude(dep1) => format!("{} ()E", dep1),
-      Rule::ExisQuntIntro(dep1) => format!("{} ∃I", dep1),
-      Rule::ExisQuntExclude(dep1, (dep2b, dep2e)) => format!("{}, {}-{} ∃E", dep1, dep2b, dep2e),
+      Rule::Derived(name, deps) if deps.is_empty() => write!(f, "{}", name),
+      Rule::Derived(name, deps) => {
+        let deps: Vec<String> = deps.iter().map(|dep| dep.to_string()).collect();
+        write!(f, "{} {}", deps.join(", "), name)
+      }
+    }
+  }
+}
+
+impl Rule {
+  /// Line numbers this rule refers to, in the order they are written. Both ends of a range are
+  /// included.
+  pub fn cited_lines(&self) -> Vec<usize> {
+    match *self {
+      Rule::Derived(_, ref deps) | Rule::SequentIntro(ref deps, _) => deps.clone(),
+      Rule::Premise => vec![],
+      Rule::AndExclude(k)
+      | Rule::OrIntro(k, None)
+      | Rule::IfIntro((None, k))
+      | Rule::IffExclude(k)
+      | Rule::Falsum(k)
+      | Rule::UnivQuntIntro(k)
+      | Rule::UnivQuntExclude(k)
+      | Rule::ExisQuntIntro(k) => vec![k],
+      Rule::AndIntro(k, l)
+      | Rule::OrIntro(k, Some(l))
+      | Rule::IfIntro((Some(k), l))
+      | Rule::IfExclude(k, l)
+      | Rule::IffIntro(k, l)
+      | Rule::NegIntro((k, l))
+      | Rule::NegExclude((k, l)) => vec![k, l],
+      Rule::OrExclude(k, (l0, l1), (m0, m1)) => vec![k, l0, l1, m0, m1],
+      Rule::ExisQuntExclude(k, (l0, l1)) => vec![k, l0, l1],
+    }
+  }
+
+  /// The same rule citing `renumber(k)` for each line `k` it cites.
+  pub fn renumbered(&self, renumber: impl Fn(usize) -> usize) -> Rule {
+    let f = &renumber;
+    let range = |(k0, k1): (usize, usize)| (f(k0), f(k1));
+    match *self {
+      Rule::Premise => Rule::Premise,
+      Rule::AndIntro(k, l) => Rule::AndIntro(f(k), f(l)),
+      Rule::AndExclude(k) => Rule::AndExclude(f(k)),
+      Rule::OrIntro(k, l) => Rule::OrIntro(f(k), l.map(f)),
+      Rule::OrExclude(k, l, m) => Rule::OrExclude(f(k), range(l), range(m)),
+      Rule::IfIntro((k0, k1)) => Rule::IfIntro((k0.map(f), f(k1))),
+      Rule::IfExclude(k, l) => Rule::IfExclude(f(k), f(l)),
+      Rule::IffIntro(k, l) => Rule::IffIntro(f(k), f(l)),
+      Rule::IffExclude(k) => Rule::IffExclude(f(k)),
+      Rule::Falsum(k) => Rule::Falsum(f(k)),
+      Rule::NegIntro(k) => Rule::NegIntro(range(k)),
+      Rule::NegExclude(k) => Rule::NegExclude(range(k)),
+      Rule::UnivQuntIntro(k) => Rule::UnivQuntIntro(f(k)),
+      Rule::UnivQuntExclude(k) => Rule::UnivQuntExclude(f(k)),
+      Rule::ExisQuntIntro(k) => Rule::ExisQuntIntro(f(k)),
+      Rule::ExisQuntExclude(k, l) => Rule::ExisQuntExclude(f(k), range(l)),
+      Rule::Derived(ref name, ref deps) => Rule::Derived(name.clone(), deps.iter().map(|&k| f(k)).collect()),
+      Rule::SequentIntro(ref deps, ref name) => Rule::SequentIntro(deps.iter().map(|&k| f(k)).collect(), name.clone()),
     }
   }
+
+  /// Ranges whose first line is an assumption this rule discharges.
+  pub fn discharged_ranges(&self) -> Vec<(usize, usize)> {
+    match *self {
+      Rule::IfIntro((Some(k0), k1)) | Rule::NegIntro((k0, k1)) | Rule::NegExclude((k0, k1)) => vec![(k0, k1)],
+      Rule::OrExclude(_, l, m) => vec![l, m],
+      Rule::ExisQuntExclude(_, l) => vec![l],
+      _ => vec![],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rule_cited_lines() {
+    assert_eq!(Rule::Premise.cited_lines(), Vec::<usize>::new());
+    assert_eq!(Rule::IfIntro((None, 3)).cited_lines(), vec![3]);
+    assert_eq!(Rule::IfIntro((Some(2), 3)).cited_lines(), vec![2, 3]);
+    assert_eq!(Rule::OrExclude(1, (2, 4), (5, 6)).cited_lines(), vec![1, 2, 4, 5, 6]);
+    assert_eq!(Rule::Derived("MT".to_owned(), vec![3, 1]).cited_lines(), vec![3, 1]);
+  }
+
+  #[test]
+  fn rule_renumbered() {
+    assert_eq!(
+      Rule::OrExclude(1, (2, 4), (5, 6)).renumbered(|k| k * 10),
+      Rule::OrExclude(10, (20, 40), (50, 60))
+    );
+    assert_eq!(Rule::IfIntro((None, 3)).renumbered(|k| k - 1), Rule::IfIntro((None, 2)));
+  }
+
+  #[test]
+  fn rule_discharged_ranges() {
+    assert_eq!(Rule::IfIntro((None, 3)).discharged_ranges(), vec![]);
+    assert_eq!(Rule::IfIntro((Some(2), 3)).discharged_ranges(), vec![(2, 3)]);
+    assert_eq!(
+      Rule::OrExclude(1, (2, 4), (5, 6)).discharged_ranges(),
+      vec![(2, 4), (5, 6)]
+    );
+    assert_eq!(Rule::ExisQuntExclude(1, (2, 4)).discharged_ranges(), vec![(2, 4)]);
+  }
 }
@@ -0,0 +1,139 @@
+use language::ast::exp::Exp;
+
+use crate::{
+  citation::{self, CitationError},
+  dependency::{dependencies, RowDependency},
+  rule_set::{parse_lines, Line, RuleSet},
+  scope::{scopes, Scope},
+};
+
+/// Everything known about a derivation after checking it. Each list has an entry per line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+  pub deps_list: Vec<RowDependency>,
+  pub scopes: Vec<Scope>,
+  pub citation_errors: Vec<Vec<CitationError>>,
+  /// Whether each line follows by its rule. Lines that do not parse or cite wrongly are invalid.
+  pub validity: Vec<bool>,
+}
+
+/// Check every line of a derivation in the given rule set.
+pub fn check<R: RuleSet>(rule_set: &R, lines: &[Line<R::Rule>]) -> Report {
+  let rules: Vec<Option<R::Rule>> = lines.iter().map(|line| line.rule.clone()).collect();
+  let deps_list = dependencies(rule_set, &rules);
+  let scopes = scopes(rule_set, &rules);
+  let citation_errors = citation::check_all(rule_set, &rules);
+  let validity = lines
+    .iter()
+    .zip(citation_errors.iter())
+    .enumerate()
+    .map(|(idx, (line, errors))| match line {
+      Line {
+        exp: Some(exp),
+        rule: Some(rule),
+      } if errors.is_empty() => rule_set.is_valid(idx + 1, exp, rule, lines, &deps_list),
+      _ => false,
+    })
+    .collect();
+
+  Report {
+    deps_list,
+    scopes,
+    citation_errors,
+    validity,
+  }
+}
+
+/// Same as `check`, for sentences and unparsed rules.
+pub fn check_rows<R: RuleSet>(rule_set: &R, rows: &[(Option<Exp>, &str)]) -> Report {
+  check(rule_set, &parse_lines(rule_set, rows))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    derived::Registry,
+    fixtures::rows,
+    rule_set::{Profile, System},
+    theorem::Library,
+  };
+  use rstest::rstest;
+
+  #[rstest]
+  #[case(System::Gentzen, &[
+    ("(P → Q)", "P"),
+    ("¬Q", "P"),
+    ("P", "P"),
+    ("Q", "1, 3 →E"),
+    ("⊥", "2, 4 →E"),
+    ("¬P", "3-5 ¬I"),
+    ("(¬Q → ¬P)", "2-6 →I"),
+  ])]
+  #[case(System::Lemmon, &[
+    ("(P → Q)", "A"),
+    ("¬Q", "A"),
+    ("¬P", "1, 2 MTT"),
+    ("(¬Q → ¬P)", "2, 3 CP"),
+  ])]
+  #[case(System::Lemmon, &[
+    ("(P → Q)", "A"),
+    ("¬Q", "A"),
+    ("P", "A"),
+    ("Q", "1, 3 MPP"),
+    ("(Q & ¬Q)", "4, 2 &I"),
+    ("¬P", "3, 5 RAA"),
+    ("¬¬¬P", "6 DN"),
+  ])]
+  #[case(System::Lemmon, &[
+    ("(∃x)Fx", "A"),
+    ("(x)(Fx → Gx)", "A"),
+    ("Fa", "A"),
+    ("(Fa → Ga)", "2 UI"),
+    ("Ga", "4, 3 MPP"),
+    ("(∃x)Gx", "5 EG"),
+    ("(∃x)Gx", "1, 3, 6 EI"),
+  ])]
+  fn check_valid(#[case] system: System, #[case] derivation: &[(&str, &str)]) {
+    let report = system.check(&rows(derivation), &Registry::new(), &Library::new(), Profile::Classical);
+    assert!(
+      report.validity.iter().all(|&is_valid| is_valid),
+      "{:?}",
+      report.validity
+    );
+  }
+
+  #[rstest]
+  #[case(System::Gentzen, &[("P", "P"), ("(P → Q)", "P"), ("Q", "1, 2 MPP")])]
+  #[case(System::Lemmon, &[("P", "A"), ("(P → Q)", "A"), ("Q", "1, 2 MPP")])]
+  #[case(System::Lemmon, &[("P", "A"), ("Q", "1 DN")])]
+  #[case(System::Lemmon, &[("Fa", "A"), ("(x)Fx", "1 UG")])]
+  #[case(System::Lemmon, &[("P", "A"), ("P", "1 &E"), ("(P → P)", "2, 2 CP")])]
+  fn check_invalid_last(#[case] system: System, #[case] derivation: &[(&str, &str)]) {
+    let report = system.check(&rows(derivation), &Registry::new(), &Library::new(), Profile::Classical);
+    assert_eq!(report.validity.last(), Some(&false));
+  }
+
+  #[rstest]
+  #[case(System::Gentzen, Profile::Classical, true)]
+  #[case(System::Gentzen, Profile::Intuitionistic, false)]
+  #[case(System::Lemmon, Profile::Classical, true)]
+  #[case(System::Lemmon, Profile::Minimal, false)]
+  fn check_double_negation(#[case] system: System, #[case] profile: Profile, #[case] expected: bool) {
+    let derivation = match system {
+      System::Gentzen => vec![("¬¬P", "P"), ("¬P", "P"), ("⊥", "1, 2 →E"), ("P", "2-3 ¬E")],
+      System::Lemmon => vec![("¬¬P", "A"), ("P", "1 DN")],
+      System::Spec(_) => unreachable!(),
+    };
+    let report = system.check(&rows(&derivation), &Registry::new(), &Library::new(), profile);
+    assert_eq!(report.validity.last(), Some(&expected));
+  }
+
+  #[rstest]
+  #[case(Profile::Intuitionistic, true)]
+  #[case(Profile::Minimal, false)]
+  fn check_ex_falso(#[case] profile: Profile, #[case] expected: bool) {
+    let derivation = [("P", "P"), ("¬P", "P"), ("⊥", "2, 1 →E"), ("Q", "3 ⊥")];
+    let report = System::Gentzen.check(&rows(&derivation), &Registry::new(), &Library::new(), profile);
+    assert_eq!(report.validity, vec![true, true, true, expected]);
+  }
+}
@@ -0,0 +1,269 @@
+use std::{error, fmt};
+
+use crate::{
+  rule_set::{Discharge, RuleSet},
+  scope::{scopes, Scope},
+};
+
+/// Why a line cannot cite what its rule refers to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CitationError {
+  /// The cited line does not exist.
+  OutOfRange { cited: usize },
+  /// A line cites itself.
+  SelfCitation,
+  /// A line cites a line after it.
+  Forward { cited: usize },
+  /// A range ends before it starts.
+  InvalidRange { start: usize, end: usize },
+  /// A discharged line, or the first line of a discharged range, is not an assumption.
+  NotAnAssumption { discharged: usize },
+  /// The cited line belongs to a scope that ends before the citing line; `scope` is the innermost.
+  ClosedScope { cited: usize, scope: Scope },
+  /// The discharged range `start-end` contains the assumption on line `assumption`, but its subproof
+  /// goes on past the end of the range.
+  CrossesScope {
+    start: usize,
+    end: usize,
+    assumption: usize,
+  },
+}
+
+impl fmt::Display for CitationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CitationError::OutOfRange { cited } => write!(f, "line {} does not exist", cited),
+      CitationError::SelfCitation => write!(f, "a line cannot cite itself"),
+      CitationError::Forward { cited } => write!(f, "line {} comes after this line", cited),
+      CitationError::InvalidRange { start, end } => write!(f, "range {}-{} ends before it starts", start, end),
+      CitationError::NotAnAssumption { discharged } => {
+        write!(f, "line {} is discharged but is not an assumption", discharged)
+      }
+      CitationError::ClosedScope { cited, scope } => write!(
+        f,
+        "line {} is inside the subproof {}-{}, which ends before this line",
+        cited, scope.start, scope.end
+      ),
+      CitationError::CrossesScope { start, end, assumption } => write!(
+        f,
+        "range {}-{} contains the assumption on line {} but not the end of its subproof",
+        start, end, assumption
+      ),
+    }
+  }
+}
+
+impl error::Error for CitationError {}
+
+/// Check what the rule on line `num` cites.
+pub fn check<R: RuleSet>(
+  rule_set: &R,
+  num: usize,
+  rule: &R::Rule,
+  rules: &[Option<R::Rule>],
+  scopes: &[Scope],
+) -> Vec<CitationError> {
+  let mut errors = vec![];
+  let cited_lines = rule_set.cited_lines(rule);
+
+  for &cited in cited_lines.iter() {
+    let error = if cited == 0 || cited > rules.len() {
+      CitationError::OutOfRange { cited }
+    } else if cited == num {
+      CitationError::SelfCitation
+    } else if cited > num {
+      CitationError::Forward { cited }
+    } else {
+      continue;
+    };
+    if !errors.contains(&error) {
+      errors.push(error);
+    }
+  }
+
+  let discharges = rule_set.discharges(rule);
+  for discharge in discharges.iter() {
+    let start = discharge.assumption();
+    if let Discharge::Range(start, end) = *discharge {
+      if start > end {
+        errors.push(CitationError::InvalidRange { start, end });
+        continue;
+      }
+    }
+    if (1..num).contains(&start) && !matches!(rules.get(start - 1), Some(Some(rule)) if rule_set.is_assumption(rule)) {
+      errors.push(CitationError::NotAnAssumption { discharged: start });
+    }
+    // every assumption discharged inside the range must be discharged within it as well
+    if let Discharge::Range(start, end) = *discharge {
+      for scope in scopes
+        .iter()
+        .filter(|scope| start < scope.start && scope.start <= end && end < scope.end)
+      {
+        errors.push(CitationError::CrossesScope {
+          start,
+          end,
+          assumption: scope.start,
+        });
+      }
+    }
+  }
+
+  for cited in cited_lines {
+    // scopes are ordered outer first, so the last one containing the line is the innermost
+    let closed = scopes.iter().rev().find(|scope| {
+      scope.end < num
+        && scope.contains(cited)
+        && !(scope.closed_by == num && discharges.contains(&Discharge::Range(scope.start, scope.end)))
+    });
+    if let Some(scope) = closed {
+      errors.push(CitationError::ClosedScope {
+        cited,
+        scope: scope.clone(),
+      });
+    }
+  }
+
+  errors
+}
+
+/// Citation errors of every line. Lines whose rule could not be parsed have none.
+pub fn check_all<R: RuleSet>(rule_set: &R, rules: &[Option<R::Rule>]) -> Vec<Vec<CitationError>> {
+  let scopes = scopes(rule_set, rules);
+  rules
+    .iter()
+    .enumerate()
+    .map(|(idx, rule)| match rule {
+      Some(rule) => check(rule_set, idx + 1, rule, rules, &scopes),
+      None => vec![],
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    ast::{lemmon_rule::LemmonRule, rule::Rule},
+    rule_set::{Gentzen, Lemmon},
+  };
+
+  fn rules(rules: &[Rule]) -> Vec<Option<Rule>> {
+    rules.iter().cloned().map(Some).collect()
+  }
+
+  #[test]
+  fn check_all_valid() {
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::Premise,
+      Rule::AndIntro(1, 2),
+      Rule::IfIntro((Some(2), 3)),
+    ]);
+    assert_eq!(
+      check_all(&Gentzen::default(), &rules),
+      vec![vec![], vec![], vec![], vec![]]
+    );
+  }
+
+  #[test]
+  fn check_all_order() {
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::AndIntro(2, 3),
+      Rule::AndExclude(0),
+      Rule::OrIntro(1, Some(9)),
+    ]);
+    assert_eq!(
+      check_all(&Gentzen::default(), &rules),
+      vec![
+        vec![],
+        vec![CitationError::SelfCitation, CitationError::Forward { cited: 3 }],
+        vec![CitationError::OutOfRange { cited: 0 }],
+        vec![CitationError::OutOfRange { cited: 9 }],
+      ]
+    );
+  }
+
+  #[test]
+  fn check_all_discharge() {
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::AndExclude(1),
+      Rule::IfIntro((Some(2), 2)),
+      Rule::NegIntro((3, 1)),
+    ]);
+    assert_eq!(
+      check_all(&Gentzen::default(), &rules),
+      vec![
+        vec![],
+        vec![],
+        vec![CitationError::NotAnAssumption { discharged: 2 }],
+        vec![CitationError::InvalidRange { start: 3, end: 1 }],
+      ]
+    );
+  }
+
+  #[test]
+  fn check_all_crossed_scope() {
+    // the subproof 1-3 ends inside the subproof 2-4
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::Premise,
+      Rule::AndIntro(1, 2),
+      Rule::IfIntro((Some(1), 3)),
+      Rule::IfIntro((Some(2), 4)),
+    ]);
+    assert_eq!(
+      check_all(&Gentzen::default(), &rules)[3],
+      vec![CitationError::CrossesScope {
+        start: 1,
+        end: 3,
+        assumption: 2
+      }]
+    );
+  }
+
+  #[test]
+  fn check_all_closed_scope() {
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::Premise,
+      Rule::IfIntro((Some(2), 2)),
+      Rule::AndIntro(1, 2),
+    ]);
+    assert_eq!(
+      check_all(&Gentzen::default(), &rules)[3],
+      vec![CitationError::ClosedScope {
+        cited: 2,
+        scope: Scope {
+          start: 2,
+          end: 2,
+          closed_by: 3
+        }
+      }]
+    );
+  }
+
+  #[test]
+  fn check_all_lemmon() {
+    let rules = vec![
+      Some(LemmonRule::Assumption),
+      Some(LemmonRule::Assumption),
+      Some(LemmonRule::Mpp(1, 2)),
+      Some(LemmonRule::Cp(2, 3)),
+      Some(LemmonRule::Cp(3, 4)),
+      Some(LemmonRule::AndIntro(2, 3)),
+    ];
+    assert_eq!(
+      check_all(&Lemmon::default(), &rules),
+      vec![
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![CitationError::NotAnAssumption { discharged: 3 }],
+        vec![],
+      ]
+    );
+  }
+}
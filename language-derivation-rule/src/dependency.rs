@@ -0,0 +1,214 @@
+use std::{
+  collections::HashSet,
+  ops::{BitOr, Sub},
+};
+
+use crate::{
+  citation,
+  rule_set::RuleSet,
+  scope::{scopes, Scope},
+};
+
+/// The assumption lines a line depends on. Incomplete when it could not be computed, e.g. the line
+/// or one of the lines it builds on has a syntax or citation error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowDependency {
+  pub is_complete: bool,
+  pub nums: HashSet<usize>,
+}
+
+impl RowDependency {
+  pub fn new() -> Self {
+    RowDependency {
+      is_complete: true,
+      nums: HashSet::new(),
+    }
+  }
+
+  pub fn new_incomplete() -> Self {
+    RowDependency {
+      is_complete: false,
+      nums: HashSet::new(),
+    }
+  }
+
+  pub fn init_from<const N: usize>(nums: [usize; N]) -> Self {
+    RowDependency {
+      is_complete: true,
+      nums: HashSet::from(nums),
+    }
+  }
+}
+
+impl Default for RowDependency {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl BitOr<&RowDependency> for RowDependency {
+  type Output = RowDependency;
+
+  fn bitor(self, rhs: &RowDependency) -> Self::Output {
+    RowDependency {
+      is_complete: self.is_complete && rhs.is_complete,
+      nums: &self.nums | &rhs.nums,
+    }
+  }
+}
+
+impl BitOr<Option<&RowDependency>> for RowDependency {
+  type Output = RowDependency;
+
+  fn bitor(self, rhs: Option<&RowDependency>) -> Self::Output {
+    match rhs {
+      Some(rhs) => self | rhs,
+      None => self,
+    }
+  }
+}
+
+impl Sub<usize> for RowDependency {
+  type Output = RowDependency;
+
+  fn sub(self, rhs: usize) -> Self::Output {
+    let mut nums = self.nums;
+    nums.remove(&rhs);
+    RowDependency {
+      is_complete: self.is_complete,
+      nums,
+    }
+  }
+}
+
+impl Sub<Option<usize>> for RowDependency {
+  type Output = RowDependency;
+
+  fn sub(self, rhs: Option<usize>) -> Self::Output {
+    match rhs {
+      Some(rhs) => self - rhs,
+      None => self,
+    }
+  }
+}
+
+/// Dependencies of every line.
+///
+/// A line with citation errors (see `citation::check`) gets an incomplete dependency, and so does
+/// every line built on it.
+pub fn dependencies<R: RuleSet>(rule_set: &R, rules: &[Option<R::Rule>]) -> Vec<RowDependency> {
+  let scopes = scopes(rule_set, rules);
+  rules.iter().enumerate().fold(vec![], |mut acc, (idx, rule)| {
+    let dep = match rule {
+      Some(rule) => dependency(rule_set, idx + 1, rule, rules, &scopes, &acc),
+      None => RowDependency::new_incomplete(),
+    };
+    acc.push(dep);
+    acc
+  })
+}
+
+fn dependency<R: RuleSet>(
+  rule_set: &R,
+  num: usize,
+  rule: &R::Rule,
+  rules: &[Option<R::Rule>],
+  scopes: &[Scope],
+  acc: &[RowDependency],
+) -> RowDependency {
+  if !citation::check(rule_set, num, rule, rules, scopes).is_empty() {
+    return RowDependency::new_incomplete();
+  }
+  // citations are checked above, so every cited line is in `acc`
+  rule_set.dependency(num, rule, acc)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    ast::{lemmon_rule::LemmonRule, rule::Rule},
+    rule_set::{Gentzen, Lemmon},
+  };
+
+  fn rules(rules: &[Rule]) -> Vec<Option<Rule>> {
+    rules.iter().cloned().map(Some).collect()
+  }
+
+  #[test]
+  fn dependencies_valid() {
+    let rules = rules(&[
+      Rule::Premise,
+      Rule::Premise,
+      Rule::OrIntro(1, Some(2)),
+      Rule::IfIntro((Some(2), 3)),
+      Rule::OrIntro(4, None),
+    ]);
+    assert_eq!(
+      dependencies(&Gentzen::default(), &rules),
+      vec![
+        RowDependency::init_from([1]),
+        RowDependency::init_from([2]),
+        RowDependency::init_from([1, 2]),
+        RowDependency::init_from([1]),
+        RowDependency::init_from([1]),
+      ]
+    );
+  }
+
+  #[test]
+  fn dependencies_incomplete() {
+    let rules = vec![
+      Some(Rule::Premise),
+      None,
+      Some(Rule::AndIntro(1, 2)),
+      Some(Rule::AndExclude(5)),
+      Some(Rule::AndExclude(1)),
+    ];
+    assert_eq!(
+      dependencies(&Gentzen::default(), &rules),
+      vec![
+        RowDependency::init_from([1]),
+        RowDependency::new_incomplete(),
+        RowDependency {
+          is_complete: false,
+          nums: HashSet::from([1]),
+        },
+        RowDependency::new_incomplete(),
+        RowDependency::init_from([1]),
+      ]
+    );
+  }
+
+  #[test]
+  fn dependencies_lemmon() {
+    // 1 (P ∨ Q)  A
+    // 2 P        A
+    // 3 (Q ∨ P)  2 ∨I
+    // 4 Q        A
+    // 5 (Q ∨ P)  4 ∨I
+    // 6 (Q ∨ P)  1, 2, 3, 4, 5 ∨E
+    // 7 ...      1, 6 CP
+    let rules = vec![
+      Some(LemmonRule::Assumption),
+      Some(LemmonRule::Assumption),
+      Some(LemmonRule::OrIntro(2)),
+      Some(LemmonRule::Assumption),
+      Some(LemmonRule::OrIntro(4)),
+      Some(LemmonRule::OrExclude(1, 2, 3, 4, 5)),
+      Some(LemmonRule::Cp(1, 6)),
+    ];
+    assert_eq!(
+      dependencies(&Lemmon::default(), &rules),
+      vec![
+        RowDependency::init_from([1]),
+        RowDependency::init_from([2]),
+        RowDependency::init_from([2]),
+        RowDependency::init_from([4]),
+        RowDependency::init_from([4]),
+        RowDependency::init_from([1]),
+        RowDependency::new(),
+      ]
+    );
+  }
+}
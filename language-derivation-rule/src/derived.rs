@@ -0,0 +1,395 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  error, fmt,
+};
+
+use language::ast::{exp::Exp, sequent::Sequent};
+
+use crate::{
+  check::check_rows,
+  rule_set::{Gentzen, Profile},
+};
+
+/// A lemma proven once and cited afterwards as a rule, e.g. `3, 1 MT`. Its sentence letters are
+/// metavariables: any sentences may be substituted for them uniformly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedRule {
+  pub name: String,
+  pub sequent: Sequent,
+  /// The weakest logic its derivation is valid in.
+  pub profile: Profile,
+}
+
+/// Why a derivation cannot be saved as a derived rule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeriveError {
+  /// Names start with a letter, are alphanumeric and are not `P`.
+  InvalidName,
+  Empty,
+  InvalidLine {
+    num: usize,
+  },
+  IncompleteDependency {
+    num: usize,
+  },
+}
+
+impl fmt::Display for DeriveError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DeriveError::InvalidName => write!(f, "a rule name must be alphanumeric, start with a letter and not be P"),
+      DeriveError::Empty => write!(f, "the derivation is empty"),
+      DeriveError::InvalidLine { num } => write!(f, "line {} is not valid", num),
+      DeriveError::IncompleteDependency { num } => write!(f, "the dependency of line {} is incomplete", num),
+    }
+  }
+}
+
+impl error::Error for DeriveError {}
+
+impl fmt::Display for DerivedRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.name, self.sequent)
+  }
+}
+
+impl DerivedRule {
+  /// The lemma a valid derivation proves: the assumptions its last line depends on, in line order,
+  /// entail its last line. The lemmas, theorems and profile of `gentzen` may be used.
+  pub fn from_derivation(name: &str, rows: &[(Option<Exp>, &str)], gentzen: &Gentzen) -> Result<Self, DeriveError> {
+    let mut chars = name.chars();
+    if !chars.next().is_some_and(|c| c.is_alphabetic()) || !chars.all(|c| c.is_alphanumeric()) || name == "P" {
+      return Err(DeriveError::InvalidName);
+    }
+
+    let report = check_rows(gentzen, rows);
+    if let Some(idx) = report.validity.iter().position(|&is_valid| !is_valid) {
+      return Err(DeriveError::InvalidLine { num: idx + 1 });
+    }
+    if let Some(idx) = report.deps_list.iter().position(|deps| !deps.is_complete) {
+      return Err(DeriveError::IncompleteDependency { num: idx + 1 });
+    }
+    let (conclusion, deps) = match (rows.last(), report.deps_list.last()) {
+      (Some((Some(conclusion), _)), Some(deps)) => (conclusion, deps),
+      _ => return Err(DeriveError::Empty),
+    };
+    let mut nums: Vec<usize> = deps.nums.iter().copied().collect();
+    nums.sort_unstable();
+    let profile = Profile::ALL
+      .into_iter()
+      .filter(|&profile| gentzen.profile.allows(profile))
+      .find(|&profile| {
+        let gentzen = Gentzen {
+          profile,
+          ..gentzen.clone()
+        };
+        check_rows(&gentzen, rows).validity.iter().all(|&is_valid| is_valid)
+      })
+      .unwrap_or(gentzen.profile);
+    let premises = nums
+      .into_iter()
+      .filter_map(|num| rows.get(num - 1).and_then(|(exp, _)| exp.clone()))
+      .collect();
+
+    Ok(DerivedRule {
+      name: name.to_owned(),
+      sequent: Sequent {
+        premises,
+        conclusion: conclusion.clone(),
+      },
+      profile,
+    })
+  }
+
+  /// Whether `exp` follows from `cited`, one line per premise in order, by substituting sentences for
+  /// the sentence letters of the lemma.
+  pub fn is_instance(&self, cited: &[&Exp], exp: &Exp) -> bool {
+    is_substitution_instance(&self.sequent, cited, exp)
+  }
+}
+
+/// Whether `cited ⊢ exp` is `sequent` with sentences substituted uniformly for its sentence letters.
+pub fn is_substitution_instance(sequent: &Sequent, cited: &[&Exp], exp: &Exp) -> bool {
+  let mut substitution = HashMap::new();
+  sequent.premises.len() == cited.len()
+    && sequent
+      .premises
+      .iter()
+      .zip(cited.iter())
+      .all(|(premise, cited)| substitutes(premise, cited, &mut substitution, &mut vec![]))
+    && substitutes(&sequent.conclusion, exp, &mut substitution, &mut vec![])
+}
+
+/// The most citations `substitution_instances` proposes for one sequent.
+pub(crate) const MAX_INSTANCES: usize = 64;
+
+/// Citations of `lines`, one per premise in order, from which `sequent` concludes `exp`. Premises are
+/// matched one at a time, so a line is only tried where it agrees with the substitution so far.
+pub(crate) fn substitution_instances(sequent: &Sequent, exp: &Exp, lines: &[(usize, &Exp)]) -> Vec<Vec<usize>> {
+  let mut substitution = HashMap::new();
+  let mut instances = vec![];
+  if substitutes(&sequent.conclusion, exp, &mut substitution, &mut vec![]) {
+    extend_instances(&sequent.premises, lines, &substitution, &mut vec![], &mut instances);
+  }
+  instances
+}
+
+fn extend_instances(
+  premises: &[Exp],
+  lines: &[(usize, &Exp)],
+  substitution: &HashMap<String, Exp>,
+  cited: &mut Vec<usize>,
+  instances: &mut Vec<Vec<usize>>,
+) {
+  let (premise, rest) = match premises.split_first() {
+    Some(split) => split,
+    None => return instances.push(cited.clone()),
+  };
+  for &(k, exp_k) in lines {
+    if instances.len() >= MAX_INSTANCES {
+      return;
+    }
+    let mut extended = substitution.clone();
+    if substitutes(premise, exp_k, &mut extended, &mut vec![]) {
+      cited.push(k);
+      extend_instances(rest, lines, &extended, cited, instances);
+      cited.pop();
+    }
+  }
+}
+
+/// The conclusion of `sequent` from the premises `cited`, with the sentences they substitute for its
+/// sentence letters. Letters only in the conclusion stay as they are, being an instance themselves.
+pub fn instantiate_conclusion(sequent: &Sequent, cited: &[&Exp]) -> Option<Exp> {
+  let mut substitution = HashMap::new();
+  let matches = sequent.premises.len() == cited.len()
+    && sequent
+      .premises
+      .iter()
+      .zip(cited.iter())
+      .all(|(premise, cited)| substitutes(premise, cited, &mut substitution, &mut vec![]));
+  matches.then(|| substituted(&sequent.conclusion, &substitution))
+}
+
+fn substituted(schema: &Exp, substitution: &HashMap<String, Exp>) -> Exp {
+  let boxed = |schema: &Exp| Box::new(substituted(schema, substitution));
+  match schema {
+    Exp::Atom { predicate, individuals } if individuals.is_empty() => {
+      substitution.get(predicate).cloned().unwrap_or_else(|| schema.clone())
+    }
+    Exp::Atom { .. } | Exp::Falsum => schema.clone(),
+    Exp::Neg(schema) => Exp::Neg(boxed(schema)),
+    Exp::And { lhs, rhs } => Exp::And {
+      lhs: boxed(lhs),
+      rhs: boxed(rhs),
+    },
+    Exp::Or { lhs, rhs } => Exp::Or {
+      lhs: boxed(lhs),
+      rhs: boxed(rhs),
+    },
+    Exp::Iff { lhs, rhs } => Exp::Iff {
+      lhs: boxed(lhs),
+      rhs: boxed(rhs),
+    },
+    Exp::Cond { antecedent, consequent } => Exp::Cond {
+      antecedent: boxed(antecedent),
+      consequent: boxed(consequent),
+    },
+    Exp::UnivGenr { variable, form } => Exp::UnivGenr {
+      variable: variable.clone(),
+      form: boxed(form),
+    },
+    Exp::ExistGenr { variable, form } => Exp::ExistGenr {
+      variable: variable.clone(),
+      form: boxed(form),
+    },
+  }
+}
+
+/// Whether `exp` is `schema` with sentence letters replaced according to `substitution`, extending it
+/// with letters seen for the first time. A sentence substituted under a quantifier may not mention
+/// the variable it binds (`bound`).
+fn substitutes(schema: &Exp, exp: &Exp, substitution: &mut HashMap<String, Exp>, bound: &mut Vec<String>) -> bool {
+  match (schema, exp) {
+    (Exp::Atom { predicate, individuals }, exp) if individuals.is_empty() => {
+      if exp.free_variables().iter().any(|variable| bound.contains(variable)) {
+        return false;
+      }
+      match substitution.get(predicate) {
+        Some(substituted) => substituted == exp,
+        None => {
+          substitution.insert(predicate.clone(), exp.clone());
+          true
+        }
+      }
+    }
+    (Exp::Neg(schema), Exp::Neg(exp)) => substitutes(schema, exp, substitution, bound),
+    (Exp::And { lhs: s1, rhs: s2 }, Exp::And { lhs: e1, rhs: e2 })
+    | (Exp::Or { lhs: s1, rhs: s2 }, Exp::Or { lhs: e1, rhs: e2 })
+    | (Exp::Iff { lhs: s1, rhs: s2 }, Exp::Iff { lhs: e1, rhs: e2 })
+    | (
+      Exp::Cond {
+        antecedent: s1,
+        consequent: s2,
+      },
+      Exp::Cond {
+        antecedent: e1,
+        consequent: e2,
+      },
+    ) => substitutes(s1, e1, substitution, bound) && substitutes(s2, e2, substitution, bound),
+    (Exp::UnivGenr { variable: v1, form: s }, Exp::UnivGenr { variable: v2, form: e })
+    | (Exp::ExistGenr { variable: v1, form: s }, Exp::ExistGenr { variable: v2, form: e })
+      if v1 == v2 =>
+    {
+      bound.push(v1.clone());
+      let result = substitutes(s, e, substitution, bound);
+      bound.pop();
+      result
+    }
+    (schema, exp) => schema == exp,
+  }
+}
+
+/// Derived rules by name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Registry {
+  rules: BTreeMap<String, DerivedRule>,
+}
+
+impl Registry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn get(&self, name: &str) -> Option<&DerivedRule> {
+    self.rules.get(name)
+  }
+
+  /// Add a rule, replacing the one with the same name if any.
+  pub fn insert(&mut self, rule: DerivedRule) -> Option<DerivedRule> {
+    self.rules.insert(rule.name.clone(), rule)
+  }
+
+  pub fn remove(&mut self, name: &str) -> Option<DerivedRule> {
+    self.rules.remove(name)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &DerivedRule> {
+    self.rules.values()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.rules.is_empty()
+  }
+}
+
+impl FromIterator<DerivedRule> for Registry {
+  fn from_iter<T: IntoIterator<Item = DerivedRule>>(iter: T) -> Self {
+    Registry {
+      rules: iter.into_iter().map(|rule| (rule.name.clone(), rule)).collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fixtures::rows;
+  use language::parser::{expression::exp, sequent::sequent};
+
+  #[test]
+  fn derived_substitution_instances() {
+    let exps: Vec<Exp> = ["P", "(P → Q)", "R", "(R → Q)"]
+      .iter()
+      .map(|s| exp(s).unwrap().1)
+      .collect();
+    let lines: Vec<(usize, &Exp)> = exps.iter().enumerate().map(|(idx, exp)| (idx + 1, exp)).collect();
+    let mp = sequent("A, (A → B) ⊢ B").unwrap().1;
+    assert_eq!(
+      substitution_instances(&mp, &exp("Q").unwrap().1, &lines),
+      [vec![1, 2], vec![3, 4]]
+    );
+    // letters only in the premises match every line, so the search stops at the cap
+    let loose = sequent("A, B, C, D ⊢ E").unwrap().1;
+    assert_eq!(
+      substitution_instances(&loose, &exp("Q").unwrap().1, &lines).len(),
+      MAX_INSTANCES
+    );
+  }
+
+  fn modus_tollens() -> DerivedRule {
+    DerivedRule::from_derivation(
+      "MT",
+      &rows(&[
+        ("(P → Q)", "P"),
+        ("¬Q", "P"),
+        ("P", "P"),
+        ("Q", "1, 3 →E"),
+        ("⊥", "2, 4 →E"),
+        ("¬P", "3-5 ¬I"),
+      ]),
+      &Gentzen::default(),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn from_derivation_valid() {
+    assert_eq!(modus_tollens().to_string(), "MT: (P → Q), ¬Q ⊢ ¬P");
+    assert_eq!(modus_tollens().profile, Profile::Minimal);
+  }
+
+  #[test]
+  fn from_derivation_invalid() {
+    let gentzen = Gentzen::default();
+    assert_eq!(
+      DerivedRule::from_derivation("1MT", &rows(&[("P", "P")]), &gentzen),
+      Err(DeriveError::InvalidName)
+    );
+    assert_eq!(
+      DerivedRule::from_derivation("MT", &rows(&[("P", "P"), ("Q", "1 &E")]), &gentzen),
+      Err(DeriveError::InvalidLine { num: 2 })
+    );
+    assert_eq!(
+      DerivedRule::from_derivation("MT", &[], &gentzen),
+      Err(DeriveError::Empty)
+    );
+  }
+
+  #[test]
+  fn derived_rule_cited() {
+    let report = check_rows(
+      &Gentzen {
+        lemmas: Registry::from_iter([modus_tollens()]),
+        ..Gentzen::default()
+      },
+      &rows(&[
+        ("(¬R → (S & T))", "P"),
+        ("¬(S & T)", "P"),
+        ("¬¬R", "1, 2 MT"),
+        ("¬¬R", "2, 1 MT"),
+        ("¬R", "1, 2 MT"),
+        ("¬¬R", "1, 2 DS"),
+      ]),
+    );
+    assert_eq!(report.validity, vec![true, true, true, false, false, false]);
+    assert_eq!(report.deps_list[2].nums, [1, 2].into());
+  }
+
+  #[test]
+  fn is_instance_capture() {
+    let (_, schema) = exp("(x)(P → Fx)").unwrap();
+    let rule = DerivedRule {
+      name: "X".to_owned(),
+      sequent: Sequent {
+        premises: vec![],
+        conclusion: schema,
+      },
+      profile: Profile::Minimal,
+    };
+    let (_, valid) = exp("(x)(Ga → Fx)").unwrap();
+    let (_, captured) = exp("(x)(Gx → Fx)").unwrap();
+    assert!(rule.is_instance(&[], &valid));
+    assert!(!rule.is_instance(&[], &captured));
+  }
+}
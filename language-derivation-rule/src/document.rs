@@ -0,0 +1,331 @@
+//! Proof documents: a derivation with the rule system, the declared sequent and some metadata, for
+//! files and share links.
+//!
+//! Documents are versioned. Reading one also accepts the legacy shape, a bare list of `{s, d}` rows,
+//! and upgrades it to the current version.
+
+use std::{error, fmt};
+
+use language::{
+  ast::{exp::Exp, sequent::Sequent},
+  parser::{parse_sentence, sequent::sequent},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  derived::Registry,
+  rule_set::{Profile, System},
+  theorem::Library,
+};
+
+/// The version documents are written in.
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Shape")]
+pub struct ProofDocument {
+  pub version: u32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub author: Option<String>,
+  pub rule_system: System,
+  /// The sequent the derivation is meant to prove, as written.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sequent: Option<String>,
+  pub rows: Vec<DocumentRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentRow {
+  #[serde(alias = "s")]
+  pub sentence: String,
+  #[serde(alias = "d")]
+  pub derivation: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub comment: Option<String>,
+}
+
+impl ProofDocument {
+  /// A document of the current version with no metadata.
+  pub fn new(rule_system: System, rows: Vec<DocumentRow>) -> Self {
+    ProofDocument {
+      version: VERSION,
+      title: None,
+      author: None,
+      rule_system,
+      sequent: None,
+      rows,
+    }
+  }
+}
+
+/// What checking a document found, for callers outside the playground's table.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct DocumentReport {
+  pub rows: Vec<RowReport>,
+  /// Whether the rows prove the declared sequent; `None` when none is declared.
+  pub proved: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowReport {
+  pub line: usize,
+  pub valid: bool,
+  /// The assumption lines the row depends on, sorted
+  pub dependencies: Vec<usize>,
+  /// Whether `dependencies` could be computed; see `RowDependency`.
+  pub complete: bool,
+  /// What is wrong with the row, in English
+  pub errors: Vec<String>,
+}
+
+impl ProofDocument {
+  /// Check every row in the document's rule system, without lemmas or theorems.
+  pub fn check(&self, profile: Profile) -> Result<DocumentReport, DocumentError> {
+    let sequent = match self.sequent.as_deref().map(str::trim) {
+      Some(s) => match sequent(s) {
+        Ok(("", sequent)) => Some(sequent),
+        _ => return Err(DocumentError::InvalidSequent),
+      },
+      None => None,
+    };
+    let exps: Vec<Option<Exp>> = self.rows.iter().map(|row| parse_sentence(&row.sentence)).collect();
+    let lines: Vec<(Option<Exp>, &str)> = exps
+      .iter()
+      .cloned()
+      .zip(self.rows.iter().map(|row| row.derivation.as_str()))
+      .collect();
+    let report = self
+      .rule_system
+      .check(&lines, &Registry::default(), &Library::default(), profile);
+
+    let rows: Vec<RowReport> = (0..lines.len())
+      .map(|idx| {
+        let mut errors: Vec<String> = report.citation_errors[idx].iter().map(|err| err.to_string()).collect();
+        if exps[idx].is_none() {
+          errors.insert(0, "invalid sentence".to_owned());
+        } else if errors.is_empty() && !report.validity[idx] {
+          errors.push("the rule does not derive this sentence".to_owned());
+        }
+        let mut dependencies: Vec<usize> = report.deps_list[idx].nums.iter().copied().collect();
+        dependencies.sort_unstable();
+        RowReport {
+          line: idx + 1,
+          valid: report.validity[idx],
+          dependencies,
+          complete: report.deps_list[idx].is_complete,
+          errors,
+        }
+      })
+      .collect();
+
+    // proved when the last row is the conclusion and depends only on rows that are premises
+    let proved = sequent.map(|Sequent { premises, conclusion }| match (exps.last(), rows.last()) {
+      (Some(Some(last)), Some(last_row)) => {
+        *last == conclusion
+          && rows.iter().all(|row| row.valid && row.complete)
+          && last_row
+            .dependencies
+            .iter()
+            .all(|&num| exps[num - 1].as_ref().is_some_and(|exp| premises.contains(exp)))
+      }
+      _ => false,
+    });
+    Ok(DocumentReport { rows, proved })
+  }
+}
+
+/// Why a document could not be read or checked, beyond what the format reports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentError {
+  /// Written by a newer version, or not a version at all.
+  UnsupportedVersion { version: u32 },
+  /// The declared sequent does not parse.
+  InvalidSequent,
+}
+
+impl fmt::Display for DocumentError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DocumentError::UnsupportedVersion { version } => write!(f, "unsupported document version {}", version),
+      DocumentError::InvalidSequent => write!(f, "invalid sequent"),
+    }
+  }
+}
+
+impl error::Error for DocumentError {}
+
+/// Every shape a document was ever written in.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Shape {
+  /// Before versioning, the rows alone, always in the Gentzen system
+  Legacy(Vec<DocumentRow>),
+  Versioned(Versioned),
+}
+
+#[derive(Deserialize)]
+struct Versioned {
+  version: u32,
+  #[serde(default)]
+  title: Option<String>,
+  #[serde(default)]
+  author: Option<String>,
+  #[serde(default)]
+  rule_system: System,
+  #[serde(default)]
+  sequent: Option<String>,
+  rows: Vec<DocumentRow>,
+}
+
+impl TryFrom<Shape> for ProofDocument {
+  type Error = DocumentError;
+
+  fn try_from(shape: Shape) -> Result<Self, Self::Error> {
+    match shape {
+      Shape::Legacy(rows) => Ok(ProofDocument::new(System::Gentzen, rows)),
+      Shape::Versioned(Versioned { version, .. }) if version == 0 || version > VERSION => {
+        Err(DocumentError::UnsupportedVersion { version })
+      }
+      Shape::Versioned(Versioned {
+        version,
+        title,
+        author,
+        rule_system,
+        sequent,
+        rows,
+      }) => Ok(ProofDocument {
+        version,
+        title,
+        author,
+        rule_system,
+        sequent,
+        rows,
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::spec::{SpecSet, GENTZEN};
+  use std::sync::Arc;
+
+  fn row(sentence: &str, derivation: &str) -> DocumentRow {
+    DocumentRow {
+      sentence: sentence.to_owned(),
+      derivation: derivation.to_owned(),
+      comment: None,
+    }
+  }
+
+  #[test]
+  fn document_round_trip() {
+    let document = ProofDocument {
+      title: Some("Modus ponens".to_owned()),
+      author: None,
+      sequent: Some("P, (P → Q) ⊢ Q".to_owned()),
+      rows: vec![
+        row("P", "A"),
+        row("(P → Q)", "A"),
+        DocumentRow {
+          comment: Some("the conclusion".to_owned()),
+          ..row("Q", "2, 1 MPP")
+        },
+      ],
+      ..ProofDocument::new(System::Lemmon, vec![])
+    };
+    let yaml = serde_yaml::to_string(&document).unwrap();
+    assert_eq!(
+      yaml,
+      "\
+version: 1
+title: Modus ponens
+rule_system: Lemmon
+sequent: P, (P → Q) ⊢ Q
+rows:
+- sentence: P
+  derivation: A
+- sentence: (P → Q)
+  derivation: A
+- sentence: Q
+  derivation: 2, 1 MPP
+  comment: the conclusion
+"
+    );
+    assert_eq!(serde_yaml::from_str::<ProofDocument>(&yaml).unwrap(), document);
+  }
+
+  #[test]
+  fn document_legacy() {
+    let legacy = "- s: P\n  d: P\n- sentence: (P ∨ Q)\n  derivation: 1 ∨I\n";
+    assert_eq!(
+      serde_yaml::from_str::<ProofDocument>(legacy).unwrap(),
+      ProofDocument::new(System::Gentzen, vec![row("P", "P"), row("(P ∨ Q)", "1 ∨I")])
+    );
+  }
+
+  #[test]
+  fn document_spec() {
+    let specs: SpecSet = GENTZEN.parse().unwrap();
+    let document = ProofDocument {
+      sequent: Some("(P → Q), P ⊢ Q".to_owned()),
+      ..ProofDocument::new(
+        System::Spec(Arc::new(specs)),
+        vec![row("(P → Q)", "P"), row("P", "P"), row("Q", "1, 2 →E")],
+      )
+    };
+    let yaml = serde_yaml::to_string(&document).unwrap();
+    assert_eq!(serde_yaml::from_str::<ProofDocument>(&yaml).unwrap(), document);
+    assert_eq!(document.check(Profile::default()).unwrap().proved, Some(true));
+  }
+
+  #[test]
+  fn document_unsupported_version() {
+    let error = serde_yaml::from_str::<ProofDocument>("version: 2\nrows: []\n").unwrap_err();
+    assert!(error.to_string().contains("unsupported document version 2"));
+  }
+
+  #[test]
+  fn document_check() {
+    let document = ProofDocument {
+      sequent: Some("(P → Q), P ⊢ Q".to_owned()),
+      ..ProofDocument::new(
+        System::Gentzen,
+        vec![row("(P → Q)", "P"), row("P", "P"), row("Q", "1, 2 →E")],
+      )
+    };
+    let report = document.check(Profile::default()).unwrap();
+    assert_eq!(report.proved, Some(true));
+    assert_eq!(
+      report.rows[2],
+      RowReport {
+        line: 3,
+        valid: true,
+        dependencies: vec![1, 2],
+        complete: true,
+        errors: vec![],
+      }
+    );
+
+    let document = ProofDocument {
+      sequent: Some("P ⊢ Q".to_owned()),
+      ..ProofDocument::new(
+        System::Gentzen,
+        vec![row("P", "P"), row("Q", "1 &E"), row("(Q", "3 &E")],
+      )
+    };
+    let report = document.check(Profile::default()).unwrap();
+    assert_eq!(report.proved, Some(false));
+    assert_eq!(report.rows[1].errors, vec!["the rule does not derive this sentence"]);
+    assert_eq!(report.rows[2].errors[0], "invalid sentence");
+
+    let document = ProofDocument {
+      sequent: Some("P ⊢".to_owned()),
+      ..ProofDocument::new(System::Gentzen, vec![])
+    };
+    assert_eq!(document.check(Profile::default()), Err(DocumentError::InvalidSequent));
+  }
+}
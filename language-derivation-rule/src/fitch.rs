@@ -0,0 +1,359 @@
+//! Fitch-style proofs in plain text, as in *forall x*: subproofs are marked by vertical bars instead
+//! of a dependency column.
+//!
+//! Each line is an optional line number, one `|` per level of nesting, the sentence and the rule.
+//! Premises are marked `PR` and each subproof starts with an assumption marked `AS`. A premise is
+//! usually in the main proof, but may stand inside a subproof when no rule discharges it. Other rules are the Gentzen rules of the table, citing lines and subproof ranges, with the
+//! rule name either after its citations or before them. Blank lines, lines starting with `#` and
+//! separators such as `| |---` are ignored.
+//!
+//! ```text
+//! 1 | (P → Q)   PR
+//! 2 | ¬Q        PR
+//!   |---
+//! 3 | | P       AS
+//!   | |---
+//! 4 | | Q       1, 3 →E
+//! 5 | | ⊥       2, 4 →E
+//! 6 | ¬P        3-5 ¬I
+//! ```
+
+use std::{error, fmt};
+
+use language::{ast::exp::Exp, parser::expression::exp};
+
+use crate::{
+  ast::rule::Rule,
+  rule_set::{parse_rule, Gentzen},
+  scope::scopes,
+};
+
+/// Why a Fitch-style proof could not be read. Line numbers are those of the text, from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitchError {
+  /// The sentence or the rule does not parse.
+  Syntax { line: usize },
+  /// The line is numbered, but not with the number of the proof line it is.
+  Numbering { line: usize, expected: usize },
+  /// The line is nested deeper than the line before without opening a subproof with `AS`.
+  Depth { line: usize },
+  /// The rule cites `start-end`, which is not a whole subproof directly inside the line's own.
+  Range { line: usize, start: usize, end: usize },
+}
+
+impl fmt::Display for FitchError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FitchError::Syntax { line } => write!(f, "line {}: syntax error", line),
+      FitchError::Numbering { line, expected } => write!(f, "line {}: should be numbered {}", line, expected),
+      FitchError::Depth { line } => write!(f, "line {}: nested without an assumption", line),
+      FitchError::Range { line, start, end } => {
+        write!(f, "line {}: {}-{} is not a subproof it can discharge", line, start, end)
+      }
+    }
+  }
+}
+
+impl error::Error for FitchError {}
+
+struct FitchLine {
+  /// The text line it was read from
+  source: usize,
+  depth: usize,
+  is_subproof_assumption: bool,
+  exp: Exp,
+  rule: Rule,
+}
+
+/// The sentences and rules of a Fitch-style proof, in the numbering of the table.
+pub fn parse(s: &str) -> Result<Vec<(Exp, Rule)>, FitchError> {
+  let mut lines: Vec<FitchLine> = vec![];
+  for (idx, text) in s.lines().enumerate() {
+    let source = idx + 1;
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('#') {
+      continue;
+    }
+
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let number = text[..digits].parse::<usize>().ok();
+    let mut rest = text[digits..].trim_start_matches('.');
+    let mut depth = 0;
+    while let Some(after_bar) = rest.trim_start().strip_prefix('|') {
+      depth += 1;
+      rest = after_bar;
+    }
+    let rest = rest.trim();
+    if number.is_none() && rest.chars().all(|c| c == '-') {
+      continue;
+    }
+    if let Some(number) = number {
+      if number != lines.len() + 1 {
+        return Err(FitchError::Numbering {
+          line: source,
+          expected: lines.len() + 1,
+        });
+      }
+    }
+    if depth == 0 {
+      return Err(FitchError::Syntax { line: source });
+    }
+
+    // a rule may begin like a sentence continues (`(Q ∨ P) ∨I 2`), so take the shortest sentence
+    // followed by a rule
+    let split = rest
+      .char_indices()
+      .filter(|(_, c)| c.is_whitespace())
+      .find_map(
+        |(idx, _)| match (exp(&rest[..idx]), parse_marker(rest[idx..].trim(), depth)) {
+          (Ok(("", exp)), Some((rule, is_subproof_assumption))) => Some((exp, rule, is_subproof_assumption)),
+          _ => None,
+        },
+      );
+    let (exp, rule, is_subproof_assumption) = match split {
+      Some(split) => split,
+      None => return Err(FitchError::Syntax { line: source }),
+    };
+
+    let previous = lines.last().map_or(0, |line| line.depth);
+    let is_nested_properly = match (&rule, is_subproof_assumption) {
+      (_, true) => 2 <= depth && depth <= previous.max(1) + 1,
+      _ => depth <= previous.max(1),
+    };
+    if !is_nested_properly {
+      return Err(FitchError::Depth { line: source });
+    }
+
+    lines.push(FitchLine {
+      source,
+      depth,
+      is_subproof_assumption,
+      exp,
+      rule,
+    });
+  }
+
+  // the last line of the subproof each assumption opens
+  let ends: Vec<Option<usize>> = (0..lines.len())
+    .map(|idx| {
+      let depth = lines[idx].depth;
+      lines[idx].is_subproof_assumption.then(|| {
+        let inside = lines[idx + 1..]
+          .iter()
+          .take_while(|line| line.depth > depth || (line.depth == depth && !line.is_subproof_assumption))
+          .count();
+        idx + 1 + inside
+      })
+    })
+    .collect();
+  for (idx, line) in lines.iter().enumerate() {
+    let num = idx + 1;
+    for (start, end) in line.rule.discharged_ranges() {
+      let is_subproof = (1..num).contains(&start)
+        && ends[start - 1] == Some(end)
+        && end < num
+        && lines[start - 1].depth == line.depth + 1;
+      if !is_subproof {
+        return Err(FitchError::Range {
+          line: line.source,
+          start,
+          end,
+        });
+      }
+    }
+  }
+
+  Ok(lines.into_iter().map(|line| (line.exp, line.rule)).collect())
+}
+
+/// The rule of a line at `depth`, and whether it opens a subproof.
+fn parse_marker(s: &str, depth: usize) -> Option<(Rule, bool)> {
+  match s {
+    "PR" => Some((Rule::Premise, false)),
+    "AS" => Some((Rule::Premise, true)),
+    _ => match parse_either_order(s)? {
+      Rule::Premise => Some((Rule::Premise, depth > 1)),
+      rule => Some((rule, false)),
+    },
+  }
+}
+
+/// A rule with its name after the citations, as in the table, or before them, as in *forall x*.
+fn parse_either_order(s: &str) -> Option<Rule> {
+  let gentzen = Gentzen::default();
+  parse_rule(&gentzen, s).or_else(|| {
+    s.char_indices()
+      .filter(|(_, c)| c.is_whitespace())
+      .find_map(|(idx, _)| parse_rule(&gentzen, &format!("{} {}", &s[idx..], &s[..idx])))
+  })
+}
+
+/// What a line is in a Fitch-style proof.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Role {
+  /// An assumption no rule discharges
+  Premise,
+  /// The assumption opening a subproof
+  Assumption,
+  Inference,
+}
+
+/// The depth of each line, the main proof being 1, and its role. Each subproof is a range some rule
+/// discharges; unparsable rows are inferences.
+pub(crate) fn nesting(rules: &[Option<Rule>]) -> Vec<(usize, Role)> {
+  let scopes = scopes(&Gentzen::default(), rules);
+  (1..=rules.len())
+    .map(|num| {
+      let depth = 1 + scopes.iter().filter(|scope| scope.contains(num)).count();
+      let role = match rules[num - 1] {
+        Some(Rule::Premise) if scopes.iter().any(|scope| scope.start == num) => Role::Assumption,
+        Some(Rule::Premise) => Role::Premise,
+        _ => Role::Inference,
+      };
+      (depth, role)
+    })
+    .collect()
+}
+
+/// A derivation in Fitch style, nesting each subproof its rule discharges. Assumptions no rule
+/// discharges are premises, written where they stand.
+pub fn write(lines: &[(Exp, Rule)]) -> String {
+  let rules: Vec<Option<Rule>> = lines.iter().map(|(_, rule)| Some(rule.clone())).collect();
+  let width = lines.len().to_string().len();
+
+  let rows: Vec<(usize, String, String)> = lines
+    .iter()
+    .zip(nesting(&rules))
+    .enumerate()
+    .map(|(idx, ((exp, rule), (depth, role)))| {
+      let rule = match role {
+        Role::Assumption => "AS".to_owned(),
+        Role::Premise => "PR".to_owned(),
+        Role::Inference => rule.to_string(),
+      };
+      (
+        depth,
+        format!("{:>width$} {}{}", idx + 1, "| ".repeat(depth), exp),
+        rule,
+      )
+    })
+    .collect();
+  let column = rows.iter().map(|(_, left, _)| left.chars().count()).max().unwrap_or(0) + 2;
+  let separator = |depth: usize| format!("{:width$} {}---\n", "", "| ".repeat(depth).trim_end());
+
+  let mut out = String::new();
+  for (idx, (depth, left, rule)) in rows.iter().enumerate() {
+    let padding = column - left.chars().count();
+    out.push_str(&format!("{}{:padding$}{}\n", left, "", rule));
+    let is_last_premise = rule == "PR" && rows.get(idx + 1).is_none_or(|(_, _, next)| next != "PR");
+    if rule == "AS" || (is_last_premise && rows[..idx].iter().all(|(_, _, rule)| rule == "PR")) {
+      out.push_str(&separator(*depth));
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  const MODUS_TOLLENS: &str = "\
+1 | (P → Q)  PR
+2 | ¬Q       PR
+  |---
+3 | | P      AS
+  | |---
+4 | | Q      1, 3 →E
+5 | | ⊥      2, 4 →E
+6 | ¬P       3-5 ¬I
+";
+
+  fn rows(lines: &[(Exp, Rule)]) -> Vec<(String, String)> {
+    lines
+      .iter()
+      .map(|(exp, rule)| (exp.to_string(), rule.to_string()))
+      .collect()
+  }
+
+  #[test]
+  fn fitch_parse() {
+    let lines = parse(MODUS_TOLLENS).unwrap();
+    let expected = [
+      ("(P → Q)", "P"),
+      ("¬Q", "P"),
+      ("P", "P"),
+      ("Q", "1, 3 →E"),
+      ("⊥", "2, 4 →E"),
+      ("¬P", "3-5 ¬I"),
+    ];
+    let expected: Vec<(String, String)> = expected.iter().map(|&(s, r)| (s.to_owned(), r.to_owned())).collect();
+    assert_eq!(rows(&lines), expected);
+  }
+
+  #[test]
+  fn fitch_round_trip() {
+    assert_eq!(write(&parse(MODUS_TOLLENS).unwrap()), MODUS_TOLLENS);
+  }
+
+  #[test]
+  fn fitch_unnumbered_rule_first() {
+    let text = "\
+| (P ∨ Q)    PR
+| | P       AS
+| | (Q ∨ P)  ∨I 2
+| | Q       AS
+| | (Q ∨ P)  ∨I 4
+| (Q ∨ P)    ∨E 1, 2-3, 4-5
+";
+    let lines = parse(text).unwrap();
+    assert_eq!(lines[5].1.to_string(), "1, 2-3, 4-5 ∨E");
+    assert_eq!(
+      write(&lines),
+      "\
+1 | (P ∨ Q)    PR
+  |---
+2 | | P        AS
+  | |---
+3 | | (Q ∨ P)  2 ∨I
+4 | | Q        AS
+  | |---
+5 | | (Q ∨ P)  4 ∨I
+6 | (Q ∨ P)    1, 2-3, 4-5 ∨E
+"
+    );
+  }
+
+  #[test]
+  fn fitch_round_trip_premise_in_subproof() {
+    // line 2 is in the subproof of line 1, but no rule discharges it
+    let text = "\
+1 | | P            AS
+  | |---
+2 | | Q            PR
+3 | | (P & Q)      1, 2 &I
+4 | (P → (P & Q))  1-3 →I
+";
+    let lines = parse(text).unwrap();
+    assert_eq!(lines[1].1, Rule::Premise);
+    assert_eq!(write(&lines), text);
+  }
+
+  #[rstest]
+  #[case("1 | P PR\n3 | Q PR\n", FitchError::Numbering { line: 2, expected: 2 })]
+  #[case("| P PR\n| | Q 1 &E\n", FitchError::Depth { line: 2 })]
+  #[case("| P PR\n| | Q PR\n", FitchError::Depth { line: 2 })]
+  #[case("| P PR\n| Q &E\n", FitchError::Syntax { line: 2 })]
+  #[case("| P PR\nP PR\n", FitchError::Syntax { line: 2 })]
+  #[case(
+    "| | P AS\n| | Q AS\n| (Q → Q) 1-2 →I\n",
+    FitchError::Range { line: 3, start: 1, end: 2 },
+  )]
+  #[case(
+    "| | P AS\n| | | Q AS\n| | (Q → P) 2-2 →I\n| (P → P) 1-1 →I\n",
+    FitchError::Range { line: 4, start: 1, end: 1 },
+  )]
+  fn fitch_invalid(#[case] text: &str, #[case] expected: FitchError) {
+    assert_eq!(parse(text), Err(expected));
+  }
+}
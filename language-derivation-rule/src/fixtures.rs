@@ -0,0 +1,24 @@
+//! Derivations written out as `(sentence, rule)` pairs, for the tests.
+
+use language::{ast::exp::Exp, parser::expression::exp};
+
+use crate::rule_set::{parse_rule, Line, RuleSet};
+
+/// Each row with its sentence parsed, or `None` when it does not parse.
+pub fn rows<'a>(rows: &[(&str, &'a str)]) -> Vec<(Option<Exp>, &'a str)> {
+  rows
+    .iter()
+    .map(|&(sentence, rule)| (exp(sentence).ok().map(|(_, exp)| exp), rule))
+    .collect()
+}
+
+/// Each row as a line with its sentence and its rule under `rule_set` parsed.
+pub fn lines<R: RuleSet>(rule_set: &R, rows: &[(&str, &str)]) -> Vec<Line<R::Rule>> {
+  rows
+    .iter()
+    .map(|&(sentence, rule)| Line {
+      exp: exp(sentence).ok().map(|(_, exp)| exp),
+      rule: parse_rule(rule_set, rule),
+    })
+    .collect()
+}
@@ -0,0 +1,126 @@
+//! Justification search, the reverse of `apply`: the rules and citations by which a line follows from
+//! the lines before it.
+
+use language::ast::exp::Exp;
+
+use crate::{
+  citation,
+  dependency::dependencies,
+  rule_set::{Line, RuleSet},
+  scope::scopes,
+};
+
+/// Every rule, other than an assumption, by which line `num` follows from the lines before it. The
+/// rule written on line `num` and the lines after it are ignored.
+pub fn justify<R: RuleSet>(rule_set: &R, num: usize, lines: &[Line<R::Rule>]) -> Vec<R::Rule> {
+  let exp = match num.checked_sub(1).and_then(|idx| lines.get(idx)) {
+    Some(Line { exp: Some(exp), .. }) => exp,
+    _ => return vec![],
+  };
+  let lines = &lines[..num];
+  let mut rules: Vec<Option<R::Rule>> = lines.iter().map(|line| line.rule.clone()).collect();
+  rules[num - 1] = None;
+  let deps_list = dependencies(rule_set, &rules[..num - 1]);
+  let scopes = scopes(rule_set, &rules[..num - 1]);
+
+  let mut justifications: Vec<R::Rule> = vec![];
+  for rule in rule_set.candidates(num, lines) {
+    if rule_set.is_assumption(&rule) || justifications.contains(&rule) {
+      continue;
+    }
+    if citation::check(rule_set, num, &rule, &rules, &scopes).is_empty()
+      && rule_set.is_valid(num, exp, &rule, lines, &deps_list)
+    {
+      justifications.push(rule);
+    }
+  }
+  justifications
+}
+
+/// Lines before `num` with a sentence, numbered.
+pub(crate) fn earlier<R>(lines: &[Line<R>], num: usize) -> Vec<(usize, &Exp)> {
+  lines
+    .iter()
+    .take(num.saturating_sub(1))
+    .enumerate()
+    .filter_map(|(idx, line)| line.exp.as_ref().map(|exp| (idx + 1, exp)))
+    .collect()
+}
+
+/// Numbers of the lines in `earlier` whose sentence is `target`.
+pub(crate) fn lines_with(earlier: &[(usize, &Exp)], target: &Exp) -> Vec<usize> {
+  earlier
+    .iter()
+    .filter(|(_, exp)| *exp == target)
+    .map(|&(num, _)| num)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    derived::Registry,
+    fixtures::rows,
+    rule_set::{Profile, System},
+    theorem::{Library, BASIC},
+  };
+  use rstest::rstest;
+
+  #[rstest]
+  #[case(System::Gentzen, &[("(P → Q)", "P"), ("P", "P"), ("Q", "")], &["1, 2 →E"])]
+  #[case(System::Gentzen, &[("P", "P"), ("Q", "P"), ("(P & Q)", "1, 2 →E")], &["1, 2 &I"])]
+  #[case(System::Gentzen, &[("(P & Q)", "P"), ("Q", "")], &["1 &E"])]
+  #[case(System::Gentzen, &[("P", "P"), ("Q", "P"), ("(P → Q)", "")], &["1-2 →I", "2 →I"])]
+  #[case(System::Gentzen, &[("¬¬P", "P"), ("¬P", "P"), ("⊥", "1, 2 →E"), ("P", "")], &["3 ⊥", "2-3 ¬E"])]
+  #[case(System::Gentzen, &[
+    ("(P ∨ Q)", "P"),
+    ("(P → R)", "P"),
+    ("(Q → R)", "P"),
+    ("P", "P"),
+    ("R", "2, 4 →E"),
+    ("Q", "P"),
+    ("R", "3, 6 →E"),
+    ("R", ""),
+  ], &["1, 4-5, 6-7 ∨E", "2, 4 →E", "3, 6 →E"])]
+  #[case(System::Gentzen, &[("(x)Fx", "P"), ("Fa", "")], &["1 ()E"])]
+  #[case(System::Gentzen, &[("P", "P"), ("R", "")], &[])]
+  #[case(System::Lemmon, &[("(P → Q)", "A"), ("¬Q", "A"), ("¬P", "")], &["1, 2 MTT"])]
+  #[case(System::Lemmon, &[("¬¬P", "A"), ("P", "")], &["1 DN"])]
+  fn justify(#[case] system: System, #[case] derivation: &[(&str, &str)], #[case] expected: &[&str]) {
+    assert_eq!(
+      system.justify(
+        &rows(derivation),
+        derivation.len(),
+        &Registry::new(),
+        &Library::new(),
+        Profile::Classical
+      ),
+      expected
+    );
+  }
+
+  #[test]
+  fn justify_profile() {
+    let derivation = rows(&[("¬¬P", "P"), ("¬P", "P"), ("⊥", "1, 2 →E"), ("P", "")]);
+    assert_eq!(
+      System::Gentzen.justify(
+        &derivation,
+        4,
+        &Registry::new(),
+        &Library::new(),
+        Profile::Intuitionistic
+      ),
+      ["3 ⊥"]
+    );
+  }
+
+  #[test]
+  fn justify_theorem() {
+    let theorems: Library = BASIC.parse().unwrap();
+    let derivation = rows(&[("(A ∨ B)", "P"), ("¬A", "P"), ("B", "")]);
+    assert_eq!(
+      System::Gentzen.justify(&derivation, 3, &Registry::new(), &theorems, Profile::Classical),
+      ["1, 2 SI (T5)"]
+    );
+  }
+}
@@ -0,0 +1,289 @@
+//! LaTeX export of derivation tables, for handouts. Sentences are typeset in math mode; sentences
+//! that do not parse are copied as text.
+
+use std::fmt;
+
+use language::{latex, parser::parse_sentence};
+
+use crate::{
+  fitch::{nesting, Role},
+  rule_set::{parse_rule, Gentzen},
+};
+
+/// How to lay out the derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+  /// A `tabular` with the dependency, line number, sentence and rule columns of the table.
+  #[default]
+  Tabular,
+  /// `\fitchprf` of the `lplfitch` package.
+  LplFitch,
+  /// The `nd` environment of `fitch.sty`.
+  FitchSty,
+}
+
+impl Layout {
+  pub const ALL: [Layout; 3] = [Layout::Tabular, Layout::LplFitch, Layout::FitchSty];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Layout::Tabular => "tabular",
+      Layout::LplFitch => "lplfitch",
+      Layout::FitchSty => "fitch.sty",
+    }
+  }
+}
+
+impl fmt::Display for Layout {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+/// A row of a derivation table, with the dependencies the checker computed for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRow {
+  pub deps: Vec<usize>,
+  pub sentence: String,
+  pub rule: String,
+}
+
+/// The derivation in `layout`. The Fitch layouts nest the subproofs Gentzen rules discharge, so they
+/// read the rules as Gentzen's and leave out the dependencies.
+pub fn export(rows: &[TableRow], layout: Layout) -> String {
+  match layout {
+    Layout::Tabular => tabular(rows),
+    Layout::LplFitch => lplfitch(rows),
+    Layout::FitchSty => fitch_sty(rows),
+  }
+}
+
+/// A sentence in math mode, without the dollar signs.
+fn sentence(s: &str) -> String {
+  match parse_sentence(s) {
+    Some(exp) => latex::exp(&exp),
+    None => format!("\\text{{{}}}", escape(s.trim())),
+  }
+}
+
+/// A rule in text mode, with its symbols in math mode and ranges with en dashes.
+fn rule(s: &str) -> String {
+  s.trim()
+    .chars()
+    .map(|c| match c {
+      '→' => "$\\to$".to_owned(),
+      '↔' => "$\\leftrightarrow$".to_owned(),
+      '¬' => "$\\neg$".to_owned(),
+      '∨' => "$\\lor$".to_owned(),
+      '∃' => "$\\exists$".to_owned(),
+      '⊥' => "$\\bot$".to_owned(),
+      '-' => "--".to_owned(),
+      c => escape(&c.to_string()),
+    })
+    .collect()
+}
+
+fn escape(s: &str) -> String {
+  s.chars()
+    .map(|c| match c {
+      '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+      '\\' => "\\textbackslash{}".to_owned(),
+      c => c.to_string(),
+    })
+    .collect()
+}
+
+fn tabular(rows: &[TableRow]) -> String {
+  let mut out = String::from("\\begin{tabular}{lrll}\n");
+  for (idx, row) in rows.iter().enumerate() {
+    let mut deps = row.deps.clone();
+    deps.sort_unstable();
+    let deps: Vec<String> = deps.iter().map(|num| num.to_string()).collect();
+    out.push_str(&format!(
+      "{} & ({}) & ${}$ & {} \\\\\n",
+      deps.join(","),
+      idx + 1,
+      sentence(&row.sentence),
+      rule(&row.rule)
+    ));
+  }
+  out.push_str("\\end{tabular}\n");
+  out
+}
+
+fn roles(rows: &[TableRow]) -> Vec<(usize, Role)> {
+  let gentzen = Gentzen::default();
+  let rules: Vec<_> = rows.iter().map(|row| parse_rule(&gentzen, &row.rule)).collect();
+  nesting(&rules)
+}
+
+fn lplfitch(rows: &[TableRow]) -> String {
+  let roles = roles(rows);
+  let pline = |idx: usize| match roles[idx].1 {
+    Role::Inference => format!(
+      "\\pline[{}.]{{{}}}[{}]",
+      idx + 1,
+      sentence(&rows[idx].sentence),
+      rule(&rows[idx].rule)
+    ),
+    _ => format!("\\pline[{}.]{{{}}}", idx + 1, sentence(&rows[idx].sentence)),
+  };
+
+  // the lines `start..end` of a proof at `depth`, each subproof in it wrapped in `\subproof`
+  fn block(roles: &[(usize, Role)], pline: &dyn Fn(usize) -> String, start: usize, end: usize, depth: usize) -> String {
+    let mut items = vec![];
+    let mut idx = start;
+    while idx < end {
+      match roles[idx] {
+        (inner, Role::Assumption) if inner == depth + 1 => {
+          let inside = roles[idx + 1..end]
+            .iter()
+            .take_while(|&&(d, role)| d > inner || (d == inner && role != Role::Assumption))
+            .count();
+          let body = block(roles, pline, idx + 1, idx + 1 + inside, inner);
+          items.push(format!("\\subproof{{{}}}{{{}}}", pline(idx), body));
+          idx += 1 + inside;
+        }
+        _ => {
+          items.push(pline(idx));
+          idx += 1;
+        }
+      }
+    }
+    items.join(" \\\\\n")
+  }
+
+  let premises = roles
+    .iter()
+    .take_while(|&&(depth, role)| depth == 1 && role == Role::Premise)
+    .count();
+  let hypotheses: Vec<String> = (0..premises).map(pline).collect();
+  format!(
+    "\\fitchprf{{{}}}\n{{{}}}\n",
+    hypotheses.join(" \\\\\n"),
+    block(&roles, &pline, premises, rows.len(), 1)
+  )
+}
+
+fn fitch_sty(rows: &[TableRow]) -> String {
+  let mut out = String::from("$\\begin{nd}\n");
+  let mut current = 1;
+  for (idx, (row, (depth, role))) in rows.iter().zip(roles(rows)).enumerate() {
+    let opened = match role {
+      Role::Assumption => depth - 1,
+      _ => depth,
+    };
+    while current > opened {
+      out.push_str(&format!("{}\\close\n", "  ".repeat(current - 1)));
+      current -= 1;
+    }
+    let indent = "  ".repeat(depth);
+    if role == Role::Assumption {
+      out.push_str(&format!("{}\\open\n", "  ".repeat(depth - 1)));
+      current = depth;
+    }
+    match role {
+      Role::Inference => {
+        // the citations come first in the rule, the name after them
+        let text = row.rule.trim();
+        let name = text.trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, ',' | '-' | ' '));
+        let citations = &text[..text.len() - name.len()];
+        out.push_str(&format!(
+          "{}\\have{{{}}}{{{}}} \\by{{{}}}{{{}}}\n",
+          indent,
+          idx + 1,
+          sentence(&row.sentence),
+          rule(name),
+          rule(citations.trim()).replace(' ', "")
+        ))
+      }
+      _ => out.push_str(&format!(
+        "{}\\hypo{{{}}}{{{}}}\n",
+        indent,
+        idx + 1,
+        sentence(&row.sentence)
+      )),
+    }
+  }
+  while current > 1 {
+    out.push_str(&format!("{}\\close\n", "  ".repeat(current - 1)));
+    current -= 1;
+  }
+  out.push_str("\\end{nd}$\n");
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn modus_tollens() -> Vec<TableRow> {
+    [
+      (vec![1], "(P → Q)", "P"),
+      (vec![2], "¬Q", "P"),
+      (vec![3], "P", "P"),
+      (vec![1, 3], "Q", "1, 3 →E"),
+      (vec![2, 1, 3], "⊥", "2, 4 →E"),
+      (vec![1, 2], "¬P", "3-5 ¬I"),
+    ]
+    .into_iter()
+    .map(|(deps, sentence, rule)| TableRow {
+      deps,
+      sentence: sentence.to_owned(),
+      rule: rule.to_owned(),
+    })
+    .collect()
+  }
+
+  #[test]
+  fn latex_tabular() {
+    let mut rows = modus_tollens();
+    rows[0].sentence = "P → Q & 100%".to_owned();
+    assert_eq!(
+      export(&rows, Layout::Tabular),
+      "\
+\\begin{tabular}{lrll}
+1 & (1) & $\\text{P → Q \\& 100\\%}$ & P \\\\
+2 & (2) & $\\neg Q$ & P \\\\
+3 & (3) & $P$ & P \\\\
+1,3 & (4) & $Q$ & 1, 3 $\\to$E \\\\
+1,2,3 & (5) & $\\bot$ & 2, 4 $\\to$E \\\\
+1,2 & (6) & $\\neg P$ & 3--5 $\\neg$I \\\\
+\\end{tabular}
+"
+    );
+  }
+
+  #[test]
+  fn latex_lplfitch() {
+    assert_eq!(
+      export(&modus_tollens(), Layout::LplFitch),
+      "\
+\\fitchprf{\\pline[1.]{(P \\to Q)} \\\\
+\\pline[2.]{\\neg Q}}
+{\\subproof{\\pline[3.]{P}}{\\pline[4.]{Q}[1, 3 $\\to$E] \\\\
+\\pline[5.]{\\bot}[2, 4 $\\to$E]} \\\\
+\\pline[6.]{\\neg P}[3--5 $\\neg$I]}
+"
+    );
+  }
+
+  #[test]
+  fn latex_fitch_sty() {
+    assert_eq!(
+      export(&modus_tollens(), Layout::FitchSty),
+      "\
+$\\begin{nd}
+  \\hypo{1}{(P \\to Q)}
+  \\hypo{2}{\\neg Q}
+  \\open
+    \\hypo{3}{P}
+    \\have{4}{Q} \\by{$\\to$E}{1,3}
+    \\have{5}{\\bot} \\by{$\\to$E}{2,4}
+  \\close
+  \\have{6}{\\neg P} \\by{$\\neg$I}{3--5}
+\\end{nd}$
+"
+    );
+  }
+}
@@ -1,5 +1,31 @@
+//! Rules of derivation for ℒ and the checks of derivations written with them.
+//!
+//! A derivation is passed around as a slice with an entry for each line: `rules[i]` is the rule of
+//! line `i + 1`, and a row that does not parse is `None`. Line numbers, as rules cite them, start
+//! from 1.
+
+pub mod apply;
 pub mod ast;
+pub mod check;
+pub mod citation;
+pub mod dependency;
+pub mod derived;
+pub mod document;
+pub mod fitch;
+#[cfg(test)]
+mod fixtures;
+pub mod justify;
+pub mod latex;
+pub mod minimize;
+pub mod normalize;
+pub mod notation;
 pub mod parser;
+pub mod proof_file;
+pub mod rule_set;
+pub mod scope;
+pub mod spec;
+pub mod theorem;
+pub mod tree;
 
 pub fn add(left: usize, right: usize) -> usize {
   left + right
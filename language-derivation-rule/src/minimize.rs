@@ -0,0 +1,117 @@
+//! Proof minimization: drop the lines the last line does not use and renumber the citations.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::rule_set::RuleSet;
+
+/// The last line and every line it cites, directly or through the lines it cites. `rules[i]` is the
+/// rule of line `i + 1`; unparsable rows are `None` and cite nothing.
+pub fn used<R: RuleSet>(rule_set: &R, rules: &[Option<R::Rule>]) -> BTreeSet<usize> {
+  let last = rules.len();
+  if last == 0 {
+    return BTreeSet::new();
+  }
+  let mut used = BTreeSet::from([last]);
+  let mut stack = vec![last];
+  while let Some(num) = stack.pop() {
+    if let Some(Some(rule)) = rules.get(num - 1) {
+      for cited in rule_set.cited_lines(rule) {
+        if (1..=last).contains(&cited) && used.insert(cited) {
+          stack.push(cited);
+        }
+      }
+    }
+  }
+  used
+}
+
+/// The lines `used` keeps, in order, each with its old number and its rule citing the new numbers.
+/// A valid derivation stays valid, with the same dependency on the last line.
+pub fn minimize<R: RuleSet>(rule_set: &R, rules: &[Option<R::Rule>]) -> Vec<(usize, Option<R::Rule>)> {
+  let used = used(rule_set, rules);
+  let numbers: BTreeMap<usize, usize> = used.iter().enumerate().map(|(idx, &num)| (num, idx + 1)).collect();
+  // lines that are not kept are only cited wrongly, so their numbers stay as they are
+  let renumber = |num: usize| numbers.get(&num).copied().unwrap_or(num);
+  used
+    .iter()
+    .map(|&num| {
+      let rule = rules[num - 1].as_ref().map(|rule| rule_set.renumbered(rule, &renumber));
+      (num, rule)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    check::check,
+    fixtures::lines,
+    rule_set::{Gentzen, Lemmon, Line},
+  };
+
+  fn minimized<R: RuleSet>(rule_set: &R, lines: &[Line<R::Rule>]) -> Vec<Line<R::Rule>> {
+    let rules: Vec<Option<R::Rule>> = lines.iter().map(|line| line.rule.clone()).collect();
+    minimize(rule_set, &rules)
+      .into_iter()
+      .map(|(num, rule)| Line {
+        exp: lines[num - 1].exp.clone(),
+        rule,
+      })
+      .collect()
+  }
+
+  #[test]
+  fn minimize_gentzen() {
+    let gentzen = Gentzen::default();
+    let original = lines(
+      &gentzen,
+      &[
+        ("(P → Q)", "P"),
+        ("R", "P"),
+        ("¬Q", "P"),
+        ("(R & R)", "2, 2 &I"),
+        ("P", "P"),
+        ("Q", "1, 5 →E"),
+        ("(Q & Q)", "6, 6 &I"),
+        ("⊥", "3, 6 →E"),
+        ("¬P", "5-8 ¬I"),
+      ],
+    );
+    let minimized = minimized(&gentzen, &original);
+    assert_eq!(
+      minimized
+        .iter()
+        .map(|line| line.rule.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>(),
+      ["P", "P", "P", "1, 3 →E", "2, 4 →E", "3-5 ¬I"]
+    );
+    let report = check(&gentzen, &minimized);
+    assert!(report.validity.iter().all(|&is_valid| is_valid));
+    assert_eq!(report.deps_list.last().unwrap().nums, [1, 2].into());
+  }
+
+  #[test]
+  fn minimize_lemmon() {
+    let lemmon = Lemmon::default();
+    let original = lines(
+      &lemmon,
+      &[
+        ("P", "A"),
+        ("R", "A"),
+        ("Q", "A"),
+        ("(P & Q)", "1, 3 &I"),
+        ("Q", "4 &E"),
+      ],
+    );
+    let minimized = minimized(&lemmon, &original);
+    assert_eq!(
+      minimized
+        .iter()
+        .map(|line| line.rule.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>(),
+      ["A", "A", "1, 2 &I", "3 &E"]
+    );
+    assert_eq!(used(&lemmon, &[]), BTreeSet::new());
+  }
+}
@@ -0,0 +1,336 @@
+//! Prawitz-style normalization: remove every introduction whose conclusion is the major premise of
+//! the matching elimination, substituting the derivation of the minor premise for the discharged
+//! assumption. Permutative conversions of ∨E and ∃E are not applied.
+
+use language::ast::exp::Exp;
+
+use crate::{
+  ast::rule::Rule,
+  rule_set::{Gentzen, Line},
+  tree::{Hypothesis, Premise, Step, Tree, TreeError},
+};
+
+/// The normal form of a valid derivation, written out as lines again.
+pub fn normalize_derivation(lines: &[Line<Rule>], gentzen: &Gentzen) -> Result<Vec<Line<Rule>>, TreeError> {
+  Tree::from_derivation(lines, gentzen).map(|tree| normalize(&tree).lines())
+}
+
+/// Contract detours until none is left.
+pub fn normalize(tree: &Tree) -> Tree {
+  let mut tree = tree.clone();
+  while let Some(reduced) = reduce(&tree) {
+    tree = reduced;
+  }
+  tree
+}
+
+/// Whether no introduction is immediately eliminated.
+pub fn is_normal(tree: &Tree) -> bool {
+  match tree {
+    Tree::Assumption { .. } => true,
+    Tree::Inference { premises, .. } => {
+      contract(tree, &mut 0).is_none() && premises.iter().all(|premise| is_normal(&premise.tree))
+    }
+  }
+}
+
+/// The tree with its outermost, leftmost detour contracted.
+fn reduce(tree: &Tree) -> Option<Tree> {
+  let mut fresh = tree.max_label() + 1;
+  reduce_with(tree, &mut fresh)
+}
+
+fn reduce_with(tree: &Tree, fresh: &mut usize) -> Option<Tree> {
+  if let Some(contracted) = contract(tree, fresh) {
+    return Some(contracted);
+  }
+  let (exp, step, premises) = match tree {
+    Tree::Assumption { .. } => return None,
+    Tree::Inference { exp, step, premises } => (exp, step, premises),
+  };
+  premises.iter().enumerate().find_map(|(idx, premise)| {
+    let reduced = reduce_with(&premise.tree, fresh)?;
+    let mut premises = premises.clone();
+    premises[idx].tree = reduced;
+    Some(Tree::Inference {
+      exp: exp.clone(),
+      step: step.clone(),
+      premises,
+    })
+  })
+}
+
+fn inference(tree: &Tree) -> Option<(&Exp, &Step, &[Premise])> {
+  match tree {
+    Tree::Assumption { .. } => None,
+    Tree::Inference { exp, step, premises } => Some((exp, step, premises)),
+  }
+}
+
+/// The premise of `premises` that concludes `exp`.
+fn concluding<'a>(premises: &'a [Premise], exp: &Exp) -> Option<&'a Tree> {
+  premises
+    .iter()
+    .map(|premise| &premise.tree)
+    .find(|tree| tree.exp() == exp)
+}
+
+/// `premise.tree` with the derivation `minor` in place of the discharged assumption.
+fn discharged_by(premise: &Premise, minor: &Tree, fresh: &mut usize) -> Tree {
+  match &premise.hypothesis {
+    Some(hypothesis) => substitute(&premise.tree, hypothesis.label, minor, fresh),
+    None => premise.tree.clone(),
+  }
+}
+
+/// Contract the detour at the root of `tree`, if there is one.
+fn contract(tree: &Tree, fresh: &mut usize) -> Option<Tree> {
+  let (exp, step, premises) = inference(tree)?;
+  if *step == Step::IfExclude {
+    // the negation may be cited second when the conclusion is ⊥
+    return [(0, 1), (1, 0)].into_iter().find_map(|(major, minor)| {
+      let (major_exp, major_step, major_premises) = inference(&premises[major].tree)?;
+      let is_detour = match (major, major_step, major_exp) {
+        (0, Step::IfIntro, Exp::Cond { consequent, .. }) => **consequent == *exp,
+        (_, Step::NegIntro, Exp::Neg(negated)) => **negated == *premises[minor].tree.exp(),
+        _ => false,
+      };
+      is_detour.then(|| discharged_by(&major_premises[0], &premises[minor].tree, fresh))
+    });
+  }
+  let (major_exp, major_step, major_premises) = inference(&premises.first()?.tree)?;
+
+  match (step, major_step) {
+    (Step::AndExclude, Step::AndIntro) | (Step::IffExclude, Step::IffIntro) => concluding(major_premises, exp).cloned(),
+
+    (Step::OrExclude, Step::OrIntro) => premises[1..].iter().find_map(|case| {
+      let hypothesis = case.hypothesis.as_ref()?;
+      let disjunct = concluding(major_premises, &hypothesis.exp)?;
+      Some(discharged_by(case, disjunct, fresh))
+    }),
+
+    (Step::UnivQuntExclude, Step::UnivQuntIntro) => {
+      let (variable, form) = match major_exp {
+        Exp::UnivGenr { variable, form } => (variable, form),
+        _ => return None,
+      };
+      let generalized = &major_premises[0].tree;
+      Some(
+        match (
+          instance_of(variable, form, generalized.exp()),
+          instance_of(variable, form, exp),
+        ) {
+          (Some(arbitrary), Some(term)) => generalized.map_exps(&|exp| exp.var_replaced(&arbitrary, &term)),
+          _ => generalized.clone(),
+        },
+      )
+    }
+
+    (Step::ExisQuntExclude, Step::ExisQuntIntro) => {
+      let (variable, form) = match major_exp {
+        Exp::ExistGenr { variable, form } => (variable, form),
+        _ => return None,
+      };
+      let witness = &major_premises[0].tree;
+      let case = &premises[1];
+      let hypothesis = case.hypothesis.as_ref()?;
+      let case = match (
+        instance_of(variable, form, &hypothesis.exp),
+        instance_of(variable, form, witness.exp()),
+      ) {
+        (Some(arbitrary), Some(term)) => Premise {
+          hypothesis: Some(Hypothesis {
+            label: hypothesis.label,
+            exp: hypothesis.exp.var_replaced(&arbitrary, &term),
+          }),
+          tree: case.tree.map_exps(&|exp| exp.var_replaced(&arbitrary, &term)),
+        },
+        _ => case.clone(),
+      };
+      Some(discharged_by(&case, witness, fresh))
+    }
+
+    _ => None,
+  }
+}
+
+/// The individual `instance` has in place of `variable`, if `variable` occurs free in `form`.
+fn instance_of(variable: &str, form: &Exp, instance: &Exp) -> Option<String> {
+  instance
+    .free_variables()
+    .into_iter()
+    .find(|individual| form.var_replaced(variable, individual) == *instance && *form != *instance)
+}
+
+/// `tree` with `replacement` for the open assumptions labelled `label`. Hypotheses that would
+/// capture an open assumption of `replacement` are relabelled first.
+fn substitute(tree: &Tree, label: usize, replacement: &Tree, fresh: &mut usize) -> Tree {
+  match tree {
+    Tree::Assumption { label: own, .. } if *own == label => replacement.clone(),
+    Tree::Assumption { .. } => tree.clone(),
+    Tree::Inference { exp, step, premises } => {
+      let open = replacement.open_assumptions();
+      let premises = premises
+        .iter()
+        .map(|premise| match &premise.hypothesis {
+          Some(hypothesis) if hypothesis.label == label => premise.clone(),
+          Some(hypothesis) if open.contains_key(&hypothesis.label) => {
+            let relabelled = Hypothesis {
+              label: *fresh,
+              exp: hypothesis.exp.clone(),
+            };
+            *fresh += 1;
+            let leaf = Tree::Assumption {
+              label: relabelled.label,
+              exp: relabelled.exp.clone(),
+            };
+            let tree = substitute(&premise.tree, hypothesis.label, &leaf, fresh);
+            Premise {
+              hypothesis: Some(relabelled),
+              tree: substitute(&tree, label, replacement, fresh),
+            }
+          }
+          _ => Premise {
+            hypothesis: premise.hypothesis.clone(),
+            tree: substitute(&premise.tree, label, replacement, fresh),
+          },
+        })
+        .collect();
+      Tree::Inference {
+        exp: exp.clone(),
+        step: step.clone(),
+        premises,
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{check::check, fixtures::lines};
+  use language::parser::expression::exp;
+  use rstest::rstest;
+
+  fn rows(lines: &[Line<Rule>]) -> Vec<(String, String)> {
+    lines
+      .iter()
+      .map(|line| {
+        (
+          line.exp.as_ref().unwrap().to_string(),
+          line.rule.as_ref().unwrap().to_string(),
+        )
+      })
+      .collect()
+  }
+
+  #[rstest]
+  #[case::and(
+    &[("P", "P"), ("Q", "P"), ("(P & Q)", "1, 2 &I"), ("Q", "3 &E")],
+    &[("Q", "P")],
+  )]
+  #[case::cond(
+    &[
+      ("Q", "P"),
+      ("P", "P"),
+      ("(P & Q)", "2, 1 &I"),
+      ("(P → (P & Q))", "2-3 →I"),
+      ("(R & P)", "P"),
+      ("P", "5 &E"),
+      ("(P & Q)", "4, 6 →E"),
+    ],
+    &[("Q", "P"), ("(R & P)", "P"), ("P", "2 &E"), ("(P & Q)", "3, 1 &I")],
+  )]
+  #[case::neg(
+    &[("¬Q", "P"), ("Q", "P"), ("⊥", "1, 2 →E"), ("¬Q", "2-3 ¬I"), ("Q", "P"), ("⊥", "5, 4 →E")],
+    &[("¬Q", "P"), ("Q", "P"), ("⊥", "1, 2 →E")],
+  )]
+  #[case::or(
+    &[
+      ("P", "P"),
+      ("(P ∨ Q)", "1 ∨I"),
+      ("P", "P"),
+      ("(Q ∨ P)", "3 ∨I"),
+      ("Q", "P"),
+      ("(Q ∨ P)", "5 ∨I"),
+      ("(Q ∨ P)", "2, 3-4, 5-6 ∨E"),
+    ],
+    &[("P", "P"), ("(Q ∨ P)", "1 ∨I")],
+  )]
+  #[case::univ(
+    &[("(x)Fx", "P"), ("Fa", "1 ()E"), ("(x)Fx", "2 ()I"), ("Fb", "3 ()E")],
+    &[("(x)Fx", "P"), ("Fb", "1 ()E")],
+  )]
+  #[case::exist(
+    &[
+      ("Fb", "P"),
+      ("(∃x)Fx", "1 ∃I"),
+      ("Fa", "P"),
+      ("(∃y)Fy", "3 ∃I"),
+      ("(∃y)Fy", "2, 3-4 ∃E"),
+    ],
+    &[("Fb", "P"), ("(∃y)Fy", "1 ∃I")],
+  )]
+  #[case::cond_falsum(
+    &[
+      ("¬(P → Q)", "P"),
+      ("P", "P"),
+      ("Q", "P"),
+      ("(P & Q)", "2, 3 &I"),
+      ("Q", "4 &E"),
+      ("(P → Q)", "2-5 →I"),
+      ("⊥", "6, 1 →E"),
+    ],
+    &[("¬(P → Q)", "P"), ("Q", "P"), ("(P → Q)", "2 →I"), ("⊥", "3, 1 →E")],
+  )]
+  fn normalize_detours(#[case] original: &[(&str, &str)], #[case] expected: &[(&str, &str)]) {
+    let gentzen = Gentzen::default();
+    let original = lines(&gentzen, original);
+    let normal = normalize_derivation(&original, &gentzen).unwrap();
+    assert!(check(&gentzen, &normal).validity.iter().all(|&is_valid| is_valid));
+    let tree = Tree::from_derivation(&original, &gentzen).unwrap();
+    let normal_tree = Tree::from_derivation(&normal, &gentzen).unwrap();
+    assert!(is_normal(&normal_tree));
+    // the same conclusion, from no assumption the original leaves open
+    assert_eq!(normal_tree.exp(), tree.exp());
+    let open: Vec<Exp> = tree.open_assumptions().into_values().collect();
+    assert!(normal_tree
+      .open_assumptions()
+      .values()
+      .all(|assumption| open.contains(assumption)));
+    let expected: Vec<(String, String)> = expected
+      .iter()
+      .map(|&(sentence, rule)| (sentence.to_owned(), rule.to_owned()))
+      .collect();
+    assert_eq!(rows(&normal), expected);
+  }
+
+  #[test]
+  fn normalize_nested() {
+    // the &I only meets its &E once the →I detour around it is contracted
+    let gentzen = Gentzen::default();
+    let original = lines(
+      &gentzen,
+      &[
+        ("P", "P"),
+        ("Q", "P"),
+        ("(P & Q)", "1, 2 &I"),
+        ("(P & Q)", "P"),
+        ("((P & Q) → (P & Q))", "4-4 →I"),
+        ("(P & Q)", "5, 3 →E"),
+        ("P", "6 &E"),
+      ],
+    );
+    let tree = Tree::from_derivation(&original, &gentzen).unwrap();
+    assert!(!is_normal(&tree));
+    let normal = normalize(&tree);
+    assert!(is_normal(&normal));
+    assert_eq!(
+      normal,
+      Tree::Assumption {
+        label: 1,
+        exp: exp("P").unwrap().1,
+      }
+    );
+  }
+}
@@ -0,0 +1,39 @@
+/// Replace ASCII aliases in a derivation being typed with their representative symbols.
+///
+/// Works like `language::notation::replace_aliases`, except that a `-` between two line numbers is
+/// a range (`3-5`) rather than a negation, so only the rule name is affected.
+pub fn replace_aliases(s: &str, caret: usize) -> (String, usize) {
+  language::notation::replace_aliases_with(s, caret, &language::notation::ALIASES, is_range_dash)
+}
+
+fn is_range_dash(chars: &[char], idx: usize) -> bool {
+  let is_digit = |c: &&char| c.is_ascii_digit();
+  let prev = chars[..idx].iter().rev().find(|c| !c.is_whitespace());
+  let next = chars[idx + 1..].iter().find(|c| !c.is_whitespace());
+  chars[idx] == '-' && prev.filter(is_digit).is_some() && next.filter(is_digit).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  #[rstest]
+  #[case("1, 3-5, 6-7 |E", 14, "1, 3-5, 6-7 ∨E", 14)]
+  #[case("2 - 3 ->I", 9, "2 - 3 →I", 8)]
+  #[case("1-2 -I", 6, "1-2 ¬I", 6)]
+  #[case("1-2 -E", 3, "1-2 ¬E", 3)]
+  #[case("1 <->E", 6, "1 ↔E", 4)]
+  #[case("1, 2-3 ]E", 9, "1, 2-3 ∃E", 9)]
+  #[case("1 \\bot", 6, "1 ⊥", 3)]
+  #[case("1-", 2, "1-", 2)]
+  #[case("1-2 -", 5, "1-2 -", 5)]
+  fn replace_aliases_valid(
+    #[case] input: &str,
+    #[case] caret: usize,
+    #[case] expected: &str,
+    #[case] expected_caret: usize,
+  ) {
+    assert_eq!(replace_aliases(input, caret), (expected.to_owned(), expected_caret));
+  }
+}
@@ -0,0 +1,128 @@
+use nom::{
+  branch::alt,
+  bytes::complete::tag,
+  character::complete::{digit1, multispace1},
+  combinator::{map, map_res, value},
+  sequence::{pair, preceded, terminated, tuple},
+  IResult,
+};
+
+use language::parser::{
+  symbol::{and, or},
+  util::ws,
+};
+
+use crate::ast::lemmon_rule::LemmonRule;
+
+fn num(s: &str) -> IResult<&str, usize> {
+  map_res(digit1, |s: &str| s.parse::<usize>())(s)
+}
+
+fn sep(s: &str) -> IResult<&str, &str> {
+  ws(tag(","))(s)
+}
+
+fn nums2(s: &str) -> IResult<&str, (usize, usize)> {
+  map(tuple((num, sep, num)), |(m, _, n)| (m, n))(s)
+}
+
+fn assumption(s: &str) -> IResult<&str, LemmonRule> {
+  value(LemmonRule::Assumption, tag("A"))(s)
+}
+
+fn binary<'a>(
+  name: &'static str,
+  rule: fn(usize, usize) -> LemmonRule,
+) -> impl FnMut(&'a str) -> IResult<&'a str, LemmonRule> {
+  map(terminated(nums2, preceded(multispace1, tag(name))), move |(m, n)| {
+    rule(m, n)
+  })
+}
+
+fn unary<'a>(name: &'static str, rule: fn(usize) -> LemmonRule) -> impl FnMut(&'a str) -> IResult<&'a str, LemmonRule> {
+  map(terminated(num, preceded(multispace1, tag(name))), rule)
+}
+
+fn and_intro(s: &str) -> IResult<&str, LemmonRule> {
+  map(
+    terminated(nums2, preceded(multispace1, pair(and, tag("I")))),
+    |(m, n)| LemmonRule::AndIntro(m, n),
+  )(s)
+}
+
+fn and_exclude(s: &str) -> IResult<&str, LemmonRule> {
+  map(
+    terminated(num, preceded(multispace1, pair(and, tag("E")))),
+    LemmonRule::AndExclude,
+  )(s)
+}
+
+fn or_intro(s: &str) -> IResult<&str, LemmonRule> {
+  map(
+    terminated(num, preceded(multispace1, pair(or, tag("I")))),
+    LemmonRule::OrIntro,
+  )(s)
+}
+
+fn or_exclude(s: &str) -> IResult<&str, LemmonRule> {
+  map(
+    terminated(
+      tuple((num, sep, num, sep, num, sep, num, sep, num)),
+      preceded(multispace1, pair(or, tag("E"))),
+    ),
+    |(k, _, l, _, m, _, n, _, o)| LemmonRule::OrExclude(k, l, m, n, o),
+  )(s)
+}
+
+fn ei(s: &str) -> IResult<&str, LemmonRule> {
+  map(
+    terminated(tuple((num, sep, num, sep, num)), preceded(multispace1, tag("EI"))),
+    |(k, _, l, _, m)| LemmonRule::Ei(k, l, m),
+  )(s)
+}
+
+pub fn rule(s: &str) -> IResult<&str, LemmonRule> {
+  alt((
+    assumption,
+    binary("MPP", LemmonRule::Mpp),
+    binary("MTT", LemmonRule::Mtt),
+    unary("DN", LemmonRule::Dn),
+    binary("CP", LemmonRule::Cp),
+    and_intro,
+    and_exclude,
+    or_intro,
+    or_exclude,
+    binary("RAA", LemmonRule::Raa),
+    unary("UI", LemmonRule::Ui),
+    unary("UG", LemmonRule::Ug),
+    unary("EG", LemmonRule::Eg),
+    ei,
+  ))(s)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use nom::IResult;
+
+  #[test]
+  fn rule_valid() {
+    assert_eq!(rule("A"), IResult::Ok(("", LemmonRule::Assumption)));
+    assert_eq!(rule("1, 2 MPP"), IResult::Ok(("", LemmonRule::Mpp(1, 2))));
+    assert_eq!(rule("1,2 MTT"), IResult::Ok(("", LemmonRule::Mtt(1, 2))));
+    assert_eq!(rule("3 DN"), IResult::Ok(("", LemmonRule::Dn(3))));
+    assert_eq!(rule("2, 4 CP"), IResult::Ok(("", LemmonRule::Cp(2, 4))));
+    assert_eq!(rule("1, 2 &I"), IResult::Ok(("", LemmonRule::AndIntro(1, 2))));
+    assert_eq!(rule("1 &E"), IResult::Ok(("", LemmonRule::AndExclude(1))));
+    assert_eq!(rule("1 |I"), IResult::Ok(("", LemmonRule::OrIntro(1))));
+    assert_eq!(
+      rule("1, 2, 3, 4, 5 ∨E"),
+      IResult::Ok(("", LemmonRule::OrExclude(1, 2, 3, 4, 5)))
+    );
+    assert_eq!(rule("2, 5 RAA"), IResult::Ok(("", LemmonRule::Raa(2, 5))));
+    assert_eq!(rule("1 UI"), IResult::Ok(("", LemmonRule::Ui(1))));
+    assert_eq!(rule("1 UG"), IResult::Ok(("", LemmonRule::Ug(1))));
+    assert_eq!(rule("1 EG"), IResult::Ok(("", LemmonRule::Eg(1))));
+    assert_eq!(rule("1, 2, 4 EI"), IResult::Ok(("", LemmonRule::Ei(1, 2, 4))));
+  }
+}
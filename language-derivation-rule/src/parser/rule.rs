@@ -1,11 +1,12 @@
 use nom::{
   branch::alt,
   bytes::complete::tag,
-  character::complete::{digit1, multispace1},
+  character::complete::{alpha1, alphanumeric0, digit1, multispace0, multispace1},
   combinator::{
-    opt, {map, map_res, value},
+    opt, recognize, verify, {map, map_res, value},
   },
-  sequence::{pair, preceded, separated_pair, terminated, tuple},
+  multi::separated_list1,
+  sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
   IResult,
 };
 
@@ -107,12 +108,12 @@ fn iff_intro(s: &str) -> IResult<&str, Rule> {
 fn iff_exclude(s: &str) -> IResult<&str, Rule> {
   map(
     terminated(num, preceded(multispace1, pair(left_right_arrow, tag("E")))),
-    |k| Rule::IffExclude(k),
+    Rule::IffExclude,
   )(s)
 }
 
 fn falsum(s: &str) -> IResult<&str, Rule> {
-  map(terminated(num, preceded(multispace1, falsum_tag)), |k| Rule::Falsum(k))(s)
+  map(terminated(num, preceded(multispace1, falsum_tag)), Rule::Falsum)(s)
 }
 
 fn neg_intro(s: &str) -> IResult<&str, Rule> {
@@ -144,7 +145,7 @@ fn univ_qunt_exclude(s: &str) -> IResult<&str, Rule> {
 fn exis_qunt_intro(s: &str) -> IResult<&str, Rule> {
   map(
     terminated(num, preceded(multispace1, pair(existential, tag("I")))),
-    |k| Rule::ExisQuntIntro(k),
+    Rule::ExisQuntIntro,
   )(s)
 }
 
@@ -158,8 +159,44 @@ fn exis_qunt_exclude(s: &str) -> IResult<&str, Rule> {
   )(s)
 }
 
+fn name(s: &str) -> IResult<&str, &str> {
+  recognize(pair(alpha1, alphanumeric0))(s)
+}
+
+fn theorem_name(s: &str) -> IResult<&str, String> {
+  map(delimited(tag("("), ws(name), tag(")")), str::to_owned)(s)
+}
+
+fn sequent_intro(s: &str) -> IResult<&str, Rule> {
+  alt((
+    map(preceded(pair(tag("TI"), multispace0), theorem_name), |name| {
+      Rule::SequentIntro(vec![], name)
+    }),
+    map(
+      pair(
+        terminated(separated_list1(sep, num), tuple((multispace1, tag("SI"), multispace0))),
+        theorem_name,
+      ),
+      |(deps, name)| Rule::SequentIntro(deps, name),
+    ),
+  ))(s)
+}
+
+/// `1, 2 MT`: a lemma name, starting with a letter, after the lines cited for its premises.
+fn derived(s: &str) -> IResult<&str, Rule> {
+  map(
+    pair(
+      opt(terminated(separated_list1(sep, num), multispace1)),
+      verify(name, |name: &str| name != "P"),
+    ),
+    |(deps, name)| Rule::Derived(name.to_owned(), deps.unwrap_or_default()),
+  )(s)
+}
+
 pub fn rule(s: &str) -> IResult<&str, Rule> {
   alt((
+    sequent_intro,
+    derived,
     premise,
     and_intro,
     and_exclude,
@@ -209,5 +246,18 @@ mod tests {
     assert_eq!(rule("1 ()E"), IResult::Ok(("", Rule::UnivQuntExclude(1))));
     assert_eq!(rule("1 ]I"), IResult::Ok(("", Rule::ExisQuntIntro(1))));
     assert_eq!(rule("1, 2-3 ]E"), IResult::Ok(("", Rule::ExisQuntExclude(1, (2, 3)))));
+    assert_eq!(
+      rule("3, 1 MT"),
+      IResult::Ok(("", Rule::Derived("MT".to_owned(), vec![3, 1])))
+    );
+    assert_eq!(rule("LEM"), IResult::Ok(("", Rule::Derived("LEM".to_owned(), vec![]))));
+    assert_eq!(
+      rule("TI (T12)"),
+      IResult::Ok(("", Rule::SequentIntro(vec![], "T12".to_owned())))
+    );
+    assert_eq!(
+      rule("1, 2 SI(T7)"),
+      IResult::Ok(("", Rule::SequentIntro(vec![1, 2], "T7".to_owned())))
+    );
   }
 }
@@ -0,0 +1,390 @@
+//! `.proof` files: a derivation as plain text, one numbered line per row, easy to write by hand and to
+//! diff.
+//!
+//! ```text
+//! # modus tollens
+//! premises: (P → Q), ¬Q
+//! conclusion: ¬P
+//!
+//! 1. (P → Q)  P
+//! 2. ¬Q       P
+//! 3. P        P
+//! 4. Q        1, 3 →E
+//! 5. ⊥        2, 4 →E  # contradicts 2
+//! 6. ¬P       3-5 ¬I
+//! ```
+//!
+//! The header lines are optional and must come before the first row. Each row is an optional `N.`,
+//! the sentence and the rule as written in the table; the rule is whatever follows the sentence, so it
+//! must not begin with a connective. Everything after `#` is a comment, and blank lines are ignored.
+
+use std::{error, fmt, str::FromStr};
+
+use language::{
+  ast::exp::Exp,
+  parser::{expression::exp, util::ws},
+};
+use nom::{combinator::all_consuming, IResult};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProofFile {
+  pub premises: Option<Vec<Exp>>,
+  pub conclusion: Option<Exp>,
+  pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+  /// A line with only a comment
+  Comment(String),
+  Row {
+    exp: Exp,
+    rule: String,
+    comment: Option<String>,
+  },
+}
+
+impl ProofFile {
+  /// The sentence and rule of each row.
+  pub fn rows(&self) -> impl Iterator<Item = (&Exp, &str)> {
+    self.entries.iter().filter_map(|entry| match entry {
+      Entry::Row { exp, rule, .. } => Some((exp, rule.as_str())),
+      Entry::Comment(_) => None,
+    })
+  }
+}
+
+/// Where and why a `.proof` file could not be read. Lines and columns start from 1; columns count
+/// chars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofFileError {
+  pub line: usize,
+  pub column: usize,
+  pub kind: ProofFileErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofFileErrorKind {
+  /// The sentence, or a sentence of the header, does not parse.
+  Sentence,
+  /// The row has a sentence but no rule.
+  MissingRule,
+  /// The row is numbered, but not with its number.
+  Numbering { expected: usize },
+  /// A header line is repeated or comes after a row.
+  Header,
+}
+
+impl fmt::Display for ProofFileError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+  }
+}
+
+impl fmt::Display for ProofFileErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ProofFileErrorKind::Sentence => write!(f, "invalid sentence"),
+      ProofFileErrorKind::MissingRule => write!(f, "missing rule"),
+      ProofFileErrorKind::Numbering { expected } => write!(f, "should be numbered {}", expected),
+      ProofFileErrorKind::Header => write!(f, "header must come once, before the rows"),
+    }
+  }
+}
+
+impl error::Error for ProofFileError {}
+
+fn sentence(s: &str) -> IResult<&str, Exp> {
+  all_consuming(ws(exp))(s)
+}
+
+impl FromStr for ProofFile {
+  type Err = ProofFileError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut file = ProofFile::default();
+    for SourceLine { content, comment, .. } in read_lines(s) {
+      match content {
+        Content::Blank => file.entries.extend(comment.map(Entry::Comment)),
+        Content::Premises(premises) => file.premises = Some(premises),
+        Content::Conclusion(conclusion) => file.conclusion = Some(conclusion),
+        Content::Row { exp, rule, .. } => file.entries.push(Entry::Row { exp, rule, comment }),
+        Content::Invalid { error, .. } => return Err(error),
+      }
+    }
+    Ok(file)
+  }
+}
+
+/// A line of a `.proof` file on its own, as an editor sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLine {
+  /// Starting from 1
+  pub line: usize,
+  pub content: Content,
+  pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+  /// Nothing, or only a comment
+  Blank,
+  Premises(Vec<Exp>),
+  Conclusion(Exp),
+  Row {
+    exp: Exp,
+    rule: String,
+    /// Where the rule starts, counting chars from 1
+    rule_column: usize,
+  },
+  /// The line does not read. It is a row unless it starts like a header line.
+  Invalid {
+    error: ProofFileError,
+    is_row: bool,
+  },
+}
+
+/// Read every line, going on after the ones that do not read. Invalid rows still take a number, so
+/// the rows after them keep theirs.
+pub fn read_lines(s: &str) -> Vec<SourceLine> {
+  let mut lines = vec![];
+  let (mut rows, mut has_premises, mut has_conclusion) = (0, false, false);
+  for (idx, text) in s.lines().enumerate() {
+    // the column where `rest`, a slice of `text`, starts
+    let column = |rest: &str| text[..rest.as_ptr() as usize - text.as_ptr() as usize].chars().count() + 1;
+    let error = |rest: &str, kind: ProofFileErrorKind| ProofFileError {
+      line: idx + 1,
+      column: column(rest),
+      kind,
+    };
+    let (code, comment) = match text.find('#') {
+      Some(hash) => (&text[..hash], Some(text[hash + 1..].trim().to_owned())),
+      None => (text, None),
+    };
+    let code = code.trim_start();
+    let content = if code.trim().is_empty() {
+      Content::Blank
+    } else if let Some(rest) = code.strip_prefix("premises:") {
+      let seen = rows > 0 || has_premises;
+      has_premises = true;
+      // sentences have no commas, so each piece between them is a premise
+      let premises: Result<Vec<Exp>, ProofFileError> = rest
+        .split(',')
+        .filter(|_| !rest.trim().is_empty())
+        .map(|piece| {
+          sentence(piece)
+            .map(|(_, exp)| exp)
+            .map_err(|err| error(remaining(&err, piece), ProofFileErrorKind::Sentence))
+        })
+        .collect();
+      match premises {
+        _ if seen => invalid(error(code, ProofFileErrorKind::Header), false),
+        Ok(premises) => Content::Premises(premises),
+        Err(err) => invalid(err, false),
+      }
+    } else if let Some(rest) = code.strip_prefix("conclusion:") {
+      let seen = rows > 0 || has_conclusion;
+      has_conclusion = true;
+      match sentence(rest) {
+        _ if seen => invalid(error(code, ProofFileErrorKind::Header), false),
+        Ok((_, exp)) => Content::Conclusion(exp),
+        Err(err) => invalid(error(remaining(&err, rest), ProofFileErrorKind::Sentence), false),
+      }
+    } else {
+      rows += 1;
+      let expected = rows;
+      let digits = code.len() - code.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+      let sentence = match code[digits..].strip_prefix('.') {
+        Some(_) if digits > 0 && code[..digits].parse() != Ok(expected) => {
+          Err(error(code, ProofFileErrorKind::Numbering { expected }))
+        }
+        Some(rest) if digits > 0 => Ok(rest.trim_start()),
+        _ => Ok(code),
+      };
+      match sentence.and_then(|sentence| {
+        exp(sentence).map_err(|err| error(remaining(&err, sentence), ProofFileErrorKind::Sentence))
+      }) {
+        Ok((rule, _)) if rule.trim().is_empty() => invalid(
+          error(&code[code.trim_end().len()..], ProofFileErrorKind::MissingRule),
+          true,
+        ),
+        Ok((rule, exp)) => Content::Row {
+          exp,
+          rule: rule.trim().to_owned(),
+          rule_column: column(rule.trim_start()),
+        },
+        Err(err) => invalid(err, true),
+      }
+    };
+    lines.push(SourceLine {
+      line: idx + 1,
+      content,
+      comment,
+    });
+  }
+  lines
+}
+
+fn invalid(error: ProofFileError, is_row: bool) -> Content {
+  Content::Invalid { error, is_row }
+}
+
+/// The input where parsing stopped; its start when nom does not say.
+fn remaining<'a>(err: &nom::Err<nom::error::Error<&'a str>>, input: &'a str) -> &'a str {
+  match err {
+    nom::Err::Error(err) | nom::Err::Failure(err) => err.input,
+    nom::Err::Incomplete(_) => input,
+  }
+}
+
+/// The header, a blank line if there is one, and the rows numbered with sentences and rules aligned.
+impl fmt::Display for ProofFile {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(premises) = &self.premises {
+      let premises: Vec<String> = premises.iter().map(|premise| premise.to_string()).collect();
+      writeln!(f, "premises: {}", premises.join(", "))?;
+    }
+    if let Some(conclusion) = &self.conclusion {
+      writeln!(f, "conclusion: {}", conclusion)?;
+    }
+    if self.premises.is_some() || self.conclusion.is_some() {
+      writeln!(f)?;
+    }
+
+    let count = self.rows().count();
+    let number_width = count.to_string().len() + 1;
+    let sentence_width = self
+      .rows()
+      .map(|(exp, _)| exp.to_string().chars().count())
+      .max()
+      .unwrap_or(0);
+    let rule_width = self.rows().map(|(_, rule)| rule.chars().count()).max().unwrap_or(0);
+    let mut num = 0;
+    for entry in &self.entries {
+      match entry {
+        Entry::Comment(comment) => writeln!(f, "# {}", comment)?,
+        Entry::Row { exp, rule, comment } => {
+          num += 1;
+          let number = format!("{}.", num);
+          let sentence = exp.to_string();
+          let line = format!(
+            "{:<number_width$} {}{:pad$}  {}",
+            number,
+            sentence,
+            "",
+            rule,
+            pad = sentence_width - sentence.chars().count()
+          );
+          match comment {
+            Some(comment) => writeln!(
+              f,
+              "{}{:pad$}  # {}",
+              line,
+              "",
+              comment,
+              pad = rule_width - rule.chars().count()
+            )?,
+            None => writeln!(f, "{}", line)?,
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  const MODUS_TOLLENS: &str = "\
+premises: (P → Q), ¬Q
+conclusion: ¬P
+
+# modus tollens
+1. (P → Q)  P
+2. ¬Q       P
+3. P        P
+4. Q        1, 3 →E
+5. ⊥        2, 4 →E  # contradicts 2
+6. ¬P       3-5 ¬I
+";
+
+  #[test]
+  fn proof_file_parse() {
+    let file: ProofFile = MODUS_TOLLENS.parse().unwrap();
+    assert_eq!(file.premises.as_ref().map(Vec::len), Some(2));
+    assert_eq!(file.conclusion.as_ref().map(Exp::to_string).as_deref(), Some("¬P"));
+    assert_eq!(file.entries[0], Entry::Comment("modus tollens".to_owned()));
+    let rows: Vec<(String, &str)> = file.rows().map(|(exp, rule)| (exp.to_string(), rule)).collect();
+    assert_eq!(rows[3], ("Q".to_owned(), "1, 3 →E"));
+    assert_eq!(
+      file.entries[5],
+      Entry::Row {
+        exp: Exp::Falsum,
+        rule: "2, 4 →E".to_owned(),
+        comment: Some("contradicts 2".to_owned()),
+      }
+    );
+  }
+
+  #[test]
+  fn proof_file_round_trip() {
+    let file: ProofFile = MODUS_TOLLENS.parse().unwrap();
+    assert_eq!(file.to_string(), MODUS_TOLLENS);
+
+    let sloppy = "  (P & Q) 1 &I\n\n#  note \n2.P 1 &E#end\n";
+    let file: ProofFile = sloppy.parse().unwrap();
+    assert_eq!(file.to_string(), "1. (P & Q)  1 &I\n# note\n2. P        1 &E  # end\n");
+    assert_eq!(file.to_string().parse(), Ok(file));
+  }
+
+  #[rstest]
+  #[case("1. P P\n3. Q P\n", 2, 1, ProofFileErrorKind::Numbering { expected: 2 })]
+  #[case("1. P P\n2.   (P &) 1 &E\n", 2, 9, ProofFileErrorKind::Sentence)]
+  #[case("1. P   \n", 1, 5, ProofFileErrorKind::MissingRule)]
+  #[case("1. P P\npremises: P\n", 2, 1, ProofFileErrorKind::Header)]
+  #[case("premises: P, (Q\n", 1, 16, ProofFileErrorKind::Sentence)]
+  fn proof_file_invalid(
+    #[case] text: &str,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] kind: ProofFileErrorKind,
+  ) {
+    assert_eq!(text.parse::<ProofFile>(), Err(ProofFileError { line, column, kind }));
+  }
+
+  #[test]
+  fn proof_file_read_lines() {
+    let lines = read_lines("1. (P →  P\n\n2. Q    1 &E  # note\nconclusion: Q\n");
+    assert_eq!(lines.len(), 4);
+    assert!(matches!(lines[0].content, Content::Invalid { is_row: true, .. }));
+    assert_eq!(lines[1].content, Content::Blank);
+    assert_eq!(
+      lines[2],
+      SourceLine {
+        line: 3,
+        content: Content::Row {
+          exp: Exp::Atom {
+            predicate: "Q".to_owned(),
+            individuals: vec![],
+          },
+          rule: "1 &E".to_owned(),
+          rule_column: 9,
+        },
+        comment: Some("note".to_owned()),
+      }
+    );
+    assert!(matches!(
+      lines[3].content,
+      Content::Invalid {
+        error: ProofFileError {
+          kind: ProofFileErrorKind::Header,
+          ..
+        },
+        is_row: false,
+      }
+    ));
+  }
+}
@@ -0,0 +1,439 @@
+use itertools::Itertools;
+use language::ast::exp::Exp;
+use nom::IResult;
+
+use super::{
+  exp_at, is_assumption_at, is_exist_generalization, is_exist_instantiation, is_univ_generalization, is_univ_instance,
+  unordered_tuple_eq, Discharge, Line, Profile, RuleSet,
+};
+use crate::{
+  apply::{and, both_orders, cond, exist, generalizations, iff, instance, or, sentence, univ, Licensed},
+  ast::rule::Rule,
+  dependency::RowDependency,
+  derived::{instantiate_conclusion, is_substitution_instance, substitution_instances, Registry},
+  justify::{earlier, lines_with},
+  parser::rule::rule,
+  theorem::Library,
+};
+
+/// The rules taught in the course this checker was written for: premises `P`, subproofs cited by
+/// range and `⊥` for contradictions. Lemmas in `lemmas` may be cited as rules (`Rule::Derived`) and
+/// results in `theorems` introduced by `TI`/`SI` (`Rule::SequentIntro`). Outside the classical
+/// `profile`, `¬E` is not allowed, nor `⊥` in minimal logic, nor lemmas and theorems needing them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Gentzen {
+  pub lemmas: Registry,
+  pub theorems: Library,
+  pub profile: Profile,
+}
+
+impl RuleSet for Gentzen {
+  type Rule = Rule;
+
+  fn parse<'a>(&self, s: &'a str) -> IResult<&'a str, Rule> {
+    rule(s)
+  }
+
+  fn assumption(&self) -> Rule {
+    Rule::Premise
+  }
+
+  fn cited_lines(&self, rule: &Rule) -> Vec<usize> {
+    rule.cited_lines()
+  }
+
+  fn renumbered(&self, rule: &Rule, renumber: &dyn Fn(usize) -> usize) -> Rule {
+    rule.renumbered(renumber)
+  }
+
+  fn discharges(&self, rule: &Rule) -> Vec<Discharge> {
+    rule
+      .discharged_ranges()
+      .into_iter()
+      .map(|(start, end)| Discharge::Range(start, end))
+      .collect()
+  }
+
+  fn dependency(&self, num: usize, rule: &Rule, acc: &[RowDependency]) -> RowDependency {
+    let get = |num: usize| acc.get(num - 1);
+    match *rule {
+      Rule::Premise => RowDependency::init_from([num]),
+      Rule::Derived(_, ref deps) | Rule::SequentIntro(ref deps, _) => {
+        deps.iter().fold(RowDependency::new(), |acc, &k| acc | get(k))
+      }
+      Rule::AndIntro(k, l) | Rule::IfExclude(k, l) | Rule::IffIntro(k, l) => RowDependency::new() | get(k) | get(l),
+      Rule::AndExclude(k)
+      | Rule::IffExclude(k)
+      | Rule::Falsum(k)
+      | Rule::UnivQuntIntro(k)
+      | Rule::UnivQuntExclude(k)
+      | Rule::ExisQuntIntro(k) => RowDependency::new() | get(k),
+      Rule::OrIntro(k, l) => RowDependency::new() | get(k) | l.and_then(get),
+      Rule::OrExclude(k, (l0, l1), (m0, m1)) => (RowDependency::new() | get(k) | get(l1) | get(m1)) - l0 - m0,
+      Rule::IfIntro((k0, k1)) => (RowDependency::new() | get(k1)) - k0,
+      Rule::NegIntro((k0, k1)) | Rule::NegExclude((k0, k1)) => (RowDependency::new() | get(k1)) - k0,
+      Rule::ExisQuntExclude(k, (l0, l1)) => (RowDependency::new() | get(k) | get(l1)) - l0,
+    }
+  }
+
+  fn is_valid(&self, _num: usize, exp: &Exp, rule: &Rule, lines: &[Line<Rule>], deps_list: &[RowDependency]) -> bool {
+    let exp_at = |num: usize| exp_at(lines, num);
+    let is_assumption_at = |num: usize| is_assumption_at(self, lines, num);
+
+    match *rule {
+      Rule::Premise => true,
+
+      Rule::Derived(ref name, ref deps) => {
+        let cited: Option<Vec<&Exp>> = deps.iter().map(|&k| exp_at(k)).collect();
+        match (self.lemmas.get(name), cited) {
+          (Some(lemma), Some(cited)) => self.profile.allows(lemma.profile) && lemma.is_instance(&cited, exp),
+          _ => false,
+        }
+      }
+
+      Rule::SequentIntro(ref deps, ref name) => {
+        let cited: Option<Vec<&Exp>> = deps.iter().map(|&k| exp_at(k)).collect();
+        match (self.theorems.get(name), cited) {
+          (Some(theorem), Some(cited)) => {
+            self.profile.allows(theorem.profile) && is_substitution_instance(&theorem.sequent, &cited, exp)
+          }
+          _ => false,
+        }
+      }
+
+      Rule::AndIntro(k, l) => match (exp, exp_at(k), exp_at(l)) {
+        (Exp::And { lhs, rhs }, Some(exp_k), Some(exp_l)) => (**lhs == *exp_k) && (**rhs == *exp_l),
+        _ => false,
+      },
+
+      Rule::AndExclude(k) => match exp_at(k) {
+        Some(Exp::And { lhs, rhs }) => (*exp == **lhs) || (*exp == **rhs),
+        _ => false,
+      },
+
+      Rule::OrIntro(k, None) => match (exp, exp_at(k)) {
+        (Exp::Or { lhs, rhs }, Some(exp_k)) => (*exp_k == **lhs) || (*exp_k == **rhs),
+        _ => false,
+      },
+
+      Rule::OrIntro(k, Some(l)) => match (exp, exp_at(k), exp_at(l)) {
+        (Exp::Or { lhs, rhs }, Some(exp_k), Some(exp_l)) => unordered_tuple_eq((exp_k, exp_l), (lhs, rhs)),
+        _ => false,
+      },
+
+      Rule::OrExclude(k, (l0, l1), (m0, m1)) => match (exp_at(k), exp_at(l0), exp_at(l1), exp_at(m0), exp_at(m1)) {
+        (Some(Exp::Or { lhs, rhs }), Some(exp_l0), Some(exp_l1), Some(exp_m0), Some(exp_m1)) => {
+          is_assumption_at(l0)
+            && is_assumption_at(m0)
+            && unordered_tuple_eq((lhs, rhs), (exp_l0, exp_m0))
+            && (exp == exp_l1)
+            && (exp == exp_m1)
+        }
+        _ => false,
+      },
+
+      Rule::IfIntro((Some(k0), k1)) => match (exp, exp_at(k0), exp_at(k1)) {
+        (Exp::Cond { antecedent, consequent }, Some(exp_k0), Some(exp_k1)) => {
+          is_assumption_at(k0) && (*exp_k0 == **antecedent) && (*exp_k1 == **consequent)
+        }
+        _ => false,
+      },
+
+      Rule::IfIntro((None, k)) => match (exp, exp_at(k)) {
+        (Exp::Cond { consequent, .. }, Some(exp_k)) => *exp_k == **consequent,
+        _ => false,
+      },
+
+      Rule::IfExclude(k, l) => match (exp, exp_at(k), exp_at(l)) {
+        (Exp::Falsum, Some(exp_k), Some(exp_l)) => (exp_k.negated() == *exp_l) || (*exp_k == exp_l.negated()),
+        (_, Some(Exp::Cond { antecedent, consequent }), Some(exp_l)) => {
+          (**antecedent == *exp_l) && (**consequent == *exp)
+        }
+        _ => false,
+      },
+
+      Rule::IffIntro(k, l) => match (exp, exp_at(k), exp_at(l)) {
+        (
+          Exp::Iff { lhs, rhs },
+          Some(Exp::Cond {
+            antecedent: exp_k_antecedent,
+            consequent: exp_k_consequent,
+          }),
+          Some(Exp::Cond {
+            antecedent: exp_l_antecedent,
+            consequent: exp_l_consequent,
+          }),
+        ) => {
+          unordered_tuple_eq((lhs, rhs), (exp_k_antecedent, exp_k_consequent))
+            && (*exp_k_antecedent == *exp_l_consequent)
+            && (*exp_k_consequent == *exp_l_antecedent)
+        }
+        _ => false,
+      },
+
+      Rule::IffExclude(k) => match (exp, exp_at(k)) {
+        (Exp::Cond { antecedent, consequent }, Some(Exp::Iff { lhs, rhs })) => {
+          unordered_tuple_eq((antecedent, consequent), (lhs, rhs))
+        }
+        _ => false,
+      },
+
+      Rule::Falsum(k) => self.profile.allows(Profile::Intuitionistic) && exp_at(k) == Some(&Exp::Falsum),
+
+      Rule::NegIntro((k0, k1)) => match (exp, exp_at(k0), exp_at(k1)) {
+        (Exp::Neg(negated), Some(exp_k0), Some(Exp::Falsum)) => **negated == *exp_k0,
+        _ => false,
+      },
+
+      Rule::NegExclude((k0, k1)) => match (exp_at(k0), exp_at(k1)) {
+        (Some(Exp::Neg(negated)), Some(Exp::Falsum)) => self.profile.allows(Profile::Classical) && *exp == **negated,
+        _ => false,
+      },
+
+      Rule::UnivQuntIntro(k) => match exp_at(k) {
+        Some(exp_k) => is_univ_generalization(exp, exp_k, k, lines, deps_list),
+        None => false,
+      },
+
+      Rule::UnivQuntExclude(k) => match exp_at(k) {
+        Some(exp_k) => is_univ_instance(exp, exp_k),
+        None => false,
+      },
+
+      Rule::ExisQuntIntro(k) => match exp_at(k) {
+        Some(exp_k) => is_exist_generalization(exp, exp_k),
+        None => false,
+      },
+
+      Rule::ExisQuntExclude(k, (l, m)) => match (exp_at(k), exp_at(l), exp_at(m)) {
+        (Some(exp_k), Some(exp_l), Some(exp_m)) => {
+          is_assumption_at(l) && is_exist_instantiation(exp, (exp_k, exp_l, exp_m), (l, m), lines, deps_list)
+        }
+        _ => false,
+      },
+    }
+  }
+
+  fn apply(&self, rule: &Rule, lines: &[Line<Rule>]) -> Vec<Licensed> {
+    let exp_at = |num: usize| exp_at(lines, num);
+    let exact = |exps: Vec<Exp>| exps.into_iter().map(Licensed::Exp).collect();
+
+    match *rule {
+      Rule::Premise => vec![],
+
+      Rule::Derived(ref name, ref deps) | Rule::SequentIntro(ref deps, ref name) => {
+        let sequent = match rule {
+          Rule::Derived(..) => self.lemmas.get(name).map(|lemma| &lemma.sequent),
+          _ => self.theorems.get(name).map(|theorem| &theorem.sequent),
+        };
+        let cited: Option<Vec<&Exp>> = deps.iter().map(|&k| exp_at(k)).collect();
+        match (sequent, cited) {
+          (Some(sequent), Some(cited)) => exact(instantiate_conclusion(sequent, &cited).into_iter().collect()),
+          _ => vec![],
+        }
+      }
+
+      Rule::AndIntro(k, l) => match (exp_at(k), exp_at(l)) {
+        (Some(exp_k), Some(exp_l)) => exact(vec![and(Box::new(exp_k.clone()), Box::new(exp_l.clone()))]),
+        _ => vec![],
+      },
+
+      Rule::AndExclude(k) => match exp_at(k) {
+        Some(Exp::And { lhs, rhs }) if lhs == rhs => exact(vec![*lhs.clone()]),
+        Some(Exp::And { lhs, rhs }) => exact(vec![*lhs.clone(), *rhs.clone()]),
+        _ => vec![],
+      },
+
+      Rule::OrIntro(k, None) => match exp_at(k) {
+        Some(exp_k) => both_orders(exp_k, &sentence(), or)
+          .into_iter()
+          .map(Licensed::AnySentence)
+          .collect(),
+        None => vec![],
+      },
+
+      Rule::OrIntro(k, Some(l)) => match (exp_at(k), exp_at(l)) {
+        (Some(exp_k), Some(exp_l)) => exact(both_orders(exp_k, exp_l, or)),
+        _ => vec![],
+      },
+
+      Rule::OrExclude(_, (_, l1), _) | Rule::ExisQuntExclude(_, (_, l1)) => {
+        exact(exp_at(l1).cloned().into_iter().collect())
+      }
+
+      Rule::IfIntro((Some(k0), k1)) => match (exp_at(k0), exp_at(k1)) {
+        (Some(exp_k0), Some(exp_k1)) => exact(vec![cond(Box::new(exp_k0.clone()), Box::new(exp_k1.clone()))]),
+        _ => vec![],
+      },
+
+      Rule::IfIntro((None, k)) => match exp_at(k) {
+        Some(exp_k) => vec![Licensed::AnySentence(cond(
+          Box::new(sentence()),
+          Box::new(exp_k.clone()),
+        ))],
+        None => vec![],
+      },
+
+      Rule::IfExclude(k, l) => match (exp_at(k), exp_at(l)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_l)) if **antecedent == *exp_l => {
+          exact(vec![*consequent.clone()])
+        }
+        (Some(exp_k), Some(exp_l)) if (exp_k.negated() == *exp_l) || (*exp_k == exp_l.negated()) => {
+          exact(vec![Exp::Falsum])
+        }
+        _ => vec![],
+      },
+
+      Rule::IffIntro(k, l) => match (exp_at(k), exp_at(l)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_l))
+          if *exp_l == cond(consequent.clone(), antecedent.clone()) =>
+        {
+          exact(both_orders(antecedent, consequent, iff))
+        }
+        _ => vec![],
+      },
+
+      Rule::IffExclude(k) => match exp_at(k) {
+        Some(Exp::Iff { lhs, rhs }) => exact(both_orders(lhs, rhs, cond)),
+        _ => vec![],
+      },
+
+      Rule::Falsum(k) => match exp_at(k) {
+        Some(Exp::Falsum) => vec![Licensed::AnySentence(sentence())],
+        _ => vec![],
+      },
+
+      Rule::NegIntro((k0, k1)) => match (exp_at(k0), exp_at(k1)) {
+        (Some(exp_k0), Some(Exp::Falsum)) => exact(vec![exp_k0.negated()]),
+        _ => vec![],
+      },
+
+      Rule::NegExclude((k0, k1)) => match (exp_at(k0), exp_at(k1)) {
+        (Some(Exp::Neg(negated)), Some(Exp::Falsum)) => exact(vec![*negated.clone()]),
+        _ => vec![],
+      },
+
+      Rule::UnivQuntIntro(k) => exp_at(k).map_or(vec![], |exp_k| generalizations(exp_k, univ)),
+
+      Rule::UnivQuntExclude(k) => exp_at(k).map_or(vec![], instance),
+
+      Rule::ExisQuntIntro(k) => exp_at(k).map_or(vec![], |exp_k| generalizations(exp_k, exist)),
+    }
+  }
+
+  fn candidates(&self, num: usize, lines: &[Line<Rule>]) -> Vec<Rule> {
+    let exp = match exp_at(lines, num) {
+      Some(exp) => exp,
+      None => return vec![],
+    };
+    let earlier = earlier(lines, num);
+    let with = |target: &Exp| lines_with(&earlier, target);
+    let assumptions_with = |target: &Exp| {
+      with(target)
+        .into_iter()
+        .filter(|&k| is_assumption_at(self, lines, k))
+        .collect_vec()
+    };
+    // a subproof from an assumption of `start` to a line of `end`
+    let subproofs = |start: &Exp, end: &Exp| {
+      assumptions_with(start)
+        .into_iter()
+        .cartesian_product(with(end))
+        .filter(|&(k0, k1)| k0 <= k1)
+        .collect_vec()
+    };
+    let mut rules = vec![];
+
+    // introductions, by the shape of the sentence
+    match exp {
+      Exp::And { lhs, rhs } => {
+        for (k, l) in with(lhs).into_iter().cartesian_product(with(rhs)) {
+          rules.push(Rule::AndIntro(k, l));
+        }
+      }
+      Exp::Or { lhs, rhs } => {
+        for k in with(lhs).into_iter().chain(with(rhs)) {
+          rules.push(Rule::OrIntro(k, None));
+        }
+      }
+      Exp::Cond { antecedent, consequent } => {
+        for range in subproofs(antecedent, consequent) {
+          rules.push(Rule::IfIntro((Some(range.0), range.1)));
+        }
+        for k in with(consequent) {
+          rules.push(Rule::IfIntro((None, k)));
+        }
+      }
+      Exp::Iff { lhs, rhs } => {
+        let forward = with(&cond(lhs.clone(), rhs.clone()));
+        let backward = with(&cond(rhs.clone(), lhs.clone()));
+        for (k, l) in forward.into_iter().cartesian_product(backward) {
+          rules.push(Rule::IffIntro(k, l));
+          rules.push(Rule::IffIntro(l, k));
+        }
+      }
+      Exp::Neg(negated) => {
+        for range in subproofs(negated, &Exp::Falsum) {
+          rules.push(Rule::NegIntro(range));
+        }
+      }
+      Exp::Falsum => {
+        for &(k, exp_k) in earlier.iter() {
+          for l in with(&exp_k.negated()) {
+            rules.push(Rule::IfExclude(k, l));
+            rules.push(Rule::IfExclude(l, k));
+          }
+        }
+      }
+      Exp::UnivGenr { .. } => rules.extend(earlier.iter().map(|&(k, _)| Rule::UnivQuntIntro(k))),
+      Exp::ExistGenr { .. } => rules.extend(earlier.iter().map(|&(k, _)| Rule::ExisQuntIntro(k))),
+      Exp::Atom { .. } => {}
+    }
+
+    // eliminations, by the shape of the cited lines
+    for &(k, exp_k) in earlier.iter() {
+      match exp_k {
+        Exp::And { lhs, rhs } if **lhs == *exp || **rhs == *exp => rules.push(Rule::AndExclude(k)),
+        Exp::Cond { antecedent, consequent } if **consequent == *exp => {
+          rules.extend(with(antecedent).into_iter().map(|l| Rule::IfExclude(k, l)));
+        }
+        Exp::Iff { .. } => rules.push(Rule::IffExclude(k)),
+        Exp::Falsum => rules.push(Rule::Falsum(k)),
+        Exp::UnivGenr { .. } => rules.push(Rule::UnivQuntExclude(k)),
+        Exp::Or { lhs, rhs } => {
+          for (l, m) in subproofs(lhs, exp).into_iter().cartesian_product(subproofs(rhs, exp)) {
+            // the two cases are separate subproofs
+            if l.1 < m.0 || m.1 < l.0 {
+              rules.push(Rule::OrExclude(k, l, m));
+            }
+          }
+        }
+        Exp::ExistGenr { .. } => {
+          for &(l, _) in earlier.iter().filter(|&&(l, _)| is_assumption_at(self, lines, l)) {
+            for m in with(exp).into_iter().filter(|&m| l <= m) {
+              rules.push(Rule::ExisQuntExclude(k, (l, m)));
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+    for range in subproofs(&exp.negated(), &Exp::Falsum) {
+      rules.push(Rule::NegExclude(range));
+    }
+
+    // lemmas and theorems whose conclusion fits, citing lines that fit each premise
+    for lemma in self.lemmas.iter() {
+      for deps in substitution_instances(&lemma.sequent, exp, &earlier) {
+        rules.push(Rule::Derived(lemma.name.clone(), deps));
+      }
+    }
+    for theorem in self.theorems.iter() {
+      for deps in substitution_instances(&theorem.sequent, exp, &earlier) {
+        rules.push(Rule::SequentIntro(deps, theorem.name.clone()));
+      }
+    }
+
+    rules
+  }
+}
@@ -0,0 +1,328 @@
+use itertools::Itertools;
+use language::ast::exp::Exp;
+use nom::IResult;
+
+use super::{
+  exp_at, is_assumption_at, is_exist_generalization, is_exist_instantiation, is_univ_generalization, is_univ_instance,
+  unordered_tuple_eq, Discharge, Line, Profile, RuleSet,
+};
+use crate::{
+  apply::{and, both_orders, cond, exist, generalizations, instance, or, sentence, univ, Licensed},
+  ast::lemmon_rule::LemmonRule,
+  dependency::RowDependency,
+  justify::{earlier, lines_with},
+  parser::lemmon_rule::rule,
+};
+
+/// The system of Lemmon's *Beginning Logic*: assumptions `A`, discharged by citing the assumption line,
+/// and contradictions written as `B & ¬B`. Eliminating a double negation by `DN` needs the classical
+/// `profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Lemmon {
+  pub profile: Profile,
+}
+
+impl RuleSet for Lemmon {
+  type Rule = LemmonRule;
+
+  fn parse<'a>(&self, s: &'a str) -> IResult<&'a str, LemmonRule> {
+    rule(s)
+  }
+
+  fn assumption(&self) -> LemmonRule {
+    LemmonRule::Assumption
+  }
+
+  fn cited_lines(&self, rule: &LemmonRule) -> Vec<usize> {
+    rule.cited_lines()
+  }
+
+  fn renumbered(&self, rule: &LemmonRule, renumber: &dyn Fn(usize) -> usize) -> LemmonRule {
+    rule.renumbered(renumber)
+  }
+
+  fn discharges(&self, rule: &LemmonRule) -> Vec<Discharge> {
+    rule.discharged_lines().into_iter().map(Discharge::Line).collect()
+  }
+
+  fn dependency(&self, num: usize, rule: &LemmonRule, acc: &[RowDependency]) -> RowDependency {
+    let get = |num: usize| acc.get(num - 1);
+    let discharged = |num: usize, assumption: usize| get(num).cloned().map(|dep| dep - assumption);
+    match *rule {
+      LemmonRule::Assumption => RowDependency::init_from([num]),
+      LemmonRule::Mpp(m, n) | LemmonRule::Mtt(m, n) | LemmonRule::AndIntro(m, n) => {
+        RowDependency::new() | get(m) | get(n)
+      }
+      LemmonRule::Dn(m)
+      | LemmonRule::AndExclude(m)
+      | LemmonRule::OrIntro(m)
+      | LemmonRule::Ui(m)
+      | LemmonRule::Ug(m)
+      | LemmonRule::Eg(m) => RowDependency::new() | get(m),
+      LemmonRule::Cp(m, n) | LemmonRule::Raa(m, n) => (RowDependency::new() | get(n)) - m,
+      LemmonRule::OrExclude(k, l, m, n, o) => {
+        RowDependency::new() | get(k) | discharged(m, l).as_ref() | discharged(o, n).as_ref()
+      }
+      LemmonRule::Ei(k, l, m) => RowDependency::new() | get(k) | discharged(m, l).as_ref(),
+    }
+  }
+
+  fn is_valid(
+    &self,
+    _num: usize,
+    exp: &Exp,
+    rule: &LemmonRule,
+    lines: &[Line<LemmonRule>],
+    deps_list: &[RowDependency],
+  ) -> bool {
+    let exp_at = |num: usize| exp_at(lines, num);
+    let is_assumption_at = |num: usize| is_assumption_at(self, lines, num);
+
+    match *rule {
+      LemmonRule::Assumption => true,
+
+      LemmonRule::Mpp(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_n)) => (**antecedent == *exp_n) && (**consequent == *exp),
+        _ => false,
+      },
+
+      LemmonRule::Mtt(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_n)) => {
+          (consequent.negated() == *exp_n) && (antecedent.negated() == *exp)
+        }
+        _ => false,
+      },
+
+      LemmonRule::Dn(m) => match exp_at(m) {
+        Some(exp_m) => {
+          (exp_m.negated().negated() == *exp)
+            || (self.profile.allows(Profile::Classical) && (*exp_m == exp.negated().negated()))
+        }
+        None => false,
+      },
+
+      LemmonRule::Cp(m, n) => match (exp, exp_at(m), exp_at(n)) {
+        (Exp::Cond { antecedent, consequent }, Some(exp_m), Some(exp_n)) => {
+          is_assumption_at(m) && (**antecedent == *exp_m) && (**consequent == *exp_n)
+        }
+        _ => false,
+      },
+
+      LemmonRule::AndIntro(m, n) => match (exp, exp_at(m), exp_at(n)) {
+        (Exp::And { lhs, rhs }, Some(exp_m), Some(exp_n)) => (**lhs == *exp_m) && (**rhs == *exp_n),
+        _ => false,
+      },
+
+      LemmonRule::AndExclude(m) => match exp_at(m) {
+        Some(Exp::And { lhs, rhs }) => (*exp == **lhs) || (*exp == **rhs),
+        _ => false,
+      },
+
+      LemmonRule::OrIntro(m) => match (exp, exp_at(m)) {
+        (Exp::Or { lhs, rhs }, Some(exp_m)) => (*exp_m == **lhs) || (*exp_m == **rhs),
+        _ => false,
+      },
+
+      LemmonRule::OrExclude(k, l, m, n, o) => match (exp_at(k), exp_at(l), exp_at(m), exp_at(n), exp_at(o)) {
+        (Some(Exp::Or { lhs, rhs }), Some(exp_l), Some(exp_m), Some(exp_n), Some(exp_o)) => {
+          is_assumption_at(l)
+            && is_assumption_at(n)
+            && unordered_tuple_eq((lhs, rhs), (exp_l, exp_n))
+            && (exp == exp_m)
+            && (exp == exp_o)
+        }
+        _ => false,
+      },
+
+      LemmonRule::Raa(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(exp_m), Some(Exp::And { lhs, rhs })) => {
+          is_assumption_at(m) && ((lhs.negated() == **rhs) || (**lhs == rhs.negated())) && (exp_m.negated() == *exp)
+        }
+        _ => false,
+      },
+
+      LemmonRule::Ui(m) => match exp_at(m) {
+        Some(exp_m) => is_univ_instance(exp, exp_m),
+        None => false,
+      },
+
+      LemmonRule::Ug(m) => match exp_at(m) {
+        Some(exp_m) => is_univ_generalization(exp, exp_m, m, lines, deps_list),
+        None => false,
+      },
+
+      LemmonRule::Eg(m) => match exp_at(m) {
+        Some(exp_m) => is_exist_generalization(exp, exp_m),
+        None => false,
+      },
+
+      LemmonRule::Ei(k, l, m) => match (exp_at(k), exp_at(l), exp_at(m)) {
+        (Some(exp_k), Some(exp_l), Some(exp_m)) => {
+          is_assumption_at(l) && is_exist_instantiation(exp, (exp_k, exp_l, exp_m), (l, m), lines, deps_list)
+        }
+        _ => false,
+      },
+    }
+  }
+
+  fn apply(&self, rule: &LemmonRule, lines: &[Line<LemmonRule>]) -> Vec<Licensed> {
+    let exp_at = |num: usize| exp_at(lines, num);
+    let exact = |exps: Vec<Exp>| exps.into_iter().map(Licensed::Exp).collect();
+
+    match *rule {
+      LemmonRule::Assumption => vec![],
+
+      LemmonRule::Mpp(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_n)) if **antecedent == *exp_n => {
+          exact(vec![*consequent.clone()])
+        }
+        _ => vec![],
+      },
+
+      LemmonRule::Mtt(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(Exp::Cond { antecedent, consequent }), Some(exp_n)) if consequent.negated() == *exp_n => {
+          exact(vec![antecedent.negated()])
+        }
+        _ => vec![],
+      },
+
+      LemmonRule::Dn(m) => match exp_at(m) {
+        Some(exp_m) => {
+          let mut exps = vec![exp_m.negated().negated()];
+          if let Exp::Neg(negated) = exp_m {
+            if let Exp::Neg(exp) = &**negated {
+              exps.insert(0, *exp.clone());
+            }
+          }
+          exact(exps)
+        }
+        None => vec![],
+      },
+
+      LemmonRule::Cp(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(exp_m), Some(exp_n)) => exact(vec![cond(Box::new(exp_m.clone()), Box::new(exp_n.clone()))]),
+        _ => vec![],
+      },
+
+      LemmonRule::AndIntro(m, n) => match (exp_at(m), exp_at(n)) {
+        (Some(exp_m), Some(exp_n)) => exact(vec![and(Box::new(exp_m.clone()), Box::new(exp_n.clone()))]),
+        _ => vec![],
+      },
+
+      LemmonRule::AndExclude(m) => match exp_at(m) {
+        Some(Exp::And { lhs, rhs }) if lhs == rhs => exact(vec![*lhs.clone()]),
+        Some(Exp::And { lhs, rhs }) => exact(vec![*lhs.clone(), *rhs.clone()]),
+        _ => vec![],
+      },
+
+      LemmonRule::OrIntro(m) => match exp_at(m) {
+        Some(exp_m) => both_orders(exp_m, &sentence(), or)
+          .into_iter()
+          .map(Licensed::AnySentence)
+          .collect(),
+        None => vec![],
+      },
+
+      LemmonRule::OrExclude(_, _, m, _, _) | LemmonRule::Ei(_, _, m) => exact(exp_at(m).cloned().into_iter().collect()),
+
+      LemmonRule::Raa(m, _) => exact(exp_at(m).map(Exp::negated).into_iter().collect()),
+
+      LemmonRule::Ui(m) => exp_at(m).map_or(vec![], instance),
+
+      LemmonRule::Ug(m) => exp_at(m).map_or(vec![], |exp_m| generalizations(exp_m, univ)),
+
+      LemmonRule::Eg(m) => exp_at(m).map_or(vec![], |exp_m| generalizations(exp_m, exist)),
+    }
+  }
+
+  fn candidates(&self, num: usize, lines: &[Line<LemmonRule>]) -> Vec<LemmonRule> {
+    let exp = match exp_at(lines, num) {
+      Some(exp) => exp,
+      None => return vec![],
+    };
+    let earlier = earlier(lines, num);
+    let with = |target: &Exp| lines_with(&earlier, target);
+    // an assumption of `start` and a later line of `end`
+    let discharging = |start: &Exp, end: &Exp| {
+      with(start)
+        .into_iter()
+        .filter(|&m| is_assumption_at(self, lines, m))
+        .cartesian_product(with(end))
+        .filter(|&(m, n)| m <= n)
+        .collect_vec()
+    };
+    let mut rules = vec![];
+
+    // introductions, by the shape of the sentence
+    match exp {
+      Exp::And { lhs, rhs } => {
+        for (m, n) in with(lhs).into_iter().cartesian_product(with(rhs)) {
+          rules.push(LemmonRule::AndIntro(m, n));
+        }
+      }
+      Exp::Or { lhs, rhs } => rules.extend(with(lhs).into_iter().chain(with(rhs)).map(LemmonRule::OrIntro)),
+      Exp::Cond { antecedent, consequent } => {
+        for (m, n) in discharging(antecedent, consequent) {
+          rules.push(LemmonRule::Cp(m, n));
+        }
+      }
+      Exp::Neg(negated) => {
+        let contradictions = earlier.iter().filter(|(_, exp_n)| match exp_n {
+          Exp::And { lhs, rhs } => (lhs.negated() == **rhs) || (**lhs == rhs.negated()),
+          _ => false,
+        });
+        for (m, &(n, _)) in with(negated).into_iter().cartesian_product(contradictions) {
+          if m <= n && is_assumption_at(self, lines, m) {
+            rules.push(LemmonRule::Raa(m, n));
+          }
+        }
+      }
+      Exp::UnivGenr { .. } => rules.extend(earlier.iter().map(|&(m, _)| LemmonRule::Ug(m))),
+      Exp::ExistGenr { .. } => rules.extend(earlier.iter().map(|&(m, _)| LemmonRule::Eg(m))),
+      _ => {}
+    }
+    rules.extend(with(&exp.negated().negated()).into_iter().map(LemmonRule::Dn));
+    if let Exp::Neg(negated) = exp {
+      if let Exp::Neg(exp) = &**negated {
+        rules.extend(with(exp).into_iter().map(LemmonRule::Dn));
+      }
+    }
+
+    // eliminations, by the shape of the cited lines
+    for &(k, exp_k) in earlier.iter() {
+      match exp_k {
+        Exp::And { lhs, rhs } if **lhs == *exp || **rhs == *exp => rules.push(LemmonRule::AndExclude(k)),
+        Exp::Cond { antecedent, consequent } => {
+          if **consequent == *exp {
+            rules.extend(with(antecedent).into_iter().map(|n| LemmonRule::Mpp(k, n)));
+          }
+          if antecedent.negated() == *exp {
+            rules.extend(with(&consequent.negated()).into_iter().map(|n| LemmonRule::Mtt(k, n)));
+          }
+        }
+        Exp::UnivGenr { .. } => rules.push(LemmonRule::Ui(k)),
+        Exp::Or { lhs, rhs } => {
+          for ((l, m), (n, o)) in discharging(lhs, exp)
+            .into_iter()
+            .cartesian_product(discharging(rhs, exp))
+          {
+            // the two cases are separate subproofs
+            if m < n || o < l {
+              rules.push(LemmonRule::OrExclude(k, l, m, n, o));
+            }
+          }
+        }
+        Exp::ExistGenr { .. } => {
+          for &(l, _) in earlier.iter().filter(|&&(l, _)| is_assumption_at(self, lines, l)) {
+            for m in with(exp).into_iter().filter(|&m| l <= m) {
+              rules.push(LemmonRule::Ei(k, l, m));
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    rules
+  }
+}
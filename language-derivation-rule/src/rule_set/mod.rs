@@ -0,0 +1,561 @@
+pub mod gentzen;
+pub mod lemmon;
+
+use std::{collections::BTreeSet, error, fmt, str::FromStr, sync::Arc};
+
+use language::ast::exp::Exp;
+use nom::IResult;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  apply::Licensed,
+  dependency::RowDependency,
+  derived::Registry,
+  spec::SpecSet,
+  theorem::Library,
+  tree::{Tree, TreeError},
+};
+
+pub use self::{gentzen::Gentzen, lemmon::Lemmon};
+
+/// A line of a derivation, as far as it could be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line<R> {
+  pub exp: Option<Exp>,
+  pub rule: Option<R>,
+}
+
+/// An assumption a rule discharges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Discharge {
+  /// The subproof `start-end`, whose first line is the assumption.
+  Range(usize, usize),
+  /// Only the assumption line; nothing is closed, so later lines may still cite the lines after it.
+  Line(usize),
+}
+
+impl Discharge {
+  pub fn assumption(&self) -> usize {
+    match *self {
+      Discharge::Range(start, _) | Discharge::Line(start) => start,
+    }
+  }
+}
+
+/// A natural deduction system: the syntax of its rules, what they cite and how they are checked.
+pub trait RuleSet {
+  type Rule: Clone + PartialEq + fmt::Debug + fmt::Display;
+
+  /// Parse a rule as written in the derivation column.
+  fn parse<'a>(&self, s: &'a str) -> IResult<&'a str, Self::Rule>;
+
+  /// The rule introducing an assumption.
+  fn assumption(&self) -> Self::Rule;
+
+  fn is_assumption(&self, rule: &Self::Rule) -> bool {
+    *rule == self.assumption()
+  }
+
+  /// Every line number the rule refers to.
+  fn cited_lines(&self, rule: &Self::Rule) -> Vec<usize>;
+
+  fn discharges(&self, rule: &Self::Rule) -> Vec<Discharge>;
+
+  /// The same rule citing `renumber(k)` for each line `k` it cites.
+  fn renumbered(&self, rule: &Self::Rule, renumber: &dyn Fn(usize) -> usize) -> Self::Rule;
+
+  /// Dependency of line `num`, given the dependencies of the lines before it. Citations are checked.
+  fn dependency(&self, num: usize, rule: &Self::Rule, acc: &[RowDependency]) -> RowDependency;
+
+  /// Whether `exp` on line `num` follows by `rule`. Citations are checked.
+  fn is_valid(
+    &self,
+    num: usize,
+    exp: &Exp,
+    rule: &Self::Rule,
+    lines: &[Line<Self::Rule>],
+    deps_list: &[RowDependency],
+  ) -> bool;
+
+  /// The sentences `rule` licenses from the lines it cites, whether or not the citations are right.
+  /// Nothing is suggested where the rule set cannot tell.
+  fn apply(&self, _rule: &Self::Rule, _lines: &[Line<Self::Rule>]) -> Vec<Licensed> {
+    vec![]
+  }
+
+  /// Rules worth trying on line `num`, citing only lines before it, for `justify::justify`. They are
+  /// checked afterwards, so they need only be plausible.
+  fn candidates(&self, _num: usize, _lines: &[Line<Self::Rule>]) -> Vec<Self::Rule> {
+    vec![]
+  }
+}
+
+/// Parse a whole rule, ignoring surrounding whitespace.
+pub fn parse_rule<R: RuleSet>(rule_set: &R, s: &str) -> Option<R::Rule> {
+  match rule_set.parse(s.trim()) {
+    Ok(("", rule)) => Some(rule),
+    _ => None,
+  }
+}
+
+/// How much of classical logic a derivation may use, from the weakest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Profile {
+  /// Intuitionistic logic without ex falso: `⊥` proves nothing by itself.
+  Minimal,
+  /// Classical logic without reductio (`¬E`) or double negation elimination.
+  Intuitionistic,
+  #[default]
+  Classical,
+}
+
+impl Profile {
+  pub const ALL: [Profile; 3] = [Profile::Minimal, Profile::Intuitionistic, Profile::Classical];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Profile::Minimal => "minimal",
+      Profile::Intuitionistic => "intuitionistic",
+      Profile::Classical => "classical",
+    }
+  }
+
+  /// Whether a rule sound in the logic `required` may be used.
+  pub fn allows(&self, required: Profile) -> bool {
+    required <= *self
+  }
+}
+
+impl fmt::Display for Profile {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for Profile {
+  type Err = NameError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Profile::ALL
+      .into_iter()
+      .find(|profile| profile.name() == s)
+      .ok_or_else(|| NameError::UnknownProfile(s.to_owned()))
+  }
+}
+
+/// A name that is not one of `Profile::ALL` or `System::ALL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+  UnknownProfile(String),
+  UnknownSystem(String),
+}
+
+impl fmt::Display for NameError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NameError::UnknownProfile(name) => write!(f, "unknown profile: {}", name),
+      NameError::UnknownSystem(name) => write!(f, "unknown system: {}", name),
+    }
+  }
+}
+
+impl error::Error for NameError {}
+
+/// The rule sets the checker knows, for choosing one at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "SystemRepr", into = "SystemRepr")]
+pub enum System {
+  #[default]
+  Gentzen,
+  Lemmon,
+  /// The rules of a spec supplied at runtime.
+  Spec(Arc<SpecSet>),
+}
+
+/// A system as documents write it: the name of a built-in system, or `spec:` followed by the text of a
+/// spec.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SystemRepr {
+  Name(String),
+  Spec { spec: String },
+}
+
+impl From<System> for SystemRepr {
+  fn from(system: System) -> Self {
+    match system {
+      System::Spec(specs) => SystemRepr::Spec {
+        spec: specs.to_string(),
+      },
+      system => SystemRepr::Name(system.name().to_owned()),
+    }
+  }
+}
+
+impl TryFrom<SystemRepr> for System {
+  type Error = String;
+
+  fn try_from(repr: SystemRepr) -> Result<Self, Self::Error> {
+    match repr {
+      SystemRepr::Name(name) => name.parse().map_err(|err: NameError| err.to_string()),
+      SystemRepr::Spec { spec } => match spec.parse() {
+        Ok(specs) => Ok(System::Spec(Arc::new(specs))),
+        Err(err) => Err(format!("invalid rule spec: {}", err)),
+      },
+    }
+  }
+}
+
+impl FromStr for System {
+  type Err = NameError;
+
+  /// A built-in system by its name. A `Spec` has no name to look up and is read as a `SpecSet`.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    System::ALL
+      .into_iter()
+      .find(|system| system.name() == s)
+      .ok_or_else(|| NameError::UnknownSystem(s.to_owned()))
+  }
+}
+
+impl System {
+  /// The built-in systems. A `Spec` has to be read from its text.
+  pub const ALL: [System; 2] = [System::Gentzen, System::Lemmon];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      System::Gentzen => "Gentzen",
+      System::Lemmon => "Lemmon",
+      System::Spec(_) => "Spec",
+    }
+  }
+
+  /// How an assumption is written in this system.
+  pub fn assumption(&self) -> String {
+    match self {
+      System::Gentzen => Gentzen::default().assumption().to_string(),
+      System::Lemmon => Lemmon::default().assumption().to_string(),
+      System::Spec(specs) => specs.assumption().to_string(),
+    }
+  }
+
+  pub fn is_assumption(&self, rule: &str) -> bool {
+    fn is_assumption<R: RuleSet>(rule_set: &R, rule: &str) -> bool {
+      parse_rule(rule_set, rule).is_some_and(|rule| rule_set.is_assumption(&rule))
+    }
+    match self {
+      System::Gentzen => is_assumption(&Gentzen::default(), rule),
+      System::Lemmon => is_assumption(&Lemmon::default(), rule),
+      System::Spec(specs) => is_assumption(specs.as_ref(), rule),
+    }
+  }
+
+  pub fn is_rule_syntax_valid(&self, rule: &str) -> bool {
+    match self {
+      System::Gentzen => parse_rule(&Gentzen::default(), rule).is_some(),
+      System::Lemmon => parse_rule(&Lemmon::default(), rule).is_some(),
+      System::Spec(specs) => parse_rule(specs.as_ref(), rule).is_some(),
+    }
+  }
+
+  /// The rule written in its canonical form, if it parses.
+  pub fn format_rule(&self, rule: &str) -> Option<String> {
+    match self {
+      System::Gentzen => parse_rule(&Gentzen::default(), rule).map(|rule| rule.to_string()),
+      System::Lemmon => parse_rule(&Lemmon::default(), rule).map(|rule| rule.to_string()),
+      System::Spec(specs) => parse_rule(specs.as_ref(), rule).map(|rule| rule.to_string()),
+    }
+  }
+
+  pub fn cited_lines(&self, rule: &str) -> Option<Vec<usize>> {
+    match self {
+      System::Gentzen => {
+        let gentzen = Gentzen::default();
+        parse_rule(&gentzen, rule).map(|rule| gentzen.cited_lines(&rule))
+      }
+      System::Lemmon => {
+        let lemmon = Lemmon::default();
+        parse_rule(&lemmon, rule).map(|rule| lemmon.cited_lines(&rule))
+      }
+      System::Spec(specs) => parse_rule(specs.as_ref(), rule).map(|rule| specs.cited_lines(&rule)),
+    }
+  }
+
+  /// Check a derivation given as sentences and unparsed rules, allowing only the rules of `profile`.
+  /// Only `Gentzen` can cite `lemmas` and `theorems`, and a spec has no profiles: all its rules are
+  /// allowed.
+  pub fn check(
+    &self,
+    rows: &[(Option<Exp>, &str)],
+    lemmas: &Registry,
+    theorems: &Library,
+    profile: Profile,
+  ) -> crate::check::Report {
+    match self {
+      System::Gentzen => crate::check::check_rows(
+        &Gentzen {
+          lemmas: lemmas.clone(),
+          theorems: theorems.clone(),
+          profile,
+        },
+        rows,
+      ),
+      System::Lemmon => crate::check::check_rows(&Lemmon { profile }, rows),
+      System::Spec(specs) => crate::check::check_rows(specs.as_ref(), rows),
+    }
+  }
+}
+
+impl System {
+  /// The sentences the rule of line `num` licenses, given the lines before it.
+  pub fn apply(
+    &self,
+    rows: &[(Option<Exp>, &str)],
+    num: usize,
+    lemmas: &Registry,
+    theorems: &Library,
+  ) -> Vec<Licensed> {
+    fn apply<R: RuleSet>(rule_set: &R, rows: &[(Option<Exp>, &str)], num: usize) -> Vec<Licensed> {
+      let lines: Vec<Line<R::Rule>> = rows
+        .iter()
+        .map(|(exp, rule)| Line {
+          exp: exp.clone(),
+          rule: parse_rule(rule_set, rule),
+        })
+        .collect();
+      match lines.get(num.wrapping_sub(1)) {
+        Some(Line { rule: Some(rule), .. }) => rule_set.apply(rule, &lines),
+        _ => vec![],
+      }
+    }
+    match self {
+      System::Gentzen => apply(
+        &Gentzen {
+          lemmas: lemmas.clone(),
+          theorems: theorems.clone(),
+          ..Gentzen::default()
+        },
+        rows,
+        num,
+      ),
+      System::Lemmon => apply(&Lemmon::default(), rows, num),
+      System::Spec(specs) => apply(specs.as_ref(), rows, num),
+    }
+  }
+
+  /// Every rule of `profile`, written out, by which the sentence of line `num` follows from the lines
+  /// before it.
+  pub fn justify(
+    &self,
+    rows: &[(Option<Exp>, &str)],
+    num: usize,
+    lemmas: &Registry,
+    theorems: &Library,
+    profile: Profile,
+  ) -> Vec<String> {
+    fn justify<R: RuleSet>(rule_set: &R, rows: &[(Option<Exp>, &str)], num: usize) -> Vec<String> {
+      let lines = parse_lines(rule_set, rows);
+      crate::justify::justify(rule_set, num, &lines)
+        .iter()
+        .map(|rule| rule.to_string())
+        .collect()
+    }
+    match self {
+      System::Gentzen => justify(
+        &Gentzen {
+          lemmas: lemmas.clone(),
+          theorems: theorems.clone(),
+          profile,
+        },
+        rows,
+        num,
+      ),
+      System::Lemmon => justify(&Lemmon { profile }, rows, num),
+      System::Spec(specs) => justify(specs.as_ref(), rows, num),
+    }
+  }
+}
+
+impl System {
+  /// The lines the last of `rules` uses; see `minimize::used`.
+  pub fn used(&self, rules: &[&str]) -> BTreeSet<usize> {
+    fn used<R: RuleSet>(rule_set: &R, rules: &[&str]) -> BTreeSet<usize> {
+      let rules: Vec<Option<R::Rule>> = rules.iter().map(|rule| parse_rule(rule_set, rule)).collect();
+      crate::minimize::used(rule_set, &rules)
+    }
+    match self {
+      System::Gentzen => used(&Gentzen::default(), rules),
+      System::Lemmon => used(&Lemmon::default(), rules),
+      System::Spec(specs) => used(specs.as_ref(), rules),
+    }
+  }
+
+  /// The old number and renumbered rule of each line `minimize::minimize` keeps. Rules that do not
+  /// parse are kept as written.
+  pub fn minimize(&self, rules: &[&str]) -> Vec<(usize, String)> {
+    fn minimize<R: RuleSet>(rule_set: &R, rules: &[&str]) -> Vec<(usize, String)> {
+      let parsed: Vec<Option<R::Rule>> = rules.iter().map(|rule| parse_rule(rule_set, rule)).collect();
+      crate::minimize::minimize(rule_set, &parsed)
+        .into_iter()
+        .map(|(num, rule)| match rule {
+          Some(rule) => (num, rule.to_string()),
+          None => (num, rules[num - 1].to_owned()),
+        })
+        .collect()
+    }
+    match self {
+      System::Gentzen => minimize(&Gentzen::default(), rules),
+      System::Lemmon => minimize(&Lemmon::default(), rules),
+      System::Spec(specs) => minimize(specs.as_ref(), rules),
+    }
+  }
+}
+
+impl System {
+  /// The proof tree of a derivation, with the rules of `profile`. Only `Gentzen` has one; Lemmon's
+  /// rules carry their dependencies along the lines rather than discharging assumptions, and a spec's
+  /// rules have no inferences to build a tree from.
+  pub fn tree(
+    &self,
+    rows: &[(Option<Exp>, &str)],
+    lemmas: &Registry,
+    theorems: &Library,
+    profile: Profile,
+  ) -> Option<Result<Tree, TreeError>> {
+    match self {
+      System::Gentzen => {
+        let gentzen = Gentzen {
+          lemmas: lemmas.clone(),
+          theorems: theorems.clone(),
+          profile,
+        };
+        Some(Tree::from_derivation(&parse_lines(&gentzen, rows), &gentzen))
+      }
+      System::Lemmon | System::Spec(_) => None,
+    }
+  }
+}
+
+pub(crate) fn parse_lines<R: RuleSet>(rule_set: &R, rows: &[(Option<Exp>, &str)]) -> Vec<Line<R::Rule>> {
+  rows
+    .iter()
+    .map(|(exp, rule)| Line {
+      exp: exp.clone(),
+      rule: parse_rule(rule_set, rule),
+    })
+    .collect()
+}
+
+impl fmt::Display for System {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+pub(crate) fn exp_at<R>(lines: &[Line<R>], num: usize) -> Option<&Exp> {
+  lines.get(num.checked_sub(1)?).and_then(|line| line.exp.as_ref())
+}
+
+pub(crate) fn is_assumption_at<R: RuleSet>(rule_set: &R, lines: &[Line<R::Rule>], num: usize) -> bool {
+  match num.checked_sub(1).and_then(|idx| lines.get(idx)) {
+    Some(Line { rule: Some(rule), .. }) => rule_set.is_assumption(rule),
+    _ => false,
+  }
+}
+
+pub(crate) fn one_or_none(set: &BTreeSet<String>) -> Option<String> {
+  if set.len() == 1 {
+    set.iter().next().cloned()
+  } else {
+    None
+  }
+}
+
+pub(crate) fn unordered_tuple_eq((a1, a2): (&Exp, &Exp), (b1, b2): (&Exp, &Exp)) -> bool {
+  ((a1 == b1) && (a2 == b2)) || ((a1 == b2) && (a2 == b1))
+}
+
+/// Whether any line `deps` depends on, except `except`, mentions the individual `beta`.
+pub(crate) fn mentioned_in_deps<R>(lines: &[Line<R>], deps: &RowDependency, beta: &str, except: Option<usize>) -> bool {
+  deps
+    .nums
+    .iter()
+    .filter(|&&num| Some(num) != except)
+    .any(|&num| match exp_at(lines, num) {
+      Some(exp) => exp.free_variables().contains(beta),
+      None => true,
+    })
+}
+
+/// `exp` generalizes `exp_k` over an individual no assumption of line `k` mentions.
+pub(crate) fn is_univ_generalization<R>(
+  exp: &Exp,
+  exp_k: &Exp,
+  k: usize,
+  lines: &[Line<R>],
+  deps_list: &[RowDependency],
+) -> bool {
+  match exp {
+    Exp::UnivGenr { variable, form } => {
+      let beta = match one_or_none(&(&exp_k.free_variables() - &form.free_variables())) {
+        Some(beta) => beta,
+        None => return false,
+      };
+      match deps_list.get(k - 1) {
+        Some(deps) if !mentioned_in_deps(lines, deps, &beta, None) => form.var_replaced(variable, &beta) == *exp_k,
+        _ => false,
+      }
+    }
+    _ => false,
+  }
+}
+
+/// `exp` instantiates the universal `exp_k`.
+pub(crate) fn is_univ_instance(exp: &Exp, exp_k: &Exp) -> bool {
+  match exp_k {
+    Exp::UnivGenr { variable: alpha, form } => match one_or_none(&(&exp.free_variables() - &form.free_variables())) {
+      Some(beta) => form.var_replaced(alpha, &beta) == *exp,
+      None => false,
+    },
+    _ => false,
+  }
+}
+
+/// `exp` is an existential generalization of `exp_k`.
+pub(crate) fn is_exist_generalization(exp: &Exp, exp_k: &Exp) -> bool {
+  match exp {
+    Exp::ExistGenr { variable: alpha, form } => {
+      match one_or_none(&(&exp_k.free_variables() - &form.free_variables())) {
+        Some(beta) => form.var_replaced(alpha, &beta) == *exp_k,
+        None => false,
+      }
+    }
+    _ => false,
+  }
+}
+
+/// `exp` follows from the existential `exp_k` by the assumption `exp_l` of an arbitrary instance on
+/// line `l` and `exp_m` on line `m`.
+pub(crate) fn is_exist_instantiation<R>(
+  exp: &Exp,
+  (exp_k, exp_l, exp_m): (&Exp, &Exp, &Exp),
+  (l, m): (usize, usize),
+  lines: &[Line<R>],
+  deps_list: &[RowDependency],
+) -> bool {
+  match exp_k {
+    Exp::ExistGenr { variable: alpha, form } => {
+      let beta = match one_or_none(&(&(&exp_l.free_variables() - &form.free_variables()) - &exp_m.free_variables())) {
+        Some(beta) => beta,
+        None => return false,
+      };
+      match deps_list.get(m - 1) {
+        Some(deps) if !mentioned_in_deps(lines, deps, &beta, Some(l)) => {
+          (form.var_replaced(alpha, &beta) == *exp_l) && (exp_m == exp)
+        }
+        _ => false,
+      }
+    }
+    _ => false,
+  }
+}
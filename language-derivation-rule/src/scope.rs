@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+
+use crate::rule_set::{Discharge, RuleSet};
+
+/// A subproof: the assumption on line `start` is open up to line `end` and discharged by the rule on
+/// line `closed_by`. Line numbers start from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope {
+  pub start: usize,
+  pub end: usize,
+  pub closed_by: usize,
+}
+
+impl Scope {
+  pub fn contains(&self, num: usize) -> bool {
+    (self.start..=self.end).contains(&num)
+  }
+}
+
+/// Scopes opened by assumption lines and closed by the rules discharging them, ordered by start line
+/// and outer scopes first.
+///
+/// A discharged range is a scope only if its first line is an assumption and it lies before the
+/// discharging line. Assumptions discharged by line alone (see `Discharge::Line`) open no scope.
+pub fn scopes<R: RuleSet>(rule_set: &R, rules: &[Option<R::Rule>]) -> Vec<Scope> {
+  let mut scopes: Vec<Scope> = rules
+    .iter()
+    .enumerate()
+    .filter_map(|(idx, rule)| rule.as_ref().map(|rule| (idx + 1, rule)))
+    .flat_map(|(num, rule)| {
+      rule_set
+        .discharges(rule)
+        .into_iter()
+        .filter_map(|discharge| match discharge {
+          Discharge::Range(start, end) => Some((start, end)),
+          Discharge::Line(_) => None,
+        })
+        .filter(move |&(start, end)| 1 <= start && start <= end && end < num)
+        .filter(|&(start, _)| matches!(rules.get(start - 1), Some(Some(rule)) if rule_set.is_assumption(rule)))
+        .map(move |(start, end)| Scope {
+          start,
+          end,
+          closed_by: num,
+        })
+    })
+    .collect();
+  scopes.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+  scopes.dedup_by(|a, b| a.start == b.start && a.end == b.end);
+  scopes
+}
+
+/// Column for drawing each scope as a vertical bar: overlapping scopes never share a column, so
+/// properly nested scopes get their nesting depth.
+pub fn columns(scopes: &[Scope]) -> Vec<usize> {
+  let mut columns: Vec<usize> = Vec::with_capacity(scopes.len());
+  for (idx, scope) in scopes.iter().enumerate() {
+    let taken: BTreeSet<usize> = scopes[..idx]
+      .iter()
+      .zip(columns.iter())
+      .filter(|(other, _)| other.start <= scope.end && scope.start <= other.end)
+      .map(|(_, &column)| column)
+      .collect();
+    columns.push((0..).find(|column| !taken.contains(column)).unwrap_or_default());
+  }
+  columns
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ast::rule::Rule, rule_set::Gentzen};
+
+  fn rules(rules: &[&Rule]) -> Vec<Option<Rule>> {
+    rules.iter().map(|&rule| Some(rule.clone())).collect()
+  }
+
+  #[test]
+  fn scopes_nested() {
+    // 1 (P ∨ Q)  P
+    // 2 ¬P       P
+    // 3 P        P
+    // 4 ⊥        2, 3 →E
+    // 5 Q        4 ⊥
+    // 6 Q        P
+    // 7 Q        1, 3-5, 6-6 ∨E
+    // 8 (¬P → Q) 2-7 →I
+    let rules = rules(&[
+      &Rule::Premise,
+      &Rule::Premise,
+      &Rule::Premise,
+      &Rule::IfExclude(2, 3),
+      &Rule::Falsum(4),
+      &Rule::Premise,
+      &Rule::OrExclude(1, (3, 5), (6, 6)),
+      &Rule::IfIntro((Some(2), 7)),
+    ]);
+    let scopes = scopes(&Gentzen::default(), &rules);
+    assert_eq!(
+      scopes,
+      vec![
+        Scope {
+          start: 2,
+          end: 7,
+          closed_by: 8
+        },
+        Scope {
+          start: 3,
+          end: 5,
+          closed_by: 7
+        },
+        Scope {
+          start: 6,
+          end: 6,
+          closed_by: 7
+        },
+      ]
+    );
+    assert_eq!(columns(&scopes), vec![0, 1, 1]);
+  }
+
+  #[test]
+  fn scopes_ignore_non_premise() {
+    let rules = rules(&[&Rule::Premise, &Rule::AndExclude(1), &Rule::IfIntro((Some(2), 2))]);
+    assert_eq!(scopes(&Gentzen::default(), &rules), vec![]);
+  }
+}
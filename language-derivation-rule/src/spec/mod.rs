@@ -0,0 +1,498 @@
+//! Inference rules defined as data.
+//!
+//! A spec file has one definition per line; blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! P: assumption
+//! &I: φ @k, ψ @l ⊢ (φ & ψ)
+//! →I: [φ]@k … ψ @l ⊢ (φ → ψ) discharging k
+//! ```
+//!
+//! A premise is a sentence cited by line (`φ @k`), an assumption cited by line (`[φ] @k`) or a
+//! subproof cited by range (`[φ]@k … ψ @l`). Rules are applied by writing the citations in the order
+//! of the premises followed by the name, e.g. `1, 2 &I` or `3-5 →I`. Several definitions may share a
+//! name; an application is valid if any of them matches. Sentences are propositional patterns over
+//! metavariables `α`–`ω`.
+//!
+//! Quantifier rules cannot be written in a spec: patterns have no quantifiers or individual symbols,
+//! and there is no way to say that an individual is new. A rule set read from a spec is propositional.
+
+mod parser;
+pub mod pattern;
+
+use std::{collections::HashSet, error, fmt, str::FromStr};
+
+use itertools::Itertools;
+use language::ast::exp::Exp;
+use nom::IResult;
+
+use self::{
+  parser::{application, definition, Definition},
+  pattern::{Bindings, Pattern},
+};
+use crate::{
+  dependency::RowDependency,
+  rule_set::{exp_at, is_assumption_at, Discharge, Line, RuleSet},
+};
+
+/// Rules of the bundled spec mirroring the propositional part of `rule_set::Gentzen`.
+pub const GENTZEN: &str = include_str!("../../rules/gentzen.rules");
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Premise {
+  /// `φ @k`, or `[φ] @k` when the cited line must be an assumption.
+  Line {
+    pattern: Pattern,
+    label: String,
+    is_assumption: bool,
+  },
+  /// `[φ]@k … ψ @l`
+  Subproof {
+    assumption: Pattern,
+    start: String,
+    conclusion: Pattern,
+    end: String,
+  },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RuleSpec {
+  pub name: String,
+  pub premises: Vec<Premise>,
+  pub conclusion: Pattern,
+  /// Labels of the assumptions the rule discharges.
+  pub discharging: Vec<String>,
+}
+
+impl fmt::Display for Premise {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Premise::Line {
+        pattern,
+        label,
+        is_assumption: false,
+      } => write!(f, "{} @{}", pattern, label),
+      Premise::Line {
+        pattern,
+        label,
+        is_assumption: true,
+      } => write!(f, "[{}]@{}", pattern, label),
+      Premise::Subproof {
+        assumption,
+        start,
+        conclusion,
+        end,
+      } => write!(f, "[{}]@{} … {} @{}", assumption, start, conclusion, end),
+    }
+  }
+}
+
+impl fmt::Display for RuleSpec {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: ", self.name)?;
+    if !self.premises.is_empty() {
+      write!(f, "{} ", self.premises.iter().join(", "))?;
+    }
+    write!(f, "⊢ {}", self.conclusion)?;
+    if !self.discharging.is_empty() {
+      write!(f, " discharging {}", self.discharging.join(", "))?;
+    }
+    Ok(())
+  }
+}
+
+impl RuleSpec {
+  fn shape(&self) -> impl Iterator<Item = bool> + '_ {
+    self
+      .premises
+      .iter()
+      .map(|premise| matches!(premise, Premise::Subproof { .. }))
+  }
+
+  fn labels(&self) -> impl Iterator<Item = (&String, bool)> {
+    self.premises.iter().flat_map(|premise| match premise {
+      Premise::Line {
+        label, is_assumption, ..
+      } => vec![(label, *is_assumption)],
+      Premise::Subproof { start, end, .. } => vec![(start, true), (end, false)],
+    })
+  }
+
+  /// Whether `exp` follows from the cited lines by this rule. `rule` has the shape of this spec.
+  fn is_valid<R: RuleSet>(&self, rule_set: &R, exp: &Exp, rule: &SpecRule, lines: &[Line<R::Rule>]) -> bool {
+    let mut bindings = Bindings::new();
+    let premises_match = self
+      .premises
+      .iter()
+      .zip(rule.citations.iter())
+      .all(|(premise, citation)| match (premise, *citation) {
+        (
+          Premise::Line {
+            pattern, is_assumption, ..
+          },
+          Citation::Line(num),
+        ) => {
+          (!is_assumption || is_assumption_at(rule_set, lines, num))
+            && exp_at(lines, num).is_some_and(|exp| pattern.matches(exp, &mut bindings))
+        }
+        (
+          Premise::Subproof {
+            assumption, conclusion, ..
+          },
+          Citation::Range(start, end),
+        ) => match (exp_at(lines, start), exp_at(lines, end)) {
+          (Some(exp_start), Some(exp_end)) => {
+            is_assumption_at(rule_set, lines, start)
+              && assumption.matches(exp_start, &mut bindings)
+              && conclusion.matches(exp_end, &mut bindings)
+          }
+          _ => false,
+        },
+        _ => false,
+      });
+    premises_match && self.conclusion.matches(exp, &mut bindings)
+  }
+}
+
+/// A line or range cited by a rule application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Citation {
+  Line(usize),
+  Range(usize, usize),
+}
+
+/// A rule application, e.g. `1, 3-5 ∨E`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecRule {
+  pub name: String,
+  pub citations: Vec<Citation>,
+}
+
+impl fmt::Display for SpecRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (idx, citation) in self.citations.iter().enumerate() {
+      let sep = if idx + 1 == self.citations.len() { " " } else { ", " };
+      match citation {
+        Citation::Line(num) => write!(f, "{}{}", num, sep)?,
+        Citation::Range(start, end) => write!(f, "{}-{}{}", start, end, sep)?,
+      }
+    }
+    write!(f, "{}", self.name)
+  }
+}
+
+/// Why a spec file could not be read. Line numbers start from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecError {
+  Syntax {
+    line: usize,
+  },
+  DuplicateLabel {
+    line: usize,
+    label: String,
+  },
+  /// A discharged label is not a premise of the rule.
+  UnknownLabel {
+    line: usize,
+    label: String,
+  },
+  /// A discharged label is not an assumption (`[φ]@k`).
+  NotAnAssumption {
+    line: usize,
+    label: String,
+  },
+  /// No `NAME: assumption` line.
+  MissingAssumption,
+}
+
+impl fmt::Display for SpecError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SpecError::Syntax { line } => write!(f, "line {}: syntax error", line),
+      SpecError::DuplicateLabel { line, label } => write!(f, "line {}: label {} is used twice", line, label),
+      SpecError::UnknownLabel { line, label } => write!(f, "line {}: label {} is not a premise", line, label),
+      SpecError::NotAnAssumption { line, label } => {
+        write!(
+          f,
+          "line {}: label {} is discharged but is not an assumption",
+          line, label
+        )
+      }
+      SpecError::MissingAssumption => write!(f, "no assumption rule is defined"),
+    }
+  }
+}
+
+impl error::Error for SpecError {}
+
+/// A rule set read from a spec file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpecSet {
+  pub assumption: String,
+  pub rules: Vec<RuleSpec>,
+}
+
+impl FromStr for SpecSet {
+  type Err = SpecError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut assumption = None;
+    let mut rules = vec![];
+    for (idx, text) in s.lines().enumerate() {
+      let line = idx + 1;
+      let text = text.trim();
+      if text.is_empty() || text.starts_with('#') {
+        continue;
+      }
+      match definition(text) {
+        Ok((_, Definition::Assumption(name))) => assumption = Some(name),
+        Ok((_, Definition::Rule(rule))) => {
+          validate(line, &rule)?;
+          rules.push(rule);
+        }
+        Err(_) => return Err(SpecError::Syntax { line }),
+      }
+    }
+    match assumption {
+      Some(assumption) => Ok(SpecSet { assumption, rules }),
+      None => Err(SpecError::MissingAssumption),
+    }
+  }
+}
+
+/// The spec written back, one definition per line.
+impl fmt::Display for SpecSet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}: assumption", self.assumption)?;
+    for rule in self.rules.iter() {
+      writeln!(f, "{}", rule)?;
+    }
+    Ok(())
+  }
+}
+
+fn validate(line: usize, rule: &RuleSpec) -> Result<(), SpecError> {
+  let mut labels = HashSet::new();
+  for (label, _) in rule.labels() {
+    if !labels.insert(label) {
+      return Err(SpecError::DuplicateLabel {
+        line,
+        label: label.clone(),
+      });
+    }
+  }
+  for label in rule.discharging.iter() {
+    match rule.labels().find(|(other, _)| *other == label) {
+      None => {
+        return Err(SpecError::UnknownLabel {
+          line,
+          label: label.clone(),
+        })
+      }
+      Some((_, false)) => {
+        return Err(SpecError::NotAnAssumption {
+          line,
+          label: label.clone(),
+        })
+      }
+      Some((_, true)) => {}
+    }
+  }
+  Ok(())
+}
+
+impl SpecSet {
+  fn names(&self) -> impl Iterator<Item = &str> {
+    [self.assumption.as_str()]
+      .into_iter()
+      .chain(self.rules.iter().map(|rule| rule.name.as_str()))
+      .unique()
+  }
+
+  fn is_assumption_name(&self, name: &str) -> bool {
+    self.assumption == name
+  }
+
+  /// Definitions `rule` may be an application of: same name, and a range cited exactly for each
+  /// subproof premise.
+  fn matching<'a>(&'a self, rule: &'a SpecRule) -> impl Iterator<Item = &'a RuleSpec> {
+    self.rules.iter().filter(move |spec| {
+      spec.name == rule.name
+        && spec.shape().eq(
+          rule
+            .citations
+            .iter()
+            .map(|citation| matches!(citation, Citation::Range(..))),
+        )
+    })
+  }
+}
+
+impl RuleSet for SpecSet {
+  type Rule = SpecRule;
+
+  fn parse<'a>(&self, s: &'a str) -> IResult<&'a str, SpecRule> {
+    application(self, s)
+  }
+
+  fn assumption(&self) -> SpecRule {
+    SpecRule {
+      name: self.assumption.clone(),
+      citations: vec![],
+    }
+  }
+
+  fn cited_lines(&self, rule: &SpecRule) -> Vec<usize> {
+    rule
+      .citations
+      .iter()
+      .flat_map(|citation| match *citation {
+        Citation::Line(num) => vec![num],
+        Citation::Range(start, end) => vec![start, end],
+      })
+      .collect()
+  }
+
+  /// The application with each line and each end of a range renumbered.
+  fn renumbered(&self, rule: &SpecRule, renumber: &dyn Fn(usize) -> usize) -> SpecRule {
+    SpecRule {
+      name: rule.name.clone(),
+      citations: rule
+        .citations
+        .iter()
+        .map(|citation| match *citation {
+          Citation::Line(num) => Citation::Line(renumber(num)),
+          Citation::Range(start, end) => Citation::Range(renumber(start), renumber(end)),
+        })
+        .collect(),
+    }
+  }
+
+  /// Discharges of the first matching definition; definitions sharing a name and shape should agree.
+  fn discharges(&self, rule: &SpecRule) -> Vec<Discharge> {
+    let spec = match self.matching(rule).next() {
+      Some(spec) => spec,
+      None => return vec![],
+    };
+    spec
+      .premises
+      .iter()
+      .zip(rule.citations.iter())
+      .filter_map(|(premise, citation)| match (premise, *citation) {
+        (Premise::Line { label, .. }, Citation::Line(num)) if spec.discharging.contains(label) => {
+          Some(Discharge::Line(num))
+        }
+        (Premise::Subproof { start: label, .. }, Citation::Range(start, end)) if spec.discharging.contains(label) => {
+          Some(Discharge::Range(start, end))
+        }
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn dependency(&self, num: usize, rule: &SpecRule, acc: &[RowDependency]) -> RowDependency {
+    if self.is_assumption(rule) {
+      return RowDependency::init_from([num]);
+    }
+    let get = |num: usize| acc.get(num - 1);
+    let deps = rule
+      .citations
+      .iter()
+      .fold(RowDependency::new(), |deps, citation| match *citation {
+        Citation::Line(num) | Citation::Range(_, num) => deps | get(num),
+      });
+    self
+      .discharges(rule)
+      .iter()
+      .fold(deps, |deps, discharge| deps - discharge.assumption())
+  }
+
+  fn is_valid(
+    &self,
+    _num: usize,
+    exp: &Exp,
+    rule: &SpecRule,
+    lines: &[Line<SpecRule>],
+    _deps_list: &[RowDependency],
+  ) -> bool {
+    self.is_assumption(rule) || self.matching(rule).any(|spec| spec.is_valid(self, exp, rule, lines))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{check::check_rows, fixtures::rows, rule_set::Gentzen};
+  use rstest::rstest;
+
+  #[test]
+  fn spec_set_gentzen() {
+    let specs: SpecSet = GENTZEN.parse().unwrap();
+    assert_eq!(specs.assumption, "P");
+    for rule in specs.rules.iter() {
+      assert_eq!(definition(&rule.to_string()), Ok(("", Definition::Rule(rule.clone()))));
+    }
+    assert_eq!(specs.to_string().parse(), Ok(specs));
+  }
+
+  #[test]
+  fn spec_set_invalid() {
+    assert_eq!(
+      "P: assumption\n\n&I: φ @k, ψ @k ⊢ (φ & ψ)".parse::<SpecSet>(),
+      Err(SpecError::DuplicateLabel {
+        line: 3,
+        label: "k".to_owned()
+      })
+    );
+    assert_eq!(
+      "P: assumption\n→I: φ @k … ψ @l ⊢ (φ → ψ) discharging k".parse::<SpecSet>(),
+      Err(SpecError::Syntax { line: 2 })
+    );
+    assert_eq!(
+      "P: assumption\n→I: φ @k, ψ @l ⊢ (φ → ψ) discharging k".parse::<SpecSet>(),
+      Err(SpecError::NotAnAssumption {
+        line: 2,
+        label: "k".to_owned()
+      })
+    );
+    assert_eq!(
+      "&I: φ @k, ψ @l ⊢ (φ & ψ)".parse::<SpecSet>(),
+      Err(SpecError::MissingAssumption)
+    );
+  }
+
+  #[rstest]
+  #[case(&[
+    ("(P ∨ Q)", "P"),
+    ("P", "P"),
+    ("(Q ∨ P)", "2 ∨I"),
+    ("Q", "P"),
+    ("(Q ∨ P)", "4 ∨I"),
+    ("(Q ∨ P)", "1, 2-3, 4-5 ∨E"),
+    ("((P ∨ Q) → (Q ∨ P))", "1-6 →I"),
+  ])]
+  #[case(&[
+    ("(P → Q)", "P"),
+    ("¬Q", "P"),
+    ("P", "P"),
+    ("Q", "1, 3 →E"),
+    ("⊥", "2, 4 →E"),
+    ("¬P", "3-5 ¬I"),
+    ("(¬Q → ¬P)", "2-6 →I"),
+  ])]
+  #[case(&[
+    ("P", "P"),
+    ("(P & P)", "1, 1 &I"),
+    ("Q", "2 &E"),
+    ("(P ↔ Q)", "1-1 →I"),
+  ])]
+  fn spec_set_agrees_with_gentzen(#[case] derivation: &[(&str, &str)]) {
+    let specs: SpecSet = GENTZEN.parse().unwrap();
+    let rows = rows(derivation);
+    let expected = check_rows(&Gentzen::default(), &rows);
+    let actual = check_rows(&specs, &rows);
+    assert_eq!(actual.validity, expected.validity);
+    assert_eq!(actual.deps_list, expected.deps_list);
+    assert_eq!(actual.scopes, expected.scopes);
+  }
+}
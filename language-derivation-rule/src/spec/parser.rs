@@ -0,0 +1,251 @@
+use nom::{
+  branch::alt,
+  bytes::complete::{tag, take_till1},
+  character::complete::{alphanumeric1, char, digit1, multispace0, multispace1, satisfy},
+  combinator::{all_consuming, map, map_res, opt, value, verify},
+  error::{Error, ErrorKind},
+  multi::separated_list1,
+  sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+  Err, IResult,
+};
+
+use language::parser::{
+  symbol::{and, falsum, left_right_arrow, negation, or, right_arrow, turnstile},
+  util::ws,
+};
+
+use super::{pattern::Pattern, Citation, Premise, RuleSpec, SpecRule, SpecSet};
+
+/// A line of a spec file.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Definition {
+  Assumption(String),
+  Rule(RuleSpec),
+}
+
+fn meta(s: &str) -> IResult<&str, Pattern> {
+  map(satisfy(|c| ('α'..='ω').contains(&c)), Pattern::Meta)(s)
+}
+
+fn binary<'a>(
+  op: fn(&'a str) -> IResult<&'a str, &'a str>,
+  lhs: fn(&'a str) -> IResult<&'a str, Pattern>,
+  rhs: fn(&'a str) -> IResult<&'a str, Pattern>,
+  node: fn(Box<Pattern>, Box<Pattern>) -> Pattern,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Pattern> {
+  map(tuple((lhs, ws(op), rhs)), move |(lhs, _, rhs)| {
+    node(Box::new(lhs), Box::new(rhs))
+  })
+}
+
+// Same precedence as `language::parser::expression`, so a pattern reads like the sentences it matches.
+pub(super) fn pattern(s: &str) -> IResult<&str, Pattern> {
+  alt((
+    binary(right_arrow, bool_pattern, pattern, |antecedent, consequent| {
+      Pattern::Cond { antecedent, consequent }
+    }),
+    binary(left_right_arrow, bool_pattern, pattern, |lhs, rhs| Pattern::Iff {
+      lhs,
+      rhs,
+    }),
+    bool_pattern,
+  ))(s)
+}
+
+fn bool_pattern(s: &str) -> IResult<&str, Pattern> {
+  alt((
+    binary(and, f, bool_pattern, |lhs, rhs| Pattern::And { lhs, rhs }),
+    binary(or, f, bool_pattern, |lhs, rhs| Pattern::Or { lhs, rhs }),
+    f,
+  ))(s)
+}
+
+fn f(s: &str) -> IResult<&str, Pattern> {
+  alt((
+    meta,
+    value(Pattern::Falsum, falsum),
+    map(preceded(negation, preceded(multispace0, f)), |pattern| {
+      Pattern::Neg(Box::new(pattern))
+    }),
+    delimited(tag("("), ws(pattern), tag(")")),
+  ))(s)
+}
+
+fn label(s: &str) -> IResult<&str, String> {
+  map(preceded(ws(char('@')), alphanumeric1), str::to_owned)(s)
+}
+
+fn assumption_pattern(s: &str) -> IResult<&str, Pattern> {
+  delimited(char('['), ws(pattern), char(']'))(s)
+}
+
+fn ellipsis(s: &str) -> IResult<&str, &str> {
+  alt((tag("…"), tag("...")))(s)
+}
+
+fn premise(s: &str) -> IResult<&str, Premise> {
+  alt((
+    map(
+      tuple((assumption_pattern, label, ws(ellipsis), pattern, label)),
+      |(assumption, start, _, conclusion, end)| Premise::Subproof {
+        assumption,
+        start,
+        conclusion,
+        end,
+      },
+    ),
+    map(pair(assumption_pattern, label), |(pattern, label)| Premise::Line {
+      pattern,
+      label,
+      is_assumption: true,
+    }),
+    map(pair(pattern, label), |(pattern, label)| Premise::Line {
+      pattern,
+      label,
+      is_assumption: false,
+    }),
+  ))(s)
+}
+
+fn name(s: &str) -> IResult<&str, String> {
+  map(take_till1(|c: char| c == ':' || c.is_whitespace()), str::to_owned)(s)
+}
+
+fn rule_spec(s: &str) -> IResult<&str, RuleSpec> {
+  map(
+    tuple((
+      terminated(name, ws(char(':'))),
+      opt(separated_list1(ws(char(',')), premise)),
+      preceded(ws(turnstile), pattern),
+      opt(preceded(
+        pair(multispace1, tag("discharging")),
+        preceded(
+          multispace1,
+          separated_list1(ws(char(',')), map(alphanumeric1, str::to_owned)),
+        ),
+      )),
+    )),
+    |(name, premises, conclusion, discharging)| RuleSpec {
+      name,
+      premises: premises.unwrap_or_default(),
+      conclusion,
+      discharging: discharging.unwrap_or_default(),
+    },
+  )(s)
+}
+
+pub(super) fn definition(s: &str) -> IResult<&str, Definition> {
+  all_consuming(alt((
+    map(
+      terminated(name, pair(ws(char(':')), tag("assumption"))),
+      Definition::Assumption,
+    ),
+    map(rule_spec, Definition::Rule),
+  )))(s.trim())
+}
+
+fn num(s: &str) -> IResult<&str, usize> {
+  map_res(digit1, |s: &str| s.parse::<usize>())(s)
+}
+
+fn citation(s: &str) -> IResult<&str, Citation> {
+  alt((
+    map(separated_pair(num, ws(tag("-")), num), |(start, end)| {
+      Citation::Range(start, end)
+    }),
+    map(num, Citation::Line),
+  ))(s)
+}
+
+/// A rule application: the citations in the order of the premises, then the rule name.
+pub(super) fn application<'a>(specs: &SpecSet, s: &'a str) -> IResult<&'a str, SpecRule> {
+  let mut names: Vec<&str> = specs.names().collect();
+  names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+  let rule_name = |s: &'a str| match names.iter().find(|name| s.starts_with(**name)) {
+    Some(name) => Ok((&s[name.len()..], name.to_string())),
+    None => Err(Err::Error(Error::new(s, ErrorKind::Tag))),
+  };
+
+  let result = verify(
+    alt((
+      map(
+        pair(
+          separated_list1(ws(char(',')), citation),
+          preceded(multispace1, rule_name),
+        ),
+        |(citations, name)| SpecRule { name, citations },
+      ),
+      map(rule_name, |name| SpecRule {
+        name,
+        citations: vec![],
+      }),
+    )),
+    |rule: &SpecRule| specs.is_assumption_name(&rule.name) || specs.matching(rule).next().is_some(),
+  )(s);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn definition_valid() {
+    assert_eq!(
+      definition("→I: [φ]@k … ψ @l ⊢ (φ → ψ) discharging k"),
+      Ok((
+        "",
+        Definition::Rule(RuleSpec {
+          name: "→I".to_owned(),
+          premises: vec![Premise::Subproof {
+            assumption: Pattern::Meta('φ'),
+            start: "k".to_owned(),
+            conclusion: Pattern::Meta('ψ'),
+            end: "l".to_owned(),
+          }],
+          conclusion: Pattern::Cond {
+            antecedent: Box::new(Pattern::Meta('φ')),
+            consequent: Box::new(Pattern::Meta('ψ')),
+          },
+          discharging: vec!["k".to_owned()],
+        })
+      ))
+    );
+    assert_eq!(
+      definition("&I: φ @k, ψ @l |- φ & ψ"),
+      Ok((
+        "",
+        Definition::Rule(RuleSpec {
+          name: "&I".to_owned(),
+          premises: vec![
+            Premise::Line {
+              pattern: Pattern::Meta('φ'),
+              label: "k".to_owned(),
+              is_assumption: false,
+            },
+            Premise::Line {
+              pattern: Pattern::Meta('ψ'),
+              label: "l".to_owned(),
+              is_assumption: false,
+            },
+          ],
+          conclusion: Pattern::And {
+            lhs: Box::new(Pattern::Meta('φ')),
+            rhs: Box::new(Pattern::Meta('ψ')),
+          },
+          discharging: vec![],
+        })
+      ))
+    );
+    assert_eq!(
+      definition("P: assumption"),
+      Ok(("", Definition::Assumption("P".to_owned())))
+    );
+  }
+
+  #[test]
+  fn definition_invalid() {
+    assert!(definition("&I: φ @k, ψ ⊢ (φ & ψ)").is_err());
+    assert!(definition("&I: P @k ⊢ P").is_err());
+  }
+}
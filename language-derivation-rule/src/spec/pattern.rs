@@ -0,0 +1,104 @@
+use std::{collections::HashMap, fmt};
+
+use language::ast::exp::Exp;
+
+/// A propositional sentence with metavariables (`φ`, `ψ`, …) standing for arbitrary sentences.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+  Meta(char),
+  Falsum,
+  Neg(Box<Pattern>),
+  And {
+    lhs: Box<Pattern>,
+    rhs: Box<Pattern>,
+  },
+  Or {
+    lhs: Box<Pattern>,
+    rhs: Box<Pattern>,
+  },
+  Cond {
+    antecedent: Box<Pattern>,
+    consequent: Box<Pattern>,
+  },
+  Iff {
+    lhs: Box<Pattern>,
+    rhs: Box<Pattern>,
+  },
+}
+
+/// What each metavariable stands for.
+pub type Bindings = HashMap<char, Exp>;
+
+impl fmt::Display for Pattern {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Pattern::Meta(meta) => write!(f, "{}", meta),
+      Pattern::Falsum => write!(f, "⊥"),
+      Pattern::Neg(pattern) => write!(f, "¬{}", pattern),
+      Pattern::And { lhs, rhs } => write!(f, "({} & {})", lhs, rhs),
+      Pattern::Or { lhs, rhs } => write!(f, "({} ∨ {})", lhs, rhs),
+      Pattern::Cond { antecedent, consequent } => write!(f, "({} → {})", antecedent, consequent),
+      Pattern::Iff { lhs, rhs } => write!(f, "({} ↔ {})", lhs, rhs),
+    }
+  }
+}
+
+impl Pattern {
+  /// Match `exp`, extending `bindings`. A metavariable already bound must stand for the same sentence.
+  pub fn matches(&self, exp: &Exp, bindings: &mut Bindings) -> bool {
+    match (self, exp) {
+      (Pattern::Meta(meta), exp) => match bindings.get(meta) {
+        Some(bound) => bound == exp,
+        None => {
+          bindings.insert(*meta, exp.clone());
+          true
+        }
+      },
+      (Pattern::Falsum, Exp::Falsum) => true,
+      (Pattern::Neg(pattern), Exp::Neg(exp)) => pattern.matches(exp, bindings),
+      (Pattern::And { lhs: p1, rhs: p2 }, Exp::And { lhs: e1, rhs: e2 })
+      | (Pattern::Or { lhs: p1, rhs: p2 }, Exp::Or { lhs: e1, rhs: e2 })
+      | (
+        Pattern::Cond {
+          antecedent: p1,
+          consequent: p2,
+        },
+        Exp::Cond {
+          antecedent: e1,
+          consequent: e2,
+        },
+      )
+      | (Pattern::Iff { lhs: p1, rhs: p2 }, Exp::Iff { lhs: e1, rhs: e2 }) => {
+        p1.matches(e1, bindings) && p2.matches(e2, bindings)
+      }
+      _ => false,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::spec::parser::pattern;
+  use language::parser::expression::exp;
+
+  #[test]
+  fn matches_valid() {
+    let (_, pattern) = pattern("((φ → ψ) & ¬ψ)").unwrap();
+    let (_, exp) = exp("((P → Q & R) & ¬(Q & R))").unwrap();
+    let mut bindings = Bindings::new();
+    assert!(pattern.matches(&exp, &mut bindings));
+    assert_eq!(bindings.get(&'φ').map(|exp| exp.to_string()), Some("P".to_owned()));
+    assert_eq!(
+      bindings.get(&'ψ').map(|exp| exp.to_string()),
+      Some("(Q & R)".to_owned())
+    );
+  }
+
+  #[test]
+  fn matches_inconsistent() {
+    let (_, pattern) = pattern("(φ & φ)").unwrap();
+    let (_, exp) = exp("(P & Q)").unwrap();
+    assert!(!pattern.matches(&exp, &mut Bindings::new()));
+  }
+}
@@ -0,0 +1,295 @@
+//! A library of proven theorems and sequents, cited by `TI (name)` and `1, 2 SI (name)`.
+//!
+//! Each entry is a header `NAME: sequent` followed by its proof, one indented line per row with the
+//! rule in square brackets. Blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! T1: ⊢ (P → P)
+//!   P [P]
+//!   (P → P) [1-1 →I]
+//! ```
+//!
+//! Proofs are checked in order, so each may introduce the entries before it. Each theorem records the
+//! weakest logic its proof is valid in, so it can be cited only under profiles allowing that logic.
+
+use std::{error, fmt, str::FromStr};
+
+use language::{
+  ast::{exp::Exp, sequent::Sequent},
+  parser::{expression::exp, sequent::sequent},
+};
+
+use crate::{
+  derived::{DeriveError, DerivedRule},
+  rule_set::{Gentzen, Profile},
+};
+
+/// The bundled library of basic theorems.
+pub const BASIC: &str = include_str!("../theorems/basic.thm");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theorem {
+  pub name: String,
+  pub sequent: Sequent,
+  /// Sentence and rule of each line, as written.
+  pub proof: Vec<(String, String)>,
+  /// The weakest logic the proof is valid in.
+  pub profile: Profile,
+}
+
+/// Why a theorem library could not be loaded. Line numbers start from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LibraryError {
+  Syntax {
+    line: usize,
+  },
+  DuplicateName {
+    line: usize,
+    name: String,
+  },
+  InvalidProof {
+    line: usize,
+    name: String,
+    error: DeriveError,
+  },
+  /// The proof is valid but proves something else.
+  ProofMismatch {
+    line: usize,
+    name: String,
+    proven: Sequent,
+  },
+}
+
+impl fmt::Display for LibraryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LibraryError::Syntax { line } => write!(f, "line {}: syntax error", line),
+      LibraryError::DuplicateName { line, name } => write!(f, "line {}: {} is defined twice", line, name),
+      LibraryError::InvalidProof { line, name, error } => write!(f, "line {}: proof of {}: {}", line, name, error),
+      LibraryError::ProofMismatch { line, name, proven } => {
+        write!(f, "line {}: the proof of {} proves {} instead", line, name, proven)
+      }
+    }
+  }
+}
+
+impl error::Error for LibraryError {}
+
+/// Theorems in the order they were proven.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Library {
+  theorems: Vec<Theorem>,
+}
+
+impl Library {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn get(&self, name: &str) -> Option<&Theorem> {
+    self.theorems.iter().find(|theorem| theorem.name == name)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Theorem> {
+    self.theorems.iter()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.theorems.is_empty()
+  }
+
+  /// Check the proof of `theorem` against the theorems so far and add it. `line` is where it is defined,
+  /// for errors.
+  fn push(&mut self, line: usize, mut theorem: Theorem) -> Result<(), LibraryError> {
+    let name = theorem.name.clone();
+    if self.get(&name).is_some() {
+      return Err(LibraryError::DuplicateName { line, name });
+    }
+    let rows: Vec<(Option<Exp>, &str)> = theorem
+      .proof
+      .iter()
+      .map(|(sentence, rule)| {
+        let exp = match exp(sentence) {
+          Ok(("", exp)) => Some(exp),
+          _ => None,
+        };
+        (exp, rule.as_str())
+      })
+      .collect();
+    let gentzen = Gentzen {
+      theorems: self.clone(),
+      ..Gentzen::default()
+    };
+    let (proven, profile) = match DerivedRule::from_derivation(&name, &rows, &gentzen) {
+      Ok(proven) => (proven.sequent, proven.profile),
+      Err(error) => return Err(LibraryError::InvalidProof { line, name, error }),
+    };
+    if proven.conclusion != theorem.sequent.conclusion
+      || !proven
+        .premises
+        .iter()
+        .all(|premise| theorem.sequent.premises.contains(premise))
+    {
+      return Err(LibraryError::ProofMismatch { line, name, proven });
+    }
+    theorem.profile = profile;
+    self.theorems.push(theorem);
+    Ok(())
+  }
+}
+
+impl FromStr for Library {
+  type Err = LibraryError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut library = Library::new();
+    // the entry being read and the line of its header
+    let mut current: Option<(usize, Theorem)> = None;
+
+    for (idx, text) in s.lines().enumerate() {
+      let line = idx + 1;
+      if text.trim().is_empty() || text.trim_start().starts_with('#') {
+        continue;
+      }
+
+      if text.starts_with(char::is_whitespace) {
+        let (_, theorem) = current.as_mut().ok_or(LibraryError::Syntax { line })?;
+        let text = text.trim();
+        let (sentence, rule) = text
+          .strip_suffix(']')
+          .and_then(|text| text.split_once('['))
+          .ok_or(LibraryError::Syntax { line })?;
+        theorem.proof.push((sentence.trim().to_owned(), rule.trim().to_owned()));
+        continue;
+      }
+
+      if let Some((line, theorem)) = current.take() {
+        library.push(line, theorem)?;
+      }
+      let (name, text) = text.split_once(':').ok_or(LibraryError::Syntax { line })?;
+      let name = name.trim();
+      let sequent = match sequent(text.trim()) {
+        Ok(("", sequent)) => sequent,
+        _ => return Err(LibraryError::Syntax { line }),
+      };
+      if name.is_empty() || !name.chars().all(char::is_alphanumeric) {
+        return Err(LibraryError::Syntax { line });
+      }
+      current = Some((
+        line,
+        Theorem {
+          name: name.to_owned(),
+          sequent,
+          proof: vec![],
+          profile: Profile::default(),
+        },
+      ));
+    }
+
+    if let Some((line, theorem)) = current.take() {
+      library.push(line, theorem)?;
+    }
+    Ok(library)
+  }
+}
+
+impl fmt::Display for Library {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (idx, theorem) in self.theorems.iter().enumerate() {
+      if idx > 0 {
+        writeln!(f)?;
+      }
+      writeln!(f, "{}: {}", theorem.name, theorem.sequent)?;
+      for (sentence, rule) in theorem.proof.iter() {
+        writeln!(f, "  {} [{}]", sentence, rule)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{check::check_rows, fixtures::rows};
+
+  #[test]
+  fn library_basic() {
+    let library: Library = BASIC.parse().unwrap();
+    assert_eq!(
+      library.iter().map(|theorem| theorem.name.as_str()).collect::<Vec<_>>(),
+      vec!["T1", "T2", "T3", "T4", "T5"]
+    );
+    assert_eq!(
+      library.iter().map(|theorem| theorem.profile).collect::<Vec<_>>(),
+      vec![
+        Profile::Minimal,
+        Profile::Classical,
+        Profile::Minimal,
+        Profile::Classical,
+        Profile::Intuitionistic
+      ]
+    );
+    assert_eq!(library.to_string().parse::<Library>(), Ok(library));
+  }
+
+  #[test]
+  fn library_invalid() {
+    assert_eq!("  P [P]".parse::<Library>(), Err(LibraryError::Syntax { line: 1 }));
+    assert_eq!(
+      "T1: ⊢ (P → P)\n  P [P]\n  (P → P) [1-1 →I]\n\nT1: P ⊢ P\n  P [P]".parse::<Library>(),
+      Err(LibraryError::DuplicateName {
+        line: 5,
+        name: "T1".to_owned()
+      })
+    );
+    assert_eq!(
+      "T1: P ⊢ Q\n  P [P]\n  Q [1 &E]".parse::<Library>(),
+      Err(LibraryError::InvalidProof {
+        line: 1,
+        name: "T1".to_owned(),
+        error: DeriveError::InvalidLine { num: 2 }
+      })
+    );
+    assert!(matches!(
+      "T1: ⊢ P\n  P [P]".parse::<Library>(),
+      Err(LibraryError::ProofMismatch { line: 1, .. })
+    ));
+  }
+
+  #[test]
+  fn sequent_intro() {
+    let gentzen = Gentzen {
+      theorems: BASIC.parse().unwrap(),
+      ..Gentzen::default()
+    };
+    let report = check_rows(
+      &gentzen,
+      &rows(&[
+        ("((R & S) ∨ ¬(R & S))", "TI (T4)"),
+        ("(A ∨ B)", "P"),
+        ("¬A", "P"),
+        ("B", "2, 3 SI (T5)"),
+        ("A", "3, 2 SI (T5)"),
+        ("(Q → Q)", "TI (T9)"),
+      ]),
+    );
+    assert_eq!(report.validity, vec![true, true, true, true, false, false]);
+    assert_eq!(report.deps_list[3].nums, [2, 3].into());
+  }
+
+  #[test]
+  fn sequent_intro_profile() {
+    let derivation = rows(&[("(A ∨ ¬A)", "TI (T4)"), ("¬B", "P"), ("(B → B)", "TI (T1)")]);
+    let validity = |profile| {
+      let gentzen = Gentzen {
+        theorems: BASIC.parse().unwrap(),
+        profile,
+        ..Gentzen::default()
+      };
+      check_rows(&gentzen, &derivation).validity
+    };
+    assert_eq!(validity(Profile::Classical), vec![true, true, true]);
+    assert_eq!(validity(Profile::Intuitionistic), vec![false, true, true]);
+  }
+}
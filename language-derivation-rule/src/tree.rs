@@ -0,0 +1,565 @@
+//! Derivations as trees of inferences, as Gentzen wrote them. Each assumption carries a label; the
+//! inference discharging it names the same label on the premise whose subproof it closes.
+
+use std::{collections::BTreeMap, error, fmt};
+
+use language::{ast::exp::Exp, latex};
+
+use crate::{
+  ast::rule::Rule,
+  check::check,
+  rule_set::{Gentzen, Line, RuleSet},
+};
+
+/// The most assumptions and inferences a tree is built with. Lines cited more than once are copied,
+/// so a short derivation can have a tree exponentially larger than itself.
+pub const MAX_SIZE: usize = 2000;
+
+/// Why a derivation has no proof tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeError {
+  Empty,
+  InvalidLine {
+    num: usize,
+  },
+  IncompleteDependency {
+    num: usize,
+  },
+  /// The tree would have more than `MAX_SIZE` nodes.
+  TooLarge,
+}
+
+impl fmt::Display for TreeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TreeError::Empty => write!(f, "the derivation is empty"),
+      TreeError::InvalidLine { num } => write!(f, "line {} is not valid", num),
+      TreeError::IncompleteDependency { num } => write!(f, "the dependency of line {} is incomplete", num),
+      TreeError::TooLarge => write!(f, "the proof tree would have more than {} nodes", MAX_SIZE),
+    }
+  }
+}
+
+impl error::Error for TreeError {}
+
+/// An inference rule without its citations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+  AndIntro,
+  AndExclude,
+  OrIntro,
+  OrExclude,
+  IfIntro,
+  IfExclude,
+  IffIntro,
+  IffExclude,
+  Falsum,
+  NegIntro,
+  NegExclude,
+  UnivQuntIntro,
+  UnivQuntExclude,
+  ExisQuntIntro,
+  ExisQuntExclude,
+  Derived(String),
+  SequentIntro(String),
+}
+
+impl Step {
+  /// The rule name for `bussproofs` labels, symbols in math mode.
+  pub fn latex(&self) -> String {
+    match self {
+      Step::AndIntro => "$\\&$I".to_owned(),
+      Step::AndExclude => "$\\&$E".to_owned(),
+      Step::OrIntro => "$\\lor$I".to_owned(),
+      Step::OrExclude => "$\\lor$E".to_owned(),
+      Step::IfIntro => "$\\to$I".to_owned(),
+      Step::IfExclude => "$\\to$E".to_owned(),
+      Step::IffIntro => "$\\leftrightarrow$I".to_owned(),
+      Step::IffExclude => "$\\leftrightarrow$E".to_owned(),
+      Step::Falsum => "$\\bot$".to_owned(),
+      Step::NegIntro => "$\\neg$I".to_owned(),
+      Step::NegExclude => "$\\neg$E".to_owned(),
+      Step::UnivQuntIntro => "()I".to_owned(),
+      Step::UnivQuntExclude => "()E".to_owned(),
+      Step::ExisQuntIntro => "$\\exists$I".to_owned(),
+      Step::ExisQuntExclude => "$\\exists$E".to_owned(),
+      Step::Derived(name) => name.clone(),
+      Step::SequentIntro(name) => format!("SI ({})", name),
+    }
+  }
+}
+
+impl fmt::Display for Step {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Step::AndIntro => write!(f, "&I"),
+      Step::AndExclude => write!(f, "&E"),
+      Step::OrIntro => write!(f, "∨I"),
+      Step::OrExclude => write!(f, "∨E"),
+      Step::IfIntro => write!(f, "→I"),
+      Step::IfExclude => write!(f, "→E"),
+      Step::IffIntro => write!(f, "↔I"),
+      Step::IffExclude => write!(f, "↔E"),
+      Step::Falsum => write!(f, "⊥"),
+      Step::NegIntro => write!(f, "¬I"),
+      Step::NegExclude => write!(f, "¬E"),
+      Step::UnivQuntIntro => write!(f, "()I"),
+      Step::UnivQuntExclude => write!(f, "()E"),
+      Step::ExisQuntIntro => write!(f, "∃I"),
+      Step::ExisQuntExclude => write!(f, "∃E"),
+      Step::Derived(name) => write!(f, "{}", name),
+      Step::SequentIntro(name) => write!(f, "SI ({})", name),
+    }
+  }
+}
+
+/// An assumption discharged on a premise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hypothesis {
+  pub label: usize,
+  pub exp: Exp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Premise {
+  /// The assumption the inference discharges in this premise's subproof.
+  pub hypothesis: Option<Hypothesis>,
+  pub tree: Tree,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tree {
+  Assumption {
+    label: usize,
+    exp: Exp,
+  },
+  Inference {
+    exp: Exp,
+    step: Step,
+    premises: Vec<Premise>,
+  },
+}
+
+impl Tree {
+  /// The tree of the last line of a valid derivation. Assumptions are labelled with their line
+  /// numbers, and lines cited more than once are copied, as long as the tree stays within `MAX_SIZE`.
+  pub fn from_derivation(lines: &[Line<Rule>], gentzen: &Gentzen) -> Result<Tree, TreeError> {
+    let report = check(gentzen, lines);
+    if let Some(idx) = report.validity.iter().position(|&is_valid| !is_valid) {
+      return Err(TreeError::InvalidLine { num: idx + 1 });
+    }
+    if let Some(idx) = report.deps_list.iter().position(|deps| !deps.is_complete) {
+      return Err(TreeError::IncompleteDependency { num: idx + 1 });
+    }
+    if lines.is_empty() {
+      return Err(TreeError::Empty);
+    }
+    if sizes(lines, gentzen).last().is_some_and(|&size| size > MAX_SIZE) {
+      return Err(TreeError::TooLarge);
+    }
+    Ok(build(lines, lines.len()))
+  }
+
+  pub fn exp(&self) -> &Exp {
+    match self {
+      Tree::Assumption { exp, .. } | Tree::Inference { exp, .. } => exp,
+    }
+  }
+
+  /// Undischarged assumptions by label.
+  pub fn open_assumptions(&self) -> BTreeMap<usize, Exp> {
+    match self {
+      Tree::Assumption { label, exp } => BTreeMap::from([(*label, exp.clone())]),
+      Tree::Inference { premises, .. } => premises
+        .iter()
+        .flat_map(|premise| {
+          let mut open = premise.tree.open_assumptions();
+          if let Some(hypothesis) = &premise.hypothesis {
+            open.remove(&hypothesis.label);
+          }
+          open
+        })
+        .collect(),
+    }
+  }
+
+  /// The greatest label in the tree, discharged or not.
+  pub fn max_label(&self) -> usize {
+    match self {
+      Tree::Assumption { label, .. } => *label,
+      Tree::Inference { premises, .. } => premises
+        .iter()
+        .map(|premise| {
+          let label = premise.hypothesis.as_ref().map_or(0, |hypothesis| hypothesis.label);
+          label.max(premise.tree.max_label())
+        })
+        .max()
+        .unwrap_or(0),
+    }
+  }
+
+  /// The same tree with `f` applied to every sentence.
+  pub fn map_exps(&self, f: &dyn Fn(&Exp) -> Exp) -> Tree {
+    match self {
+      Tree::Assumption { label, exp } => Tree::Assumption {
+        label: *label,
+        exp: f(exp),
+      },
+      Tree::Inference { exp, step, premises } => Tree::Inference {
+        exp: f(exp),
+        step: step.clone(),
+        premises: premises
+          .iter()
+          .map(|premise| Premise {
+            hypothesis: premise.hypothesis.as_ref().map(|hypothesis| Hypothesis {
+              label: hypothesis.label,
+              exp: f(&hypothesis.exp),
+            }),
+            tree: premise.tree.map_exps(f),
+          })
+          .collect(),
+      },
+    }
+  }
+
+  /// The labels this inference discharges, in the order of its premises.
+  pub fn discharged_labels(&self) -> Vec<usize> {
+    match self {
+      Tree::Assumption { .. } => vec![],
+      Tree::Inference { premises, .. } => premises
+        .iter()
+        .filter_map(|premise| premise.hypothesis.as_ref().map(|hypothesis| hypothesis.label))
+        .collect(),
+    }
+  }
+
+  /// The tree as a `prooftree` environment of the LaTeX `bussproofs` package. Discharged
+  /// assumptions are bracketed, and they and the inference discharging them carry the label as a
+  /// superscript. `None` if an inference has more than the five premises `bussproofs` can draw.
+  pub fn bussproofs(&self) -> Option<String> {
+    let mut out = String::from("\\begin{prooftree}\n");
+    write_bussproofs(self, &mut vec![], &mut out)?;
+    out.push_str("\\end{prooftree}\n");
+    Some(out)
+  }
+
+  /// The derivation of this tree: open assumptions first, then each subproof right after the
+  /// assumption it discharges.
+  pub fn lines(&self) -> Vec<Line<Rule>> {
+    let mut linearizer = Linearizer::default();
+    for (label, exp) in self.open_assumptions() {
+      let num = linearizer.push(exp, Rule::Premise);
+      linearizer.scope.push((label, num));
+    }
+    linearizer.emit(self);
+    linearizer.lines
+  }
+}
+
+fn write_bussproofs(tree: &Tree, bound: &mut Vec<usize>, out: &mut String) -> Option<()> {
+  let (exp, step, premises) = match tree {
+    Tree::Assumption { label, exp } if bound.contains(label) => {
+      out.push_str(&format!("\\AxiomC{{$[{}]^{{{}}}$}}\n", latex::exp(exp), label));
+      return Some(());
+    }
+    Tree::Assumption { exp, .. } => {
+      out.push_str(&format!("\\AxiomC{{${}$}}\n", latex::exp(exp)));
+      return Some(());
+    }
+    Tree::Inference { exp, step, premises } => (exp, step, premises),
+  };
+
+  for premise in premises {
+    let label = premise.hypothesis.as_ref().map(|hypothesis| hypothesis.label);
+    bound.extend(label);
+    let written = write_bussproofs(&premise.tree, bound, out);
+    if label.is_some() {
+      bound.pop();
+    }
+    written?;
+  }
+  if premises.is_empty() {
+    out.push_str("\\AxiomC{}\n");
+  }
+
+  let labels = tree.discharged_labels();
+  match labels.is_empty() {
+    true => out.push_str(&format!("\\RightLabel{{\\scriptsize {}}}\n", step.latex())),
+    false => out.push_str(&format!(
+      "\\RightLabel{{\\scriptsize {}$^{{{}}}$}}\n",
+      step.latex(),
+      labels
+        .iter()
+        .map(|label| label.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+    )),
+  }
+  let command = match premises.len() {
+    0 | 1 => "UnaryInfC",
+    2 => "BinaryInfC",
+    3 => "TrinaryInfC",
+    4 => "QuaternaryInfC",
+    5 => "QuinaryInfC",
+    _ => return None,
+  };
+  out.push_str(&format!("\\{}{{${}$}}\n", command, latex::exp(exp)));
+  Some(())
+}
+
+/// An upper bound on the size of the tree of each line, counting the assumption of a subproof along
+/// with the subproof.
+fn sizes(lines: &[Line<Rule>], gentzen: &Gentzen) -> Vec<usize> {
+  let mut sizes: Vec<usize> = vec![];
+  for line in lines {
+    let cited = match &line.rule {
+      Some(rule) => gentzen.cited_lines(rule),
+      None => vec![],
+    };
+    let size = cited
+      .iter()
+      .filter_map(|&k| sizes.get(k.checked_sub(1)?))
+      .fold(1, |size: usize, &cited| size.saturating_add(cited));
+    sizes.push(size);
+  }
+  sizes
+}
+
+fn build(lines: &[Line<Rule>], num: usize) -> Tree {
+  let line = &lines[num - 1];
+  // the derivation is valid, so every line has a sentence and a rule
+  let exp = line.exp.clone().expect("valid line has a sentence");
+  let rule = line.rule.as_ref().expect("valid line has a rule");
+  let cited = |k: usize| Premise {
+    hypothesis: None,
+    tree: build(lines, k),
+  };
+  let discharging = |(k0, k1): (usize, usize)| Premise {
+    hypothesis: Some(Hypothesis {
+      label: k0,
+      exp: lines[k0 - 1].exp.clone().expect("valid line has a sentence"),
+    }),
+    tree: build(lines, k1),
+  };
+  let (step, premises) = match *rule {
+    Rule::Premise => return Tree::Assumption { label: num, exp },
+    Rule::AndIntro(k, l) => (Step::AndIntro, vec![cited(k), cited(l)]),
+    Rule::AndExclude(k) => (Step::AndExclude, vec![cited(k)]),
+    Rule::OrIntro(k, None) => (Step::OrIntro, vec![cited(k)]),
+    Rule::OrIntro(k, Some(l)) => (Step::OrIntro, vec![cited(k), cited(l)]),
+    Rule::OrExclude(k, l, m) => (Step::OrExclude, vec![cited(k), discharging(l), discharging(m)]),
+    Rule::IfIntro((Some(k0), k1)) => (Step::IfIntro, vec![discharging((k0, k1))]),
+    Rule::IfIntro((None, k)) => (Step::IfIntro, vec![cited(k)]),
+    Rule::IfExclude(k, l) => (Step::IfExclude, vec![cited(k), cited(l)]),
+    Rule::IffIntro(k, l) => (Step::IffIntro, vec![cited(k), cited(l)]),
+    Rule::IffExclude(k) => (Step::IffExclude, vec![cited(k)]),
+    Rule::Falsum(k) => (Step::Falsum, vec![cited(k)]),
+    Rule::NegIntro(range) => (Step::NegIntro, vec![discharging(range)]),
+    Rule::NegExclude(range) => (Step::NegExclude, vec![discharging(range)]),
+    Rule::UnivQuntIntro(k) => (Step::UnivQuntIntro, vec![cited(k)]),
+    Rule::UnivQuntExclude(k) => (Step::UnivQuntExclude, vec![cited(k)]),
+    Rule::ExisQuntIntro(k) => (Step::ExisQuntIntro, vec![cited(k)]),
+    Rule::ExisQuntExclude(k, l) => (Step::ExisQuntExclude, vec![cited(k), discharging(l)]),
+    Rule::Derived(ref name, ref deps) => (Step::Derived(name.clone()), deps.iter().map(|&k| cited(k)).collect()),
+    Rule::SequentIntro(ref deps, ref name) => (
+      Step::SequentIntro(name.clone()),
+      deps.iter().map(|&k| cited(k)).collect(),
+    ),
+  };
+  Tree::Inference { exp, step, premises }
+}
+
+#[derive(Default)]
+struct Linearizer {
+  lines: Vec<Line<Rule>>,
+  /// Lines of the assumptions in scope by label, innermost last.
+  scope: Vec<(usize, usize)>,
+}
+
+impl Linearizer {
+  fn push(&mut self, exp: Exp, rule: Rule) -> usize {
+    self.lines.push(Line {
+      exp: Some(exp),
+      rule: Some(rule),
+    });
+    self.lines.len()
+  }
+
+  /// Write out `tree` and return the line of its conclusion.
+  fn emit(&mut self, tree: &Tree) -> usize {
+    let (exp, step, premises) = match tree {
+      Tree::Assumption { label, .. } => {
+        return match self.scope.iter().rev().find(|(scoped, _)| scoped == label) {
+          Some(&(_, num)) => num,
+          // every open assumption is in scope from the start
+          None => unreachable!("assumption {} is out of scope", label),
+        };
+      }
+      Tree::Inference { exp, step, premises } => (exp, step, premises),
+    };
+
+    // a →I whose subproof never uses its assumption is written without one
+    let hypotheses: Vec<Option<&Hypothesis>> = premises
+      .iter()
+      .map(|premise| {
+        premise.hypothesis.as_ref().filter(|hypothesis| {
+          *step != Step::IfIntro || premise.tree.open_assumptions().contains_key(&hypothesis.label)
+        })
+      })
+      .collect();
+    // the first and last line of each premise's subproof
+    let ranges: Vec<(usize, usize)> = premises
+      .iter()
+      .zip(&hypotheses)
+      .map(|(premise, hypothesis)| match hypothesis {
+        Some(hypothesis) => {
+          let start = self.push(hypothesis.exp.clone(), Rule::Premise);
+          self.scope.push((hypothesis.label, start));
+          let end = self.emit(&premise.tree);
+          self.scope.pop();
+          (start, end)
+        }
+        None => {
+          let end = self.emit(&premise.tree);
+          (end, end)
+        }
+      })
+      .collect();
+    let end = |idx: usize| ranges[idx].1;
+    let discharged = |idx: usize| hypotheses[idx].is_some();
+
+    let rule = match step {
+      Step::AndIntro => Rule::AndIntro(end(0), end(1)),
+      Step::AndExclude => Rule::AndExclude(end(0)),
+      Step::OrIntro => Rule::OrIntro(end(0), (ranges.len() > 1).then(|| end(1))),
+      Step::OrExclude => Rule::OrExclude(end(0), ranges[1], ranges[2]),
+      Step::IfIntro if discharged(0) => Rule::IfIntro((Some(ranges[0].0), end(0))),
+      Step::IfIntro => Rule::IfIntro((None, end(0))),
+      Step::IfExclude => Rule::IfExclude(end(0), end(1)),
+      Step::IffIntro => Rule::IffIntro(end(0), end(1)),
+      Step::IffExclude => Rule::IffExclude(end(0)),
+      Step::Falsum => Rule::Falsum(end(0)),
+      Step::NegIntro => Rule::NegIntro(ranges[0]),
+      Step::NegExclude => Rule::NegExclude(ranges[0]),
+      Step::UnivQuntIntro => Rule::UnivQuntIntro(end(0)),
+      Step::UnivQuntExclude => Rule::UnivQuntExclude(end(0)),
+      Step::ExisQuntIntro => Rule::ExisQuntIntro(end(0)),
+      Step::ExisQuntExclude => Rule::ExisQuntExclude(end(0), ranges[1]),
+      Step::Derived(name) => Rule::Derived(name.clone(), ranges.iter().map(|range| range.1).collect()),
+      Step::SequentIntro(name) => Rule::SequentIntro(ranges.iter().map(|range| range.1).collect(), name.clone()),
+    };
+    self.push(exp.clone(), rule)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fixtures::lines;
+
+  fn rules(lines: &[Line<Rule>]) -> Vec<String> {
+    lines
+      .iter()
+      .map(|line| line.rule.as_ref().unwrap().to_string())
+      .collect()
+  }
+
+  #[test]
+  fn tree_round_trip() {
+    let original = lines(
+      &Gentzen::default(),
+      &[
+        ("(P → Q)", "P"),
+        ("¬Q", "P"),
+        ("P", "P"),
+        ("Q", "1, 3 →E"),
+        ("⊥", "2, 4 →E"),
+        ("¬P", "3-5 ¬I"),
+      ],
+    );
+    let tree = Tree::from_derivation(&original, &Gentzen::default()).unwrap();
+    assert_eq!(tree.open_assumptions().into_keys().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(tree.lines(), original);
+  }
+
+  #[test]
+  fn tree_copies_shared_lines() {
+    let original = lines(
+      &Gentzen::default(),
+      &[
+        ("P", "P"),
+        ("(P & P)", "1, 1 &I"),
+        ("Q", "P"),
+        ("((P & P) & Q)", "2, 3 &I"),
+        ("(((P & P) & Q) → ((P & P) & Q))", "4 →I"),
+      ],
+    );
+    let tree = Tree::from_derivation(&original, &Gentzen::default()).unwrap();
+    let linear = tree.lines();
+    assert_eq!(rules(&linear), ["P", "P", "1, 1 &I", "3, 2 &I", "4 →I"]);
+    assert!(check(&Gentzen::default(), &linear)
+      .validity
+      .iter()
+      .all(|&is_valid| is_valid));
+  }
+
+  #[test]
+  fn tree_too_large() {
+    // each &I cites the line before twice, doubling the tree every two lines
+    let mut rows = vec![("P", "P".to_owned())];
+    for num in (1..24).step_by(2) {
+      rows.push(("(P & P)", format!("{}, {} &I", num, num)));
+      rows.push(("P", format!("{} &E", num + 1)));
+    }
+    let rows: Vec<(&str, &str)> = rows.iter().map(|(sentence, rule)| (*sentence, rule.as_str())).collect();
+    let original = lines(&Gentzen::default(), &rows);
+    assert_eq!(
+      Tree::from_derivation(&original, &Gentzen::default()),
+      Err(TreeError::TooLarge)
+    );
+    assert!(Tree::from_derivation(&original[..19], &Gentzen::default()).is_ok());
+  }
+
+  #[test]
+  fn tree_bussproofs() {
+    let original = lines(
+      &Gentzen::default(),
+      &[
+        ("(P → Q)", "P"),
+        ("¬Q", "P"),
+        ("P", "P"),
+        ("Q", "1, 3 →E"),
+        ("⊥", "2, 4 →E"),
+        ("¬P", "3-5 ¬I"),
+      ],
+    );
+    let tree = Tree::from_derivation(&original, &Gentzen::default()).unwrap();
+    assert_eq!(
+      tree.bussproofs().unwrap(),
+      [
+        "\\begin{prooftree}",
+        "\\AxiomC{$\\neg Q$}",
+        "\\AxiomC{$(P \\to Q)$}",
+        "\\AxiomC{$[P]^{3}$}",
+        "\\RightLabel{\\scriptsize $\\to$E}",
+        "\\BinaryInfC{$Q$}",
+        "\\RightLabel{\\scriptsize $\\to$E}",
+        "\\BinaryInfC{$\\bot$}",
+        "\\RightLabel{\\scriptsize $\\neg$I$^{3}$}",
+        "\\UnaryInfC{$\\neg P$}",
+        "\\end{prooftree}",
+        "",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn tree_invalid() {
+    let original = lines(&Gentzen::default(), &[("P", "P"), ("Q", "1 &E")]);
+    assert_eq!(
+      Tree::from_derivation(&original, &Gentzen::default()),
+      Err(TreeError::InvalidLine { num: 2 })
+    );
+  }
+}
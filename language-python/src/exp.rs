@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use language::{ast::exp::Exp, latex, parser::parse_sentence};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// A sentence of ℒ. `Exp("P -> Q")` parses it; `str()` prints it with the representative symbols.
+#[pyclass(name = "Exp", module = "elementary_logic", frozen, eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyExp(pub Exp);
+
+/// A sentence given to a Python function, as text or already parsed.
+#[derive(FromPyObject)]
+pub enum Sentence {
+  Exp(PyExp),
+  Text(String),
+}
+
+impl Sentence {
+  pub fn parse(self) -> PyResult<Exp> {
+    match self {
+      Sentence::Exp(PyExp(exp)) => Ok(exp),
+      Sentence::Text(s) => parse(&s),
+    }
+  }
+}
+
+pub fn parse(s: &str) -> PyResult<Exp> {
+  parse_sentence(s).ok_or_else(|| PyValueError::new_err(format!("invalid sentence: {}", s)))
+}
+
+#[pymethods]
+impl PyExp {
+  #[new]
+  fn new(s: &str) -> PyResult<Self> {
+    parse(s).map(PyExp)
+  }
+
+  fn __str__(&self) -> String {
+    self.0.to_string()
+  }
+
+  fn __repr__(&self) -> String {
+    format!("Exp('{}')", self.0)
+  }
+
+  /// The main connective: `atom`, `conditional`, `biconditional`, `conjunction`, `disjunction`,
+  /// `negation`, `universal`, `existential` or `falsum`.
+  #[getter]
+  fn kind(&self) -> &'static str {
+    match self.0 {
+      Exp::Atom { .. } => "atom",
+      Exp::Cond { .. } => "conditional",
+      Exp::Iff { .. } => "biconditional",
+      Exp::And { .. } => "conjunction",
+      Exp::Or { .. } => "disjunction",
+      Exp::Neg(_) => "negation",
+      Exp::UnivGenr { .. } => "universal",
+      Exp::ExistGenr { .. } => "existential",
+      Exp::Falsum => "falsum",
+    }
+  }
+
+  /// The immediate subsentences, from the left.
+  #[getter]
+  fn children(&self) -> Vec<PyExp> {
+    match &self.0 {
+      Exp::Cond {
+        antecedent: lhs,
+        consequent: rhs,
+      }
+      | Exp::Iff { lhs, rhs }
+      | Exp::And { lhs, rhs }
+      | Exp::Or { lhs, rhs } => vec![PyExp(*lhs.clone()), PyExp(*rhs.clone())],
+      Exp::Neg(lhs) | Exp::UnivGenr { form: lhs, .. } | Exp::ExistGenr { form: lhs, .. } => {
+        vec![PyExp(*lhs.clone())]
+      }
+      Exp::Atom { .. } | Exp::Falsum => vec![],
+    }
+  }
+
+  /// The variables (`u` to `z`) that occur free; constants are left out.
+  fn free_variables(&self) -> BTreeSet<String> {
+    self.0.free_individual_variables()
+  }
+
+  /// The sentence with each free occurrence of `variable` replaced by `individual`. Raises `ValueError`
+  /// when a quantifier would bind `individual`.
+  fn substitute(&self, variable: &str, individual: &str) -> PyResult<Self> {
+    if !self.0.is_free_for(variable, individual) {
+      return Err(PyValueError::new_err(format!(
+        "{} would be bound in {}",
+        individual, self.0
+      )));
+    }
+    Ok(PyExp(self.0.var_replaced(variable, individual)))
+  }
+
+  fn latex(&self) -> String {
+    latex::exp(&self.0)
+  }
+}
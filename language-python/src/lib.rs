@@ -0,0 +1,219 @@
+//! The `elementary_logic` Python module, for grading and research scripts. Build it with
+//! `maturin develop` in this directory.
+//!
+//! Results are plain dicts, lists and bools. Invalid input raises `ValueError`.
+
+// `#[pyfunction]` expands to conversions of `PyErr` into itself
+#![allow(clippy::useless_conversion)]
+
+pub mod exp;
+
+use language::{
+  ast::sequent::Sequent,
+  semantics::{self, truth_table as table, Valuation},
+};
+use language_derivation_rule::{
+  document::{DocumentRow, ProofDocument},
+  rule_set::{NameError, Profile, System},
+};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+use self::exp::{PyExp, Sentence};
+
+/// `check([("P", "P"), ("(P ∨ Q)", "1 ∨I")], system="Gentzen", profile="classical", sequent="P ⊢ (P ∨ Q)")`
+///
+/// A dict with `rows`, a dict for each row with `line`, `valid`, `dependencies`, `complete` and
+/// `errors`, and `proved`, which is `None` when no sequent is given.
+#[pyfunction]
+#[pyo3(signature = (rows, system = None, profile = None, sequent = None))]
+fn check(
+  py: Python<'_>,
+  rows: Vec<(String, String)>,
+  system: Option<&str>,
+  profile: Option<&str>,
+  sequent: Option<String>,
+) -> PyResult<PyObject> {
+  let system = match system {
+    Some(name) => name
+      .parse()
+      .map_err(|err: NameError| PyValueError::new_err(err.to_string()))?,
+    None => System::default(),
+  };
+  let profile = match profile {
+    Some(name) => name
+      .parse()
+      .map_err(|err: NameError| PyValueError::new_err(err.to_string()))?,
+    None => Profile::default(),
+  };
+  let rows = rows
+    .into_iter()
+    .map(|(sentence, derivation)| DocumentRow {
+      sentence,
+      derivation,
+      comment: None,
+    })
+    .collect();
+  let document = ProofDocument {
+    sequent,
+    ..ProofDocument::new(system, rows)
+  };
+  let report = document
+    .check(profile)
+    .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+  let rows = report
+    .rows
+    .into_iter()
+    .map(|row| {
+      let dict = PyDict::new_bound(py);
+      dict.set_item("line", row.line)?;
+      dict.set_item("valid", row.valid)?;
+      dict.set_item("dependencies", row.dependencies)?;
+      dict.set_item("complete", row.complete)?;
+      dict.set_item("errors", row.errors)?;
+      Ok(dict)
+    })
+    .collect::<PyResult<Vec<_>>>()?;
+  let dict = PyDict::new_bound(py);
+  dict.set_item("rows", rows)?;
+  dict.set_item("proved", report.proved)?;
+  Ok(dict.into())
+}
+
+/// `truth_table(["(P → Q)", "¬P"])`
+///
+/// A dict with the sorted `letters` and `rows`, from all letters true to all false. Each row is a
+/// dict with the `valuation` of the letters and the `values` of the sentences.
+#[pyfunction]
+fn truth_table(py: Python<'_>, sentences: Vec<Sentence>) -> PyResult<PyObject> {
+  let exps = sentences
+    .into_iter()
+    .map(Sentence::parse)
+    .collect::<PyResult<Vec<_>>>()?;
+  let table = table(&exps).map_err(|err| PyValueError::new_err(err.to_string()))?;
+  let rows = table
+    .rows
+    .into_iter()
+    .map(|row| {
+      let valuation = PyDict::new_bound(py);
+      for (letter, value) in table.letters.iter().zip(row.valuation) {
+        valuation.set_item(letter, value)?;
+      }
+      let dict = PyDict::new_bound(py);
+      dict.set_item("valuation", valuation)?;
+      dict.set_item("values", row.values)?;
+      Ok(dict)
+    })
+    .collect::<PyResult<Vec<_>>>()?;
+  let dict = PyDict::new_bound(py);
+  dict.set_item("letters", table.letters)?;
+  dict.set_item("rows", rows)?;
+  Ok(dict.into())
+}
+
+/// `counterexample(["(P → Q)", "Q"], "P")`: a dict of letters to values making the premises true and
+/// the conclusion false, or `None` when the premises entail the conclusion.
+#[pyfunction]
+fn counterexample(premises: Vec<Sentence>, conclusion: Sentence) -> PyResult<Option<Valuation>> {
+  let sequent = Sequent {
+    premises: premises.into_iter().map(Sentence::parse).collect::<PyResult<_>>()?,
+    conclusion: conclusion.parse()?,
+  };
+  semantics::counterexample(&sequent).map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// `entails(["(P → Q)", "P"], "Q")`: whether the premises entail the conclusion truth-functionally.
+#[pyfunction]
+fn entails(premises: Vec<Sentence>, conclusion: Sentence) -> PyResult<bool> {
+  Ok(counterexample(premises, conclusion)?.is_none())
+}
+
+#[pymodule]
+fn elementary_logic(module: &Bound<'_, PyModule>) -> PyResult<()> {
+  module.add_class::<PyExp>()?;
+  module.add_function(wrap_pyfunction!(check, module)?)?;
+  module.add_function(wrap_pyfunction!(truth_table, module)?)?;
+  module.add_function(wrap_pyfunction!(counterexample, module)?)?;
+  module.add_function(wrap_pyfunction!(entails, module)?)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Run Python `code` with the module imported as `el`.
+  fn run(code: &str) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+      let module = PyModule::new_bound(py, "elementary_logic").unwrap();
+      elementary_logic(&module).unwrap();
+      let locals = PyDict::new_bound(py);
+      locals.set_item("el", module).unwrap();
+      if let Err(err) = py.run_bound(code, None, Some(&locals)) {
+        err.print(py);
+        panic!("python failed: {}", err);
+      }
+    });
+  }
+
+  #[test]
+  fn python_exp() {
+    run(
+      r#"
+exp = el.Exp("(x)(Fx -> Gx)")
+assert str(exp) == "(x)(Fx → Gx)"
+assert repr(exp) == "Exp('(x)(Fx → Gx)')"
+assert exp.kind == "universal"
+assert [child.kind for child in exp.children] == ["conditional"]
+assert exp.children[0].free_variables() == {"x"}
+assert str(exp.children[0].substitute("x", "a")) == "(Fa → Ga)"
+assert el.Exp("(Fa → Gx)").free_variables() == {"x"}
+try:
+    el.Exp("(x)(Fx → Gy)").substitute("y", "x")
+    assert False
+except ValueError as err:
+    assert "would be bound" in str(err)
+assert exp == el.Exp("(x)(Fx → Gx)")
+try:
+    el.Exp("(P ->")
+    assert False
+except ValueError as err:
+    assert "invalid sentence" in str(err)
+"#,
+    );
+  }
+
+  #[test]
+  fn python_check() {
+    run(
+      r#"
+report = el.check([("(P → Q)", "P"), ("P", "P"), ("Q", "1, 2 →E")], sequent="(P → Q), P ⊢ Q")
+assert report["proved"] is True
+assert report["rows"][2] == {"line": 3, "valid": True, "dependencies": [1, 2], "complete": True, "errors": []}
+
+report = el.check([("P", "P"), ("Q", "1 &E")], system="Gentzen", profile="minimal")
+assert report["proved"] is None
+assert report["rows"][1]["errors"] == ["the rule does not derive this sentence"]
+try:
+    el.check([], profile="modal")
+    assert False
+except ValueError:
+    pass
+"#,
+    );
+  }
+
+  #[test]
+  fn python_semantics() {
+    run(
+      r#"
+table = el.truth_table(["(P ∨ Q)", el.Exp("¬P")])
+assert table["letters"] == ["P", "Q"]
+assert table["rows"][3] == {"valuation": {"P": False, "Q": False}, "values": [False, True]}
+assert el.entails(["(P → Q)", "P"], "Q")
+assert el.counterexample(["(P → Q)", "Q"], "P") == {"P": False, "Q": True}
+"#,
+    );
+  }
+}
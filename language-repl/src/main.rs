@@ -0,0 +1,35 @@
+//! A REPL for sentences of ℒ: enter a sentence to see how it parses, or a command to work on it.
+
+mod session;
+
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+use self::session::Session;
+
+fn main() -> rustyline::Result<()> {
+  let mut editor = DefaultEditor::new()?;
+  let mut session = Session::default();
+  println!("Enter a sentence, or :help for the commands.");
+  loop {
+    let line = match editor.readline("> ") {
+      Ok(line) => line,
+      // Ctrl-C drops the line, as in a shell
+      Err(ReadlineError::Interrupted) => continue,
+      Err(ReadlineError::Eof) => break,
+      Err(err) => return Err(err),
+    };
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    editor.add_history_entry(line)?;
+    if matches!(line, ":quit" | ":q") {
+      break;
+    }
+    match session.eval(line) {
+      Ok(output) => println!("{}", output),
+      Err(err) => eprintln!("{}", err),
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,333 @@
+//! What the REPL does with each line, apart from reading it.
+
+use std::fmt;
+
+use language::{
+  ast::{exp::Exp, sequent::Sequent},
+  normal_form::{cnf, nnf},
+  parser::{
+    expression::exp,
+    individual_constant::{ind_sym, var},
+    util::ws,
+  },
+  semantics::{self, truth_table, SemanticsError},
+};
+use nom::combinator::all_consuming;
+
+pub const HELP: &str = "\
+φ              parse φ and make it the current formula
+:tt [φ]        truth table
+:models [φ]    valuations making the formula true
+:nnf [φ]       negation normal form
+:cnf [φ]       conjunctive normal form
+:equiv φ ; ψ   whether φ and ψ are truth-functionally equivalent
+:subst x a     replace the free x in the current formula with a
+:help          this list
+:quit          leave
+Commands without φ work on the current formula, and their results become the current formula.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplError {
+  /// `input` does not read from `column`, counting chars from 1.
+  Syntax {
+    input: String,
+    column: usize,
+  },
+  NoFormula,
+  UnknownCommand(String),
+  Usage(&'static str),
+  /// `:subst` would put the individual symbol under a quantifier that binds it.
+  Captured(String),
+  Semantics(SemanticsError),
+}
+
+impl fmt::Display for ReplError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ReplError::Syntax { input, column } => {
+        writeln!(f, "  {}", input)?;
+        writeln!(f, "  {}^", " ".repeat(column - 1))?;
+        write!(f, "syntax error at column {}", column)
+      }
+      ReplError::NoFormula => write!(f, "no current formula; enter one first"),
+      ReplError::UnknownCommand(command) => write!(f, "unknown command {}; try :help", command),
+      ReplError::Usage(usage) => write!(f, "usage: {}", usage),
+      ReplError::Captured(individual) => write!(f, "{} would be bound by a quantifier of the formula", individual),
+      ReplError::Semantics(err) => write!(f, "{}", err),
+    }
+  }
+}
+
+impl From<SemanticsError> for ReplError {
+  fn from(err: SemanticsError) -> Self {
+    ReplError::Semantics(err)
+  }
+}
+
+/// The formula the commands work on when they are not given one.
+#[derive(Debug, Default)]
+pub struct Session {
+  pub current: Option<Exp>,
+}
+
+impl Session {
+  /// The output for `line`, a formula or a command.
+  pub fn eval(&mut self, line: &str) -> Result<String, ReplError> {
+    let line = line.trim();
+    let (command, argument) = match line.strip_prefix(':') {
+      Some(command) => {
+        let (command, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        (Some(command), argument.trim())
+      }
+      None => (None, line),
+    };
+    match command {
+      None => {
+        let exp = self.formula(argument)?;
+        Ok(describe(&exp))
+      }
+      Some("help" | "h") => Ok(HELP.to_owned()),
+      Some("tt") => {
+        let exp = self.formula(argument)?;
+        Ok(format_truth_table(&exp)?)
+      }
+      Some("models") => {
+        let exp = self.formula(argument)?;
+        let table = truth_table(std::slice::from_ref(&exp))?;
+        let models: Vec<String> = table
+          .rows
+          .iter()
+          .filter(|row| row.values[0])
+          .map(|row| {
+            let values: Vec<String> = table
+              .letters
+              .iter()
+              .zip(&row.valuation)
+              .map(|(letter, &value)| format!("{}={}", letter, truth_value(value)))
+              .collect();
+            values.join(" ")
+          })
+          .collect();
+        match models.len() {
+          0 => Ok("no models".to_owned()),
+          _ => Ok(models.join("\n")),
+        }
+      }
+      Some("nnf") => {
+        let exp = nnf(&self.formula(argument)?);
+        Ok(self.replace(exp))
+      }
+      Some("cnf") => {
+        let exp = cnf(&self.formula(argument)?);
+        Ok(self.replace(exp))
+      }
+      Some("equiv") => {
+        let (lhs, rhs) = argument.split_once(';').ok_or(ReplError::Usage(":equiv φ ; ψ"))?;
+        let (lhs, rhs) = (parse(lhs.trim())?, parse(rhs.trim())?);
+        let sequent = Sequent {
+          premises: vec![],
+          conclusion: Exp::Iff {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+          },
+        };
+        match semantics::counterexample(&sequent)? {
+          None => Ok("equivalent".to_owned()),
+          Some(valuation) => {
+            let values: Vec<String> = valuation
+              .iter()
+              .map(|(letter, &value)| format!("{}={}", letter, truth_value(value)))
+              .collect();
+            Ok(format!("not equivalent: they differ at {}", values.join(" ")))
+          }
+        }
+      }
+      Some("subst") => {
+        let usage = ReplError::Usage(":subst x a, replacing the variable x with the individual symbol a");
+        let (variable, individual) = match argument.split_whitespace().collect::<Vec<_>>()[..] {
+          [variable, individual] => (variable, individual),
+          _ => return Err(usage),
+        };
+        if all_consuming(var)(variable).is_err() || all_consuming(ind_sym)(individual).is_err() {
+          return Err(usage);
+        }
+        let exp = self.current.as_ref().ok_or(ReplError::NoFormula)?;
+        if !exp.is_free_for(variable, individual) {
+          return Err(ReplError::Captured(individual.to_owned()));
+        }
+        let exp = exp.var_replaced(variable, individual);
+        Ok(self.replace(exp))
+      }
+      Some(command) => Err(ReplError::UnknownCommand(format!(":{}", command))),
+    }
+  }
+
+  /// `argument` parsed, which becomes the current formula, or the current formula when it is empty.
+  fn formula(&mut self, argument: &str) -> Result<Exp, ReplError> {
+    if argument.is_empty() {
+      return self.current.clone().ok_or(ReplError::NoFormula);
+    }
+    let exp = parse(argument)?;
+    self.current = Some(exp.clone());
+    Ok(exp)
+  }
+
+  fn replace(&mut self, exp: Exp) -> String {
+    let output = exp.to_string();
+    self.current = Some(exp);
+    output
+  }
+}
+
+pub fn parse(input: &str) -> Result<Exp, ReplError> {
+  all_consuming(ws(exp))(input).map(|(_, exp)| exp).map_err(|err| {
+    let rest = match err {
+      nom::Err::Error(err) | nom::Err::Failure(err) => err.input,
+      nom::Err::Incomplete(_) => input,
+    };
+    ReplError::Syntax {
+      input: input.to_owned(),
+      column: input[..input.len() - rest.len()].chars().count() + 1,
+    }
+  })
+}
+
+/// The tree, the pretty-printed form and the free variables of `exp`.
+fn describe(exp: &Exp) -> String {
+  let mut tree = String::new();
+  write_tree(exp, "", "", &mut tree);
+  let free: Vec<String> = exp.free_individual_variables().into_iter().collect();
+  let free = match free.len() {
+    0 => "none".to_owned(),
+    _ => free.join(", "),
+  };
+  format!("{}{}\nfree variables: {}", tree, exp, free)
+}
+
+/// One line for `exp` and its subsentences below it, each under the connective it is an operand of.
+fn write_tree(exp: &Exp, first: &str, rest: &str, out: &mut String) {
+  let (label, children): (String, Vec<&Exp>) = match exp {
+    Exp::Atom { .. } | Exp::Falsum => (exp.to_string(), vec![]),
+    Exp::Cond { antecedent, consequent } => ("→".to_owned(), vec![antecedent, consequent]),
+    Exp::Iff { lhs, rhs } => ("↔".to_owned(), vec![lhs, rhs]),
+    Exp::And { lhs, rhs } => ("&".to_owned(), vec![lhs, rhs]),
+    Exp::Or { lhs, rhs } => ("∨".to_owned(), vec![lhs, rhs]),
+    Exp::Neg(lhs) => ("¬".to_owned(), vec![lhs]),
+    Exp::UnivGenr { variable, form } => (format!("({})", variable), vec![form]),
+    Exp::ExistGenr { variable, form } => (format!("(∃{})", variable), vec![form]),
+  };
+  out.push_str(&format!("{}{}\n", first, label));
+  for (idx, child) in children.iter().enumerate() {
+    if idx + 1 < children.len() {
+      write_tree(child, &format!("{}├─ ", rest), &format!("{}│  ", rest), out);
+    } else {
+      write_tree(child, &format!("{}└─ ", rest), &format!("{}   ", rest), out);
+    }
+  }
+}
+
+fn format_truth_table(exp: &Exp) -> Result<String, SemanticsError> {
+  let table = truth_table(std::slice::from_ref(exp))?;
+  let sentence = exp.to_string();
+  let pad = |value: bool, width: usize| format!("{:width$}", truth_value(value), width = width);
+  let mut lines = vec![format!("{} | {}", table.letters.join(" "), sentence)];
+  for row in &table.rows {
+    let values: Vec<String> = table
+      .letters
+      .iter()
+      .zip(&row.valuation)
+      .map(|(letter, &value)| pad(value, letter.chars().count()))
+      .collect();
+    lines.push(format!("{} | {}", values.join(" "), truth_value(row.values[0])));
+  }
+  Ok(lines.join("\n"))
+}
+
+fn truth_value(value: bool) -> &'static str {
+  if value {
+    "T"
+  } else {
+    "F"
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  #[test]
+  fn session_formula() {
+    let mut session = Session::default();
+    assert_eq!(
+      session.eval("(x)(Fx -> -Gxy)").unwrap(),
+      "\
+(x)
+└─ →
+   ├─ Fx
+   └─ ¬
+      └─ Gxy
+(x)(Fx → ¬Gxy)
+free variables: y"
+    );
+    assert_eq!(session.eval(":subst y a").unwrap(), "(x)(Fx → ¬Gxa)");
+    assert_eq!(
+      session.eval(":subst x"),
+      Err(ReplError::Usage(
+        ":subst x a, replacing the variable x with the individual symbol a"
+      ))
+    );
+  }
+
+  #[test]
+  fn session_subst_capture() {
+    let mut session = Session::default();
+    assert!(session.eval("(x)(Fx -> Gy)").unwrap().ends_with("free variables: y"));
+    assert_eq!(session.eval(":subst y x"), Err(ReplError::Captured("x".to_owned())));
+    assert_eq!(session.eval(":subst y a").unwrap(), "(x)(Fx → Ga)");
+    assert!(session.eval("Fa").unwrap().ends_with("free variables: none"));
+  }
+
+  #[rstest]
+  #[case("(P ->", 4)]
+  #[case("P & Q)", 6)]
+  #[case("  P Q", 5)]
+  fn session_syntax_error(#[case] input: &str, #[case] column: usize) {
+    assert_eq!(
+      parse(input),
+      Err(ReplError::Syntax {
+        input: input.to_owned(),
+        column
+      })
+    );
+  }
+
+  #[test]
+  fn session_commands() {
+    let mut session = Session::default();
+    assert_eq!(session.eval(":tt"), Err(ReplError::NoFormula));
+    assert_eq!(
+      session.eval(":tt P -> Q").unwrap(),
+      "\
+P Q | (P → Q)
+T T | T
+T F | F
+F T | T
+F F | T"
+    );
+    assert_eq!(session.eval(":models").unwrap(), "P=T Q=T\nP=F Q=T\nP=F Q=F");
+    assert_eq!(session.eval(":models P & -P").unwrap(), "no models");
+    assert_eq!(session.eval(":nnf -(P -> Q)").unwrap(), "(P & ¬Q)");
+    assert_eq!(session.eval(":cnf (P & -Q) | R").unwrap(), "((P ∨ R) & (¬Q ∨ R))");
+    assert_eq!(session.eval(":equiv P -> Q ; -Q -> -P").unwrap(), "equivalent");
+    assert_eq!(
+      session.eval(":equiv P -> Q ; Q -> P").unwrap(),
+      "not equivalent: they differ at P=T Q=F"
+    );
+    assert_eq!(session.eval(":equiv P"), Err(ReplError::Usage(":equiv φ ; ψ")));
+    assert_eq!(
+      session.eval(":frobnicate"),
+      Err(ReplError::UnknownCommand(":frobnicate".to_owned()))
+    );
+  }
+}
@@ -0,0 +1,115 @@
+//! Sentences as plain JSON trees for JavaScript.
+
+use language::{ast::exp::Exp, parser::parse_sentence};
+use serde::Serialize;
+
+/// A sentence, tagged by `type`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Formula {
+  Atom {
+    predicate: String,
+    individuals: Vec<String>,
+  },
+  Conditional {
+    antecedent: Box<Formula>,
+    consequent: Box<Formula>,
+  },
+  Biconditional {
+    lhs: Box<Formula>,
+    rhs: Box<Formula>,
+  },
+  Conjunction {
+    lhs: Box<Formula>,
+    rhs: Box<Formula>,
+  },
+  Disjunction {
+    lhs: Box<Formula>,
+    rhs: Box<Formula>,
+  },
+  Negation {
+    operand: Box<Formula>,
+  },
+  Universal {
+    variable: String,
+    form: Box<Formula>,
+  },
+  Existential {
+    variable: String,
+    form: Box<Formula>,
+  },
+  Falsum,
+}
+
+impl From<&Exp> for Formula {
+  fn from(exp: &Exp) -> Self {
+    let boxed = |exp: &Exp| Box::new(Formula::from(exp));
+    match exp {
+      Exp::Atom { predicate, individuals } => Formula::Atom {
+        predicate: predicate.clone(),
+        individuals: individuals.clone(),
+      },
+      Exp::Cond { antecedent, consequent } => Formula::Conditional {
+        antecedent: boxed(antecedent),
+        consequent: boxed(consequent),
+      },
+      Exp::Iff { lhs, rhs } => Formula::Biconditional {
+        lhs: boxed(lhs),
+        rhs: boxed(rhs),
+      },
+      Exp::And { lhs, rhs } => Formula::Conjunction {
+        lhs: boxed(lhs),
+        rhs: boxed(rhs),
+      },
+      Exp::Or { lhs, rhs } => Formula::Disjunction {
+        lhs: boxed(lhs),
+        rhs: boxed(rhs),
+      },
+      Exp::Neg(operand) => Formula::Negation {
+        operand: boxed(operand),
+      },
+      Exp::UnivGenr { variable, form } => Formula::Universal {
+        variable: variable.clone(),
+        form: boxed(form),
+      },
+      Exp::ExistGenr { variable, form } => Formula::Existential {
+        variable: variable.clone(),
+        form: boxed(form),
+      },
+      Exp::Falsum => Formula::Falsum,
+    }
+  }
+}
+
+/// Parse a whole sentence, ignoring surrounding whitespace.
+pub fn parse(s: &str) -> Result<Exp, String> {
+  parse_sentence(s).ok_or_else(|| format!("invalid sentence: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn formula_from_exp() {
+    let atom = |predicate: &str, individuals: &[&str]| {
+      Box::new(Formula::Atom {
+        predicate: predicate.to_owned(),
+        individuals: individuals.iter().map(|&ind| ind.to_owned()).collect(),
+      })
+    };
+    assert_eq!(
+      Formula::from(&parse(" (x)(Fx → ¬Ga) ").unwrap()),
+      Formula::Universal {
+        variable: "x".to_owned(),
+        form: Box::new(Formula::Conditional {
+          antecedent: atom("F", &["x"]),
+          consequent: Box::new(Formula::Negation {
+            operand: atom("G", &["a"])
+          }),
+        }),
+      }
+    );
+    assert!(parse("(P →").is_err());
+  }
+}
@@ -0,0 +1,130 @@
+//! The parser and checker for JavaScript, without the playground. Build it with
+//! `wasm-pack build language-wasm`.
+//!
+//! Every function returns a plain object or string and throws an `Error` when its input is invalid.
+
+pub mod formula;
+
+use language_derivation_rule::{
+  document::{DocumentReport, ProofDocument},
+  rule_set::{NameError, Profile, System},
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use self::formula::Formula;
+
+/// `parseFormula("P -> Q")`: the sentence as a tree of `{type, ...}` objects.
+#[wasm_bindgen(js_name = parseFormula)]
+pub fn parse_formula(s: &str) -> Result<JsValue, JsError> {
+  let exp = formula::parse(s).map_err(|err| JsError::new(&err))?;
+  to_js(&Formula::from(&exp))
+}
+
+/// `formatFormula("P -> Q")`: the sentence written with its representative symbols, `(P → Q)`.
+#[wasm_bindgen(js_name = formatFormula)]
+pub fn format_formula(s: &str) -> Result<String, JsError> {
+  formula::parse(s)
+    .map(|exp| exp.to_string())
+    .map_err(|err| JsError::new(&err))
+}
+
+/// `parseRule("1, 2 &I", "Gentzen")`: the rule in its canonical form and the lines it cites. The
+/// system is Gentzen when left out.
+#[wasm_bindgen(js_name = parseRule)]
+pub fn parse_rule(rule: &str, system: Option<String>) -> Result<JsValue, JsError> {
+  to_js(&rule_info(rule, system.as_deref()).map_err(|err| JsError::new(&err))?)
+}
+
+/// `checkDerivation(document, "classical")`: whether each row of a proof document is valid, what it
+/// depends on and what is wrong with it. A bare list of `{sentence, derivation}` rows is also a
+/// document. The profile is classical when left out.
+#[wasm_bindgen(js_name = checkDerivation)]
+pub fn check_derivation(document: JsValue, profile: Option<String>) -> Result<JsValue, JsError> {
+  let document: ProofDocument =
+    serde_wasm_bindgen::from_value(document).map_err(|err| JsError::new(&err.to_string()))?;
+  to_js(&check(&document, profile.as_deref()).map_err(|err| JsError::new(&err))?)
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+  serde_wasm_bindgen::to_value(value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// What `parseRule` tells about a rule.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleInfo {
+  pub text: String,
+  pub cited_lines: Vec<usize>,
+  pub is_assumption: bool,
+}
+
+/// The rule as written in `system`, by its name.
+pub fn rule_info(rule: &str, system: Option<&str>) -> Result<RuleInfo, String> {
+  let system = match system {
+    Some(name) => name.parse().map_err(|err: NameError| err.to_string())?,
+    None => System::default(),
+  };
+  match (system.format_rule(rule), system.cited_lines(rule)) {
+    (Some(text), Some(cited_lines)) => Ok(RuleInfo {
+      text,
+      cited_lines,
+      is_assumption: system.is_assumption(rule),
+    }),
+    _ => Err(format!("invalid rule: {}", rule)),
+  }
+}
+
+/// Check `document` in the logic named `profile`.
+pub fn check(document: &ProofDocument, profile: Option<&str>) -> Result<DocumentReport, String> {
+  let profile = match profile {
+    Some(name) => name.parse().map_err(|err: NameError| err.to_string())?,
+    None => Profile::default(),
+  };
+  document.check(profile).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use language_derivation_rule::document::DocumentRow;
+  use rstest::rstest;
+
+  #[rstest]
+  #[case("1,2 &I", None, Ok(("1, 2 &I", vec![1, 2], false)))]
+  #[case("P", Some("Gentzen"), Ok(("P", vec![], true)))]
+  #[case("A", Some("Lemmon"), Ok(("A", vec![], true)))]
+  #[case("1 &", None, Err("invalid rule: 1 &"))]
+  #[case("P", Some("Frege"), Err("unknown system: Frege"))]
+  fn wasm_rule_info(
+    #[case] rule: &str,
+    #[case] system: Option<&str>,
+    #[case] expected: Result<(&str, Vec<usize>, bool), &str>,
+  ) {
+    let expected = expected
+      .map(|(text, cited_lines, is_assumption)| RuleInfo {
+        text: text.to_owned(),
+        cited_lines,
+        is_assumption,
+      })
+      .map_err(|err| err.to_owned());
+    assert_eq!(rule_info(rule, system), expected);
+  }
+
+  #[test]
+  fn wasm_check() {
+    let rows = [("P", "P"), ("(P ∨ Q)", "1 ∨I")]
+      .into_iter()
+      .map(|(sentence, derivation)| DocumentRow {
+        sentence: sentence.to_owned(),
+        derivation: derivation.to_owned(),
+        comment: None,
+      })
+      .collect();
+    let document = ProofDocument::new(System::Gentzen, rows);
+    let report = check(&document, Some("minimal")).unwrap();
+    assert!(report.rows.iter().all(|row| row.valid));
+    assert_eq!(report.rows[1].dependencies, vec![1]);
+    assert_eq!(check(&document, Some("modal")).unwrap_err(), "unknown profile: modal");
+  }
+}
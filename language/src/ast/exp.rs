@@ -1,4 +1,6 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, fmt};
+
+use crate::parser::individual_constant::var;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum Exp {
@@ -34,21 +36,23 @@ pub enum Exp {
   Falsum,
 }
 
-impl Exp {
-  pub fn to_string(&self) -> String {
+impl fmt::Display for Exp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
-      Exp::Atom { predicate, individuals } => format!("{}{}", predicate, individuals.join("")),
-      Exp::Cond { antecedent, consequent } => format!("({} → {})", antecedent.to_string(), consequent.to_string()),
-      Exp::Iff { lhs, rhs } => format!("({} ↔ {})", lhs.to_string(), rhs.to_string()),
-      Exp::And { lhs, rhs } => format!("({} & {})", lhs.to_string(), rhs.to_string()),
-      Exp::Or { lhs, rhs } => format!("({} ∨ {})", lhs.to_string(), rhs.to_string()),
-      Exp::Neg(lhs) => format!("¬{}", lhs.to_string()),
-      Exp::UnivGenr { variable, form } => format!("({}){}", variable, form.to_string()),
-      Exp::ExistGenr { variable, form } => format!("(∃{}){}", variable, form.to_string()),
-      Exp::Falsum => "⊥".to_owned(),
+      Exp::Atom { predicate, individuals } => write!(f, "{}{}", predicate, individuals.join("")),
+      Exp::Cond { antecedent, consequent } => write!(f, "({} → {})", antecedent, consequent),
+      Exp::Iff { lhs, rhs } => write!(f, "({} ↔ {})", lhs, rhs),
+      Exp::And { lhs, rhs } => write!(f, "({} & {})", lhs, rhs),
+      Exp::Or { lhs, rhs } => write!(f, "({} ∨ {})", lhs, rhs),
+      Exp::Neg(lhs) => write!(f, "¬{}", lhs),
+      Exp::UnivGenr { variable, form } => write!(f, "({}){}", variable, form),
+      Exp::ExistGenr { variable, form } => write!(f, "(∃{}){}", variable, form),
+      Exp::Falsum => write!(f, "⊥"),
     }
   }
+}
 
+impl Exp {
   pub fn negated(&self) -> Self {
     Exp::Neg(Box::new(self.clone()))
   }
@@ -62,7 +66,7 @@ impl Exp {
       }
       | Self::Iff { lhs, rhs }
       | Self::And { lhs, rhs }
-      | Self::Or { lhs, rhs } => ((&lhs.free_variables()) | (&rhs.free_variables())),
+      | Self::Or { lhs, rhs } => &lhs.free_variables() | &rhs.free_variables(),
       Self::Neg(lhs) => lhs.free_variables(),
       Self::UnivGenr { variable, form } | Self::ExistGenr { variable, form } => {
         let mut vars = form.free_variables();
@@ -73,6 +77,34 @@ impl Exp {
     }
   }
 
+  /// The free individual variables, `u` to `z`; `free_variables` counts constants as well.
+  pub fn free_individual_variables(&self) -> BTreeSet<String> {
+    self
+      .free_variables()
+      .into_iter()
+      .filter(|individual| matches!(var(individual), Ok(("", _))))
+      .collect()
+  }
+
+  /// Whether `beta` may replace the free occurrences of `alpha` without a quantifier binding it.
+  pub fn is_free_for(&self, alpha: &str, beta: &str) -> bool {
+    match self {
+      Self::Atom { .. } | Self::Falsum => true,
+      Self::Cond {
+        antecedent: lhs,
+        consequent: rhs,
+      }
+      | Self::Iff { lhs, rhs }
+      | Self::And { lhs, rhs }
+      | Self::Or { lhs, rhs } => lhs.is_free_for(alpha, beta) && rhs.is_free_for(alpha, beta),
+      Self::Neg(lhs) => lhs.is_free_for(alpha, beta),
+      Self::UnivGenr { variable, .. } | Self::ExistGenr { variable, .. } if variable == alpha => true,
+      Self::UnivGenr { variable, form } | Self::ExistGenr { variable, form } => {
+        !(variable == beta && form.free_variables().contains(alpha)) && form.is_free_for(alpha, beta)
+      }
+    }
+  }
+
   /// Replace free variable and returns new expression
   pub fn var_replaced(&self, alpha: &str, beta: &str) -> Self {
     match self {
@@ -215,4 +247,39 @@ mod tests {
   fn exp_to_string(#[case] input: Exp, #[case] expected: &str) {
     assert_eq!(expected, input.to_string())
   }
+
+  #[test]
+  fn exp_free_individual_variables() {
+    let exp = Exp::ExistGenr {
+      variable: "x".to_owned(),
+      form: Box::new(Exp::Atom {
+        predicate: "R".to_owned(),
+        individuals: vec!["x".to_owned(), "y".to_owned(), "a".to_owned()],
+      }),
+    };
+    assert_eq!(exp.free_variables(), ["a".to_owned(), "y".to_owned()].into());
+    assert_eq!(exp.free_individual_variables(), ["y".to_owned()].into());
+  }
+
+  #[rstest]
+  #[case("y", "a", true)]
+  #[case("y", "x", false)]
+  #[case("x", "y", true)]
+  #[case("z", "x", true)]
+  fn exp_is_free_for(#[case] alpha: &str, #[case] beta: &str, #[case] expected: bool) {
+    let exp = Exp::UnivGenr {
+      variable: "x".to_owned(),
+      form: Box::new(Exp::Cond {
+        antecedent: Box::new(Exp::Atom {
+          predicate: "F".to_owned(),
+          individuals: vec!["x".to_owned()],
+        }),
+        consequent: Box::new(Exp::Atom {
+          predicate: "G".to_owned(),
+          individuals: vec!["y".to_owned()],
+        }),
+      }),
+    };
+    assert_eq!(expected, exp.is_free_for(alpha, beta))
+  }
 }
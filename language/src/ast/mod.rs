@@ -1 +1,2 @@
 pub mod exp;
+pub mod sequent;
@@ -0,0 +1,57 @@
+use std::fmt;
+
+use super::exp::Exp;
+
+/// `Γ ⊢ φ`: the conclusion is derivable from the premises.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sequent {
+  pub premises: Vec<Exp>,
+  pub conclusion: Exp,
+}
+
+impl fmt::Display for Sequent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (idx, premise) in self.premises.iter().enumerate() {
+      if idx > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{}", premise)?;
+    }
+    if !self.premises.is_empty() {
+      write!(f, " ")?;
+    }
+    write!(f, "⊢ {}", self.conclusion)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn atom(predicate: &str) -> Exp {
+    Exp::Atom {
+      predicate: predicate.to_owned(),
+      individuals: vec![],
+    }
+  }
+
+  #[test]
+  fn sequent_to_string() {
+    assert_eq!(
+      Sequent {
+        premises: vec![atom("P"), Exp::Neg(Box::new(atom("Q")))],
+        conclusion: atom("R"),
+      }
+      .to_string(),
+      "P, ¬Q ⊢ R"
+    );
+    assert_eq!(
+      Sequent {
+        premises: vec![],
+        conclusion: atom("R"),
+      }
+      .to_string(),
+      "⊢ R"
+    );
+  }
+}
@@ -0,0 +1,49 @@
+//! LaTeX math-mode notation for sentences and sequents, for typesetting proofs.
+
+use crate::ast::{exp::Exp, sequent::Sequent};
+
+/// `exp` in math mode, parenthesized the same way `Display` does.
+pub fn exp(exp: &Exp) -> String {
+  match exp {
+    Exp::Atom { predicate, individuals } => format!("{}{}", predicate, individuals.join("")),
+    Exp::Cond { antecedent, consequent } => format!("({} \\to {})", self::exp(antecedent), self::exp(consequent)),
+    Exp::Iff { lhs, rhs } => format!("({} \\leftrightarrow {})", self::exp(lhs), self::exp(rhs)),
+    Exp::And { lhs, rhs } => format!("({} \\mathbin{{\\&}} {})", self::exp(lhs), self::exp(rhs)),
+    Exp::Or { lhs, rhs } => format!("({} \\lor {})", self::exp(lhs), self::exp(rhs)),
+    Exp::Neg(lhs) => format!("\\neg {}", self::exp(lhs)),
+    Exp::UnivGenr { variable, form } => format!("({}){}", variable, self::exp(form)),
+    Exp::ExistGenr { variable, form } => format!("(\\exists {}){}", variable, self::exp(form)),
+    Exp::Falsum => "\\bot".to_owned(),
+  }
+}
+
+/// `sequent` in math mode.
+pub fn sequent(sequent: &Sequent) -> String {
+  let premises: Vec<String> = sequent.premises.iter().map(exp).collect();
+  match premises.is_empty() {
+    true => format!("\\vdash {}", exp(&sequent.conclusion)),
+    false => format!("{} \\vdash {}", premises.join(", "), exp(&sequent.conclusion)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::{expression::exp as parse_exp, sequent::sequent as parse_sequent};
+  use rstest::rstest;
+
+  #[rstest]
+  #[case("Rab", "Rab")]
+  #[case("(P → ¬Q)", "(P \\to \\neg Q)")]
+  #[case("((P & Q) ∨ ⊥)", "((P \\mathbin{\\&} Q) \\lor \\bot)")]
+  #[case("(x)(∃y)(Fx ↔ Gy)", "(x)(\\exists y)(Fx \\leftrightarrow Gy)")]
+  fn exp_to_latex(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(exp(&parse_exp(input).unwrap().1), expected);
+  }
+
+  #[test]
+  fn sequent_to_latex() {
+    let (_, parsed) = parse_sequent("P, (P → Q) ⊢ Q").unwrap();
+    assert_eq!(sequent(&parsed), "P, (P \\to Q) \\vdash Q");
+  }
+}
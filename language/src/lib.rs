@@ -1,5 +1,9 @@
 pub mod ast;
+pub mod latex;
+pub mod normal_form;
+pub mod notation;
 pub mod parser;
+pub mod semantics;
 
 pub fn add(left: usize, right: usize) -> usize {
   left + right
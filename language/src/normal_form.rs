@@ -0,0 +1,130 @@
+//! Negation and conjunctive normal forms. Both keep quantifiers where they are: a quantified
+//! sentence is put in normal form inside and otherwise stands like an atomic sentence.
+
+use crate::ast::exp::Exp;
+
+/// `exp` with `→` and `↔` spelled out with `¬`, `&` and `∨`, and each `¬` pushed down onto an
+/// atomic sentence or `⊥`.
+pub fn nnf(exp: &Exp) -> Exp {
+  match exp {
+    Exp::Atom { .. } | Exp::Falsum => exp.clone(),
+    Exp::Cond { antecedent, consequent } => or(negated_nnf(antecedent), nnf(consequent)),
+    Exp::Iff { lhs, rhs } => and(or(negated_nnf(lhs), nnf(rhs)), or(negated_nnf(rhs), nnf(lhs))),
+    Exp::And { lhs, rhs } => and(nnf(lhs), nnf(rhs)),
+    Exp::Or { lhs, rhs } => or(nnf(lhs), nnf(rhs)),
+    Exp::Neg(lhs) => negated_nnf(lhs),
+    Exp::UnivGenr { variable, form } => Exp::UnivGenr {
+      variable: variable.clone(),
+      form: Box::new(nnf(form)),
+    },
+    Exp::ExistGenr { variable, form } => Exp::ExistGenr {
+      variable: variable.clone(),
+      form: Box::new(nnf(form)),
+    },
+  }
+}
+
+/// The negation normal form of `¬exp`.
+fn negated_nnf(exp: &Exp) -> Exp {
+  match exp {
+    Exp::Atom { .. } | Exp::Falsum => exp.negated(),
+    Exp::Cond { antecedent, consequent } => and(nnf(antecedent), negated_nnf(consequent)),
+    Exp::Iff { lhs, rhs } => or(and(nnf(lhs), negated_nnf(rhs)), and(negated_nnf(lhs), nnf(rhs))),
+    Exp::And { lhs, rhs } => or(negated_nnf(lhs), negated_nnf(rhs)),
+    Exp::Or { lhs, rhs } => and(negated_nnf(lhs), negated_nnf(rhs)),
+    Exp::Neg(lhs) => nnf(lhs),
+    Exp::UnivGenr { variable, form } => Exp::ExistGenr {
+      variable: variable.clone(),
+      form: Box::new(negated_nnf(form)),
+    },
+    Exp::ExistGenr { variable, form } => Exp::UnivGenr {
+      variable: variable.clone(),
+      form: Box::new(negated_nnf(form)),
+    },
+  }
+}
+
+/// `exp` as a conjunction of disjunctions, by distributing `∨` over `&` in its negation normal form.
+pub fn cnf(exp: &Exp) -> Exp {
+  fn from_nnf(exp: Exp) -> Exp {
+    match exp {
+      Exp::And { lhs, rhs } => and(from_nnf(*lhs), from_nnf(*rhs)),
+      Exp::Or { lhs, rhs } => distribute(from_nnf(*lhs), from_nnf(*rhs)),
+      Exp::UnivGenr { variable, form } => Exp::UnivGenr {
+        variable,
+        form: Box::new(from_nnf(*form)),
+      },
+      Exp::ExistGenr { variable, form } => Exp::ExistGenr {
+        variable,
+        form: Box::new(from_nnf(*form)),
+      },
+      _ => exp,
+    }
+  }
+
+  /// `lhs ∨ rhs` for `lhs` and `rhs` already in conjunctive normal form.
+  fn distribute(lhs: Exp, rhs: Exp) -> Exp {
+    match (lhs, rhs) {
+      (
+        Exp::And {
+          lhs: first,
+          rhs: second,
+        },
+        rhs,
+      ) => and(distribute(*first, rhs.clone()), distribute(*second, rhs)),
+      (
+        lhs,
+        Exp::And {
+          lhs: first,
+          rhs: second,
+        },
+      ) => and(distribute(lhs.clone(), *first), distribute(lhs, *second)),
+      (lhs, rhs) => or(lhs, rhs),
+    }
+  }
+
+  from_nnf(nnf(exp))
+}
+
+fn and(lhs: Exp, rhs: Exp) -> Exp {
+  Exp::And {
+    lhs: Box::new(lhs),
+    rhs: Box::new(rhs),
+  }
+}
+
+fn or(lhs: Exp, rhs: Exp) -> Exp {
+  Exp::Or {
+    lhs: Box::new(lhs),
+    rhs: Box::new(rhs),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::expression::exp;
+  use rstest::rstest;
+
+  #[rstest]
+  #[case("(P → Q)", "(¬P ∨ Q)")]
+  #[case("¬(P & ¬Q)", "(¬P ∨ Q)")]
+  #[case("¬(P ↔ Q)", "((P & ¬Q) ∨ (¬P & Q))")]
+  #[case("¬¬⊥", "⊥")]
+  #[case("¬(x)(Fx → Gx)", "(∃x)(Fx & ¬Gx)")]
+  #[case("¬(∃x)¬Fx", "(x)Fx")]
+  fn normal_form_nnf(#[case] input: &str, #[case] expected: &str) {
+    let (_, input) = exp(input).unwrap();
+    assert_eq!(nnf(&input).to_string(), expected);
+  }
+
+  #[rstest]
+  #[case("(P ∨ (Q & R))", "((P ∨ Q) & (P ∨ R))")]
+  #[case("((P & Q) ∨ (R & S))", "(((P ∨ R) & (P ∨ S)) & ((Q ∨ R) & (Q ∨ S)))")]
+  #[case("(P ↔ Q)", "((¬P ∨ Q) & (¬Q ∨ P))")]
+  #[case("(x)(Fx ∨ (Gx & Hx))", "(x)((Fx ∨ Gx) & (Fx ∨ Hx))")]
+  fn normal_form_cnf(#[case] input: &str, #[case] expected: &str) {
+    let (_, input) = exp(input).unwrap();
+    assert_eq!(cnf(&input).to_string(), expected);
+  }
+}
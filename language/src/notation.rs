@@ -0,0 +1,145 @@
+/// ASCII aliases accepted by `parser::symbol` and the representative symbols they stand for.
+///
+/// Longer aliases come first so that `<->` wins over `->` and `->` wins over `-`.
+pub const ALIASES: [(&str, &str); 6] = [
+  ("<->", "↔"),
+  ("->", "→"),
+  ("\\bot", "⊥"),
+  ("|", "∨"),
+  ("-", "¬"),
+  ("]", "∃"),
+];
+
+/// Replace ASCII aliases in a sentence being typed with their representative symbols.
+///
+/// `caret` is the cursor position counted in chars; the returned position points at the same place
+/// in the replaced text. An alias ending right at the caret is left alone while it may still grow
+/// into a longer one (`-` → `->`), so the user can finish typing it.
+pub fn replace_aliases(s: &str, caret: usize) -> (String, usize) {
+  replace_aliases_with(s, caret, &ALIASES, |_, _| false)
+}
+
+/// Same as `replace_aliases`, for sequents. `|-` is left as typed, since it is either the turnstile or
+/// a disjunction and a negation (`(P|-Q)`), which only the sequent parser can tell apart; so is a `|`
+/// at the caret, which may still become one.
+pub fn replace_sequent_aliases(s: &str, caret: usize) -> (String, usize) {
+  replace_aliases_with(s, caret, &ALIASES, |chars, idx| match chars[idx] {
+    '|' => chars.get(idx + 1) == Some(&'-') || idx + 1 == caret,
+    '-' => idx > 0 && chars[idx - 1] == '|',
+    _ => false,
+  })
+}
+
+/// Same as `replace_aliases`, but with the given alias table, and leaves the alias at the given char
+/// index untouched whenever `keep` returns `true`. Used by grammars where an alias character has
+/// another meaning (e.g. ranges).
+pub fn replace_aliases_with<F>(s: &str, caret: usize, aliases: &[(&str, &str)], keep: F) -> (String, usize)
+where
+  F: Fn(&[char], usize) -> bool,
+{
+  let chars: Vec<char> = s.chars().collect();
+  let mut replaced = String::with_capacity(s.len());
+  let mut replaced_len = 0;
+  let mut new_caret = None;
+
+  let mut idx = 0;
+  while idx < chars.len() {
+    if idx == caret {
+      new_caret = Some(replaced_len);
+    }
+
+    let alias = aliases.iter().find(|(alias, _)| starts_with(&chars[idx..], alias));
+    let (consumed, symbol) = match alias {
+      Some((alias, symbol)) => {
+        let len = alias.chars().count();
+        let is_pending = idx + len == caret
+          && aliases
+            .iter()
+            .any(|(other, _)| other.starts_with(alias) && other != alias);
+        if is_pending || keep(&chars, idx) {
+          (1, None)
+        } else {
+          (len, Some(*symbol))
+        }
+      }
+      None => (1, None),
+    };
+
+    match symbol {
+      Some(symbol) => {
+        replaced.push_str(symbol);
+        replaced_len += symbol.chars().count();
+      }
+      None => {
+        replaced.push(chars[idx]);
+        replaced_len += 1;
+      }
+    }
+    if idx < caret && caret < idx + consumed {
+      new_caret = Some(replaced_len);
+    }
+    idx += consumed;
+  }
+
+  (replaced, new_caret.unwrap_or(replaced_len))
+}
+
+fn starts_with(chars: &[char], prefix: &str) -> bool {
+  let mut chars = chars.iter();
+  prefix.chars().all(|p| chars.next() == Some(&p))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  #[rstest]
+  #[case("P -> Q", 6, "P → Q", 5)]
+  #[case("P <-> Q", 7, "P ↔ Q", 5)]
+  #[case("(P | Q) & -R", 12, "(P ∨ Q) & ¬R", 12)]
+  #[case("(]x)Fx", 6, "(∃x)Fx", 6)]
+  #[case("\\bot", 4, "⊥", 1)]
+  #[case("-P -> Q", 2, "¬P → Q", 2)]
+  #[case("P -> Q", 3, "P → Q", 3)]
+  #[case("P → Q", 5, "P → Q", 5)]
+  fn replace_aliases_valid(
+    #[case] input: &str,
+    #[case] caret: usize,
+    #[case] expected: &str,
+    #[case] expected_caret: usize,
+  ) {
+    assert_eq!(replace_aliases(input, caret), (expected.to_owned(), expected_caret));
+  }
+
+  #[rstest]
+  #[case("P, P -> Q |- Q", 14, "P, P → Q |- Q", 13)]
+  #[case("P | Q |", 7, "P ∨ Q |", 7)]
+  #[case("(]x)Fx |- -(x)-Fx", 17, "(∃x)Fx |- ¬(x)¬Fx", 17)]
+  #[case("(P|-Q) |- R", 11, "(P|-Q) |- R", 11)]
+  fn replace_sequent_aliases_valid(
+    #[case] input: &str,
+    #[case] caret: usize,
+    #[case] expected: &str,
+    #[case] expected_caret: usize,
+  ) {
+    assert_eq!(
+      replace_sequent_aliases(input, caret),
+      (expected.to_owned(), expected_caret)
+    );
+  }
+
+  #[rstest]
+  #[case("P -", 3, "P -", 3)]
+  #[case("P <-", 4, "P <-", 4)]
+  #[case("\\bo", 3, "\\bo", 3)]
+  #[case("-P -", 4, "¬P -", 4)]
+  fn replace_aliases_pending(
+    #[case] input: &str,
+    #[case] caret: usize,
+    #[case] expected: &str,
+    #[case] expected_caret: usize,
+  ) {
+    assert_eq!(replace_aliases(input, caret), (expected.to_owned(), expected_caret));
+  }
+}
@@ -20,11 +20,11 @@ pub fn dim(s: &str) -> IResult<&str, u8> {
 }
 
 fn var_ch(s: &str) -> IResult<&str, char> {
-  satisfy(|c| c >= 'u' && c <= 'z')(s)
+  satisfy(|c| ('u'..='z').contains(&c))(s)
 }
 
 fn ind_ch(s: &str) -> IResult<&str, char> {
-  satisfy(|c| c >= 'a' && c <= 't')(s)
+  satisfy(|c| ('a'..='t').contains(&c))(s)
 }
 
 pub fn var(s: &str) -> IResult<&str, &str> {
@@ -41,7 +41,7 @@ pub fn ind_sym(s: &str) -> IResult<&str, &str> {
 }
 
 pub fn pre(s: &str) -> IResult<&str, &str> {
-  recognize(pair(satisfy(|c| c >= 'A' && c <= 'Z'), opt(subscr)))(s)
+  recognize(pair(satisfy(|c| c.is_ascii_uppercase()), opt(subscr)))(s)
 }
 
 #[cfg(test)]
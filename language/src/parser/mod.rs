@@ -1,4 +1,33 @@
 pub mod expression;
-mod individual_constant;
+pub mod individual_constant;
+pub mod sequent;
 pub mod symbol;
 pub mod util;
+
+use crate::ast::exp::Exp;
+
+/// A whole sentence, ignoring the whitespace around it.
+pub fn parse_sentence(s: &str) -> Option<Exp> {
+  match expression::exp(s.trim()) {
+    Ok(("", exp)) => Some(exp),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_sentence_whole() {
+    assert_eq!(
+      parse_sentence(" Fa "),
+      Some(Exp::Atom {
+        predicate: "F".to_owned(),
+        individuals: vec!["a".to_owned()],
+      })
+    );
+    assert_eq!(parse_sentence("Fa )"), None);
+    assert_eq!(parse_sentence(""), None);
+  }
+}
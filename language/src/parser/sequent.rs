@@ -0,0 +1,109 @@
+use super::{expression::exp, symbol::turnstile, util::ws};
+use crate::ast::sequent::Sequent;
+use nom::{
+  bytes::complete::tag,
+  character::complete::multispace0,
+  combinator::all_consuming,
+  error::{Error, ErrorKind},
+  multi::separated_list0,
+  sequence::{pair, preceded, terminated},
+  IResult,
+};
+
+/// `|-` also reads as a disjunction followed by a negation, as in `(P|-Q)`, so each place a turnstile
+/// could start is tried from the left, until the premises before it and the conclusion after it both read.
+pub fn sequent(s: &str) -> IResult<&str, Sequent> {
+  let mut error = nom::Err::Error(Error::new(s, ErrorKind::Tag));
+  let candidates = (0..s.len()).filter(|&idx| s.is_char_boundary(idx) && turnstile(&s[idx..]).is_ok());
+  for idx in candidates {
+    let (before, after) = s.split_at(idx);
+    let premises = all_consuming(terminated(separated_list0(ws(tag(",")), ws(exp)), multispace0))(before);
+    let conclusion = preceded(pair(turnstile, multispace0), exp)(after);
+    match (premises, conclusion) {
+      (Ok((_, premises)), Ok((rest, conclusion))) => return Ok((rest, Sequent { premises, conclusion })),
+      (Err(err), _) | (_, Err(err)) => error = err,
+    }
+  }
+  Err(error)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ast::exp::Exp;
+  use nom::IResult;
+
+  fn atom(predicate: &str) -> Exp {
+    Exp::Atom {
+      predicate: predicate.to_owned(),
+      individuals: vec![],
+    }
+  }
+
+  #[test]
+  fn sequent_valid() {
+    let expected = Sequent {
+      premises: vec![
+        atom("P"),
+        Exp::Cond {
+          antecedent: Box::new(atom("P")),
+          consequent: Box::new(atom("Q")),
+        },
+      ],
+      conclusion: atom("Q"),
+    };
+    assert_eq!(sequent("P, (P → Q) ⊢ Q"), IResult::Ok(("", expected.clone())));
+    assert_eq!(sequent("P,P -> Q |- Q"), IResult::Ok(("", expected)));
+    assert_eq!(
+      sequent("⊢ (P ∨ ¬P)"),
+      IResult::Ok((
+        "",
+        Sequent {
+          premises: vec![],
+          conclusion: Exp::Or {
+            lhs: Box::new(atom("P")),
+            rhs: Box::new(Exp::Neg(Box::new(atom("P")))),
+          },
+        }
+      ))
+    );
+    assert_eq!(
+      sequent("P | -Q |- R"),
+      IResult::Ok((
+        "",
+        Sequent {
+          premises: vec![Exp::Or {
+            lhs: Box::new(atom("P")),
+            rhs: Box::new(Exp::Neg(Box::new(atom("Q")))),
+          }],
+          conclusion: atom("R"),
+        }
+      ))
+    );
+  }
+
+  #[test]
+  fn sequent_disjoined_negation() {
+    let or_not_q = Exp::Or {
+      lhs: Box::new(atom("P")),
+      rhs: Box::new(Exp::Neg(Box::new(atom("Q")))),
+    };
+    assert_eq!(exp("(P|-Q)"), IResult::Ok(("", or_not_q.clone())));
+    assert_eq!(
+      sequent("(P|-Q) |- R"),
+      IResult::Ok((
+        "",
+        Sequent {
+          premises: vec![or_not_q],
+          conclusion: atom("R"),
+        }
+      ))
+    );
+  }
+
+  #[test]
+  fn sequent_invalid() {
+    assert!(sequent("P, Q").is_err());
+    assert!(sequent("P, ⊢ Q").is_err());
+  }
+}
@@ -27,3 +27,7 @@ pub fn existential(s: &str) -> IResult<&str, &str> {
 pub fn falsum(s: &str) -> IResult<&str, &str> {
   alt((tag("⊥"), tag("\\bot")))(s)
 }
+
+pub fn turnstile(s: &str) -> IResult<&str, &str> {
+  alt((tag("⊢"), tag("|-")))(s)
+}
@@ -2,9 +2,9 @@ use nom::{character::complete::multispace0, error::ParseError, sequence::delimit
 
 /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
 /// trailing whitespace, returning the output of `inner`.
-pub fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+pub fn ws<'a, F, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
-  F: Fn(&'a str) -> IResult<&'a str, O, E>,
+  F: 'a + Fn(&'a str) -> IResult<&'a str, O, E>,
 {
   delimited(multispace0, inner, multispace0)
 }
@@ -0,0 +1,198 @@
+//! Truth-functional semantics: truth tables and entailment for sentences without quantifiers. Each
+//! atomic sentence, with or without individuals, is a sentence letter.
+
+use std::{collections::BTreeMap, error, fmt};
+
+use crate::ast::{exp::Exp, sequent::Sequent};
+
+/// The most sentence letters a table is built for; it has `2^n` rows.
+pub const MAX_LETTERS: usize = 12;
+
+/// A truth value for each sentence letter.
+pub type Valuation = BTreeMap<String, bool>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticsError {
+  /// A sentence has a quantifier, so truth tables do not decide it.
+  Quantified,
+  /// More than `MAX_LETTERS` sentence letters.
+  TooManyLetters { count: usize },
+}
+
+impl fmt::Display for SemanticsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SemanticsError::Quantified => write!(f, "sentences with quantifiers have no truth table"),
+      SemanticsError::TooManyLetters { count } => {
+        write!(f, "{} sentence letters, at most {} are supported", count, MAX_LETTERS)
+      }
+    }
+  }
+}
+
+impl error::Error for SemanticsError {}
+
+/// The sentence letters of `exps`, sorted.
+pub fn letters(exps: &[Exp]) -> Result<Vec<String>, SemanticsError> {
+  fn collect(exp: &Exp, letters: &mut Vec<String>) -> Result<(), SemanticsError> {
+    match exp {
+      Exp::Atom { .. } => letters.push(exp.to_string()),
+      Exp::Cond {
+        antecedent: lhs,
+        consequent: rhs,
+      }
+      | Exp::Iff { lhs, rhs }
+      | Exp::And { lhs, rhs }
+      | Exp::Or { lhs, rhs } => {
+        collect(lhs, letters)?;
+        collect(rhs, letters)?;
+      }
+      Exp::Neg(lhs) => collect(lhs, letters)?,
+      Exp::UnivGenr { .. } | Exp::ExistGenr { .. } => return Err(SemanticsError::Quantified),
+      Exp::Falsum => {}
+    }
+    Ok(())
+  }
+
+  let mut letters = vec![];
+  for exp in exps {
+    collect(exp, &mut letters)?;
+  }
+  letters.sort_unstable();
+  letters.dedup();
+  Ok(letters)
+}
+
+/// The truth value of `exp`. `None` when it has a quantifier or a letter `valuation` leaves out.
+pub fn evaluate(exp: &Exp, valuation: &Valuation) -> Option<bool> {
+  match exp {
+    Exp::Atom { .. } => valuation.get(&exp.to_string()).copied(),
+    Exp::Cond { antecedent, consequent } => Some(!evaluate(antecedent, valuation)? || evaluate(consequent, valuation)?),
+    Exp::Iff { lhs, rhs } => Some(evaluate(lhs, valuation)? == evaluate(rhs, valuation)?),
+    Exp::And { lhs, rhs } => Some(evaluate(lhs, valuation)? && evaluate(rhs, valuation)?),
+    Exp::Or { lhs, rhs } => Some(evaluate(lhs, valuation)? || evaluate(rhs, valuation)?),
+    Exp::Neg(lhs) => Some(!evaluate(lhs, valuation)?),
+    Exp::UnivGenr { .. } | Exp::ExistGenr { .. } => None,
+    Exp::Falsum => Some(false),
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TruthTable {
+  pub letters: Vec<String>,
+  /// From all letters true to all false, as in textbooks.
+  pub rows: Vec<TruthRow>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TruthRow {
+  /// The value of each letter, in the order of `TruthTable::letters`
+  pub valuation: Vec<bool>,
+  /// The value of each sentence the table is for
+  pub values: Vec<bool>,
+}
+
+/// Every valuation of the letters in `exps` and the values of `exps` under it.
+pub fn truth_table(exps: &[Exp]) -> Result<TruthTable, SemanticsError> {
+  let letters = letters(exps)?;
+  let rows = valuations(&letters)?
+    .map(|valuation| TruthRow {
+      values: exps
+        .iter()
+        .map(|exp| evaluate(exp, &valuation).unwrap_or_default())
+        .collect(),
+      valuation: letters.iter().map(|letter| valuation[letter]).collect(),
+    })
+    .collect();
+  Ok(TruthTable { letters, rows })
+}
+
+/// A valuation making every premise true and the conclusion false; `None` when the premises entail
+/// the conclusion.
+pub fn counterexample(sequent: &Sequent) -> Result<Option<Valuation>, SemanticsError> {
+  let mut exps = sequent.premises.clone();
+  exps.push(sequent.conclusion.clone());
+  let letters = letters(&exps)?;
+  let found = valuations(&letters)?.find(|valuation| {
+    sequent
+      .premises
+      .iter()
+      .all(|premise| evaluate(premise, valuation) == Some(true))
+      && evaluate(&sequent.conclusion, valuation) == Some(false)
+  });
+  Ok(found)
+}
+
+fn valuations(letters: &[String]) -> Result<impl Iterator<Item = Valuation> + '_, SemanticsError> {
+  if letters.len() > MAX_LETTERS {
+    return Err(SemanticsError::TooManyLetters { count: letters.len() });
+  }
+  let count = letters.len();
+  Ok((0..1usize << count).map(move |row| {
+    letters
+      .iter()
+      .enumerate()
+      .map(|(idx, letter)| (letter.clone(), row >> (count - 1 - idx) & 1 == 0))
+      .collect()
+  }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::{expression::exp, sequent::sequent};
+  use rstest::rstest;
+
+  fn parse(s: &str) -> Exp {
+    exp(s).unwrap().1
+  }
+
+  #[test]
+  fn semantics_truth_table() {
+    let table = truth_table(&[parse("(P → Q)"), parse("¬P")]).unwrap();
+    assert_eq!(table.letters, vec!["P", "Q"]);
+    let rows: Vec<(Vec<bool>, Vec<bool>)> = table.rows.into_iter().map(|row| (row.valuation, row.values)).collect();
+    assert_eq!(
+      rows,
+      vec![
+        (vec![true, true], vec![true, false]),
+        (vec![true, false], vec![false, false]),
+        (vec![false, true], vec![true, true]),
+        (vec![false, false], vec![true, true]),
+      ]
+    );
+  }
+
+  #[rstest]
+  #[case("(P → Q), P ⊢ Q", None)]
+  #[case("⊢ (P ∨ ¬P)", None)]
+  #[case("⊥ ⊢ Fa", None)]
+  #[case("(P → Q), Q ⊢ P", Some(vec![("P", false), ("Q", true)]))]
+  #[case("(Fa ∨ Gb) ⊢ Fa", Some(vec![("Fa", false), ("Gb", true)]))]
+  fn semantics_counterexample(#[case] s: &str, #[case] expected: Option<Vec<(&str, bool)>>) {
+    let sequent = sequent(s).unwrap().1;
+    let expected = expected.map(|pairs| {
+      pairs
+        .into_iter()
+        .map(|(letter, value)| (letter.to_owned(), value))
+        .collect()
+    });
+    assert_eq!(counterexample(&sequent), Ok(expected));
+  }
+
+  #[test]
+  fn semantics_unsupported() {
+    assert_eq!(truth_table(&[parse("(x)Fx")]), Err(SemanticsError::Quantified));
+    let many = (0..=MAX_LETTERS)
+      .map(|idx| parse(&((b'A' + idx as u8) as char).to_string()))
+      .reduce(|lhs, rhs| Exp::And {
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+      })
+      .unwrap();
+    assert_eq!(
+      truth_table(&[many]),
+      Err(SemanticsError::TooManyLetters { count: MAX_LETTERS + 1 })
+    );
+  }
+}
@@ -1 +1,3 @@
+pub mod palette;
+pub mod proof_tree;
 pub mod row;
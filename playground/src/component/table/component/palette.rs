@@ -0,0 +1,64 @@
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, InputEvent, InputEventInit};
+use yew::{classes, events::MouseEvent, function_component, html, Callback, Html};
+
+const SYMBOLS: [&str; 9] = ["¬", "&", "∨", "→", "↔", "∃", "⊥", "(", ")"];
+
+/// 포커스된 입력칸의 커서 위치에 기호를 넣습니다.
+fn insert_at_caret(symbol: &str) {
+  let input = match web_sys::window()
+    .and_then(|window| window.document())
+    .and_then(|document| document.active_element())
+    .and_then(|element| element.dyn_into::<HtmlInputElement>().ok())
+  {
+    Some(input) if !input.read_only() => input,
+    _ => return,
+  };
+
+  let value: Vec<char> = input.value().chars().collect();
+  let start = input
+    .selection_start()
+    .ok()
+    .flatten()
+    .map_or(value.len(), |i| i as usize);
+  let end = input.selection_end().ok().flatten().map_or(start, |i| i as usize);
+  let (start, end) = (start.min(value.len()), end.min(value.len()).max(start));
+  let next: String = value[..start]
+    .iter()
+    .chain(symbol.chars().collect::<Vec<_>>().iter())
+    .chain(value[end..].iter())
+    .collect();
+  let caret = (start + symbol.chars().count()) as u32;
+  input.set_value(&next);
+  let _ = input.set_selection_range(caret, caret);
+
+  // 행 컴포넌트의 `oninput` 핸들러가 상태를 갱신하도록 입력 이벤트를 발생시킵니다.
+  let mut init = InputEventInit::new();
+  init.bubbles(true);
+  if let Ok(event) = InputEvent::new_with_event_init_dict("input", &init) {
+    let _ = input.dispatch_event(&event);
+  }
+}
+
+#[function_component(Palette)]
+pub fn palette() -> Html {
+  // 버튼을 누를 때 입력칸의 포커스를 잃지 않도록 합니다.
+  let handle_mousedown = Callback::from(|e: MouseEvent| e.prevent_default());
+
+  html! {
+    <div class="flex flex-wrap gap-1 py-2 not-prose">
+      { for SYMBOLS.iter().map(|&symbol| {
+        let handle_click = Callback::from(move |_: MouseEvent| insert_at_caret(symbol));
+        html! {
+          <button
+            class={classes!("w-10", "h-10", "font-mono", "rounded", "bg-slate-100", "hover:bg-slate-200")}
+            onmousedown={handle_mousedown.clone()}
+            onclick={handle_click}
+          >
+            {symbol}
+          </button>
+        }
+      }) }
+    </div>
+  }
+}
@@ -0,0 +1,181 @@
+use itertools::Itertools;
+use language_derivation_rule::tree::Tree;
+use yew::{function_component, html, Html, Properties};
+
+// 고정폭 글꼴을 쓰므로 글자 수로 너비를 어림합니다.
+const FONT_SIZE: f64 = 14.0;
+const CHAR_WIDTH: f64 = 8.4;
+const LABEL_FONT_SIZE: f64 = 11.0;
+const LABEL_CHAR_WIDTH: f64 = 6.6;
+/// 추론선과 위아래 식 사이의 간격
+const RULE_GAP: f64 = 4.0;
+/// 나란히 놓인 전제 사이의 간격
+const PREMISE_GAP: f64 = 24.0;
+
+#[derive(Properties, PartialEq)]
+pub struct ProofTreeProps {
+  pub tree: Tree,
+}
+
+/// 증명 나무를 SVG로 그립니다. 소거된 가정은 대괄호로 묶고, 가정과 그 가정을 소거한 추론에 같은 번호를
+/// 위첨자로 붙입니다.
+#[function_component(ProofTree)]
+pub fn proof_tree(props: &ProofTreeProps) -> Html {
+  let layout = Layout::new(&props.tree, &mut vec![]);
+  let mut elements = vec![];
+  layout.draw(0.0, 0.0, &mut elements);
+  html! {
+    <svg
+      width={layout.width.ceil().to_string()}
+      height={layout.height.ceil().to_string()}
+      font-family="monospace"
+      font-size={FONT_SIZE.to_string()}
+    >
+      { for elements }
+    </svg>
+  }
+}
+
+/// 식이나 규칙 이름과 그 위첨자
+struct Text {
+  body: String,
+  superscript: String,
+}
+
+impl Text {
+  fn width(&self, char_width: f64) -> f64 {
+    self.body.chars().count() as f64 * char_width + self.superscript.chars().count() as f64 * LABEL_CHAR_WIDTH
+  }
+
+  fn html(&self, x: f64, y: f64, anchor: &'static str, font_size: f64) -> Html {
+    html! {
+      <text x={x.to_string()} y={y.to_string()} text-anchor={anchor} font-size={font_size.to_string()}>
+        {&self.body}
+        if !self.superscript.is_empty() {
+          <tspan dy="-5" font-size={LABEL_FONT_SIZE.to_string()}>{&self.superscript}</tspan>
+        }
+      </text>
+    }
+  }
+}
+
+/// 각 부분 나무가 차지하는 상자. `body_width`는 규칙 이름을 뺀 너비입니다.
+struct Layout {
+  conclusion: Text,
+  label: Option<Text>,
+  premises: Vec<Layout>,
+  premises_width: f64,
+  premises_height: f64,
+  body_width: f64,
+  width: f64,
+  height: f64,
+}
+
+impl Layout {
+  /// `bound`는 바깥 추론들이 소거하는 가정의 번호입니다.
+  fn new(tree: &Tree, bound: &mut Vec<usize>) -> Layout {
+    match tree {
+      Tree::Assumption { label, exp } => {
+        let conclusion = match bound.contains(label) {
+          true => Text {
+            body: format!("[{}]", exp),
+            superscript: label.to_string(),
+          },
+          false => Text {
+            body: exp.to_string(),
+            superscript: "".to_owned(),
+          },
+        };
+        let width = conclusion.width(CHAR_WIDTH);
+        Layout {
+          conclusion,
+          label: None,
+          premises: vec![],
+          premises_width: 0.0,
+          premises_height: 0.0,
+          body_width: width,
+          width,
+          height: FONT_SIZE + RULE_GAP,
+        }
+      }
+      Tree::Inference { exp, step, premises } => {
+        let premises = premises
+          .iter()
+          .map(|premise| {
+            let label = premise.hypothesis.as_ref().map(|hypothesis| hypothesis.label);
+            bound.extend(label);
+            let layout = Layout::new(&premise.tree, bound);
+            if label.is_some() {
+              bound.pop();
+            }
+            layout
+          })
+          .collect_vec();
+        let conclusion = Text {
+          body: exp.to_string(),
+          superscript: "".to_owned(),
+        };
+        let label = Text {
+          body: step.to_string(),
+          superscript: tree.discharged_labels().iter().join(","),
+        };
+        let premises_width = premises.iter().map(|premise| premise.width).sum::<f64>()
+          + PREMISE_GAP * premises.len().saturating_sub(1) as f64;
+        let premises_height = premises.iter().map(|premise| premise.height).fold(0.0, f64::max);
+        let body_width = premises_width.max(conclusion.width(CHAR_WIDTH));
+        Layout {
+          width: body_width + RULE_GAP + label.width(LABEL_CHAR_WIDTH),
+          height: premises_height + RULE_GAP * 2.0 + FONT_SIZE + RULE_GAP,
+          conclusion,
+          label: Some(label),
+          premises,
+          premises_width,
+          premises_height,
+          body_width,
+        }
+      }
+    }
+  }
+
+  fn draw(&self, left: f64, top: f64, out: &mut Vec<Html>) {
+    let center = left + self.body_width / 2.0;
+    let label = match &self.label {
+      Some(label) => label,
+      None => {
+        out.push(self.conclusion.html(center, top + FONT_SIZE, "middle", FONT_SIZE));
+        return;
+      }
+    };
+
+    // 전제들은 아래를 맞춰 가운데에 늘어놓습니다.
+    let mut x = center - self.premises_width / 2.0;
+    for premise in &self.premises {
+      premise.draw(x, top + self.premises_height - premise.height, out);
+      x += premise.width + PREMISE_GAP;
+    }
+
+    let rule_y = top + self.premises_height + RULE_GAP;
+    // 본체 너비는 전제들과 결론 중 넓은 쪽이므로, 추론선은 본체 전체에 걸칩니다.
+    let rule_right = left + self.body_width;
+    out.push(html! {
+      <line
+        x1={left.to_string()}
+        y1={rule_y.to_string()}
+        x2={rule_right.to_string()}
+        y2={rule_y.to_string()}
+        stroke="black"
+      />
+    });
+    out.push(label.html(
+      rule_right + RULE_GAP,
+      rule_y + LABEL_FONT_SIZE / 3.0,
+      "start",
+      LABEL_FONT_SIZE,
+    ));
+    out.push(
+      self
+        .conclusion
+        .html(center, rule_y + RULE_GAP + FONT_SIZE, "middle", FONT_SIZE),
+    );
+  }
+}
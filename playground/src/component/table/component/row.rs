@@ -1,7 +1,12 @@
 use std::collections::HashSet;
 
-use super::super::parser::{parse_exp, parse_rule};
+use super::super::parser::parse_exp;
+use crate::i18n::use_messages;
 use itertools::Itertools;
+use language::notation::replace_aliases as replace_exp_aliases;
+use language_derivation_rule::{
+  citation::CitationError, notation::replace_aliases as replace_rule_aliases, rule_set::System,
+};
 use web_sys::HtmlInputElement;
 use yew::{
   classes,
@@ -17,6 +22,8 @@ pub struct RowProps {
   pub class: AttrValue,
   #[prop_or(false)]
   pub readonly: bool,
+  #[prop_or_default]
+  pub system: System,
 
   #[prop_or(HashSet::from([]))]
   pub dependents: HashSet<usize>,
@@ -27,6 +34,17 @@ pub struct RowProps {
   pub derivation: AttrValue,
   #[prop_or(false)]
   pub is_derivation_valid: bool,
+  /// 왼쪽부터 각 열에 그릴 가정 범위 막대
+  #[prop_or_default]
+  pub scope_bars: Vec<Option<ScopeBar>>,
+  #[prop_or_default]
+  pub citation_errors: Vec<CitationError>,
+  /// 도출규칙으로부터 얻는 식. 식 칸이 비어 있을 때 눌러서 채울 수 있습니다.
+  #[prop_or_default]
+  pub suggestions: Vec<String>,
+  /// 식을 뒷받침하는 도출규칙. 도출규칙이 비어 있거나 올바르지 않을 때 눌러서 바꿀 수 있습니다.
+  #[prop_or_default]
+  pub justifications: Vec<String>,
 
   #[prop_or(Callback::noop())]
   pub on_change_sentence: Callback<String>,
@@ -42,8 +60,31 @@ pub struct RowProps {
   pub on_blur: Callback<()>,
 }
 
+/// 행 하나에 걸친 가정 범위 막대. 범위의 첫 행(가정)과 마지막 행에서 모양이 달라집니다.
+#[derive(Clone, PartialEq)]
+pub struct ScopeBar {
+  pub is_start: bool,
+  pub is_end: bool,
+}
+
+/// 입력 중인 값의 대체 기호를 대표 기호로 바꾸고, 커서 위치를 유지합니다.
+pub fn replace_in_place(input: &HtmlInputElement, replace: fn(&str, usize) -> (String, usize)) -> String {
+  let value = input.value();
+  let caret = match input.selection_start() {
+    Ok(Some(caret)) => caret as usize,
+    _ => value.chars().count(),
+  };
+  let (replaced, caret) = replace(&value, caret);
+  if replaced != value {
+    input.set_value(&replaced);
+    let _ = input.set_selection_range(caret as u32, caret as u32);
+  }
+  replaced
+}
+
 #[function_component(Row)]
 pub fn row(props: &RowProps) -> Html {
+  let t = use_messages();
   let is_sentence_syntax_valid = use_memo(
     |sentence| match parse_exp(sentence) {
       Ok(_) => true,
@@ -52,25 +93,22 @@ pub fn row(props: &RowProps) -> Html {
     props.sentence.clone(),
   );
   let is_rule_syntax_valid = use_memo(
-    |rule| match parse_rule(rule) {
-      Ok(_) => true,
-      Err(()) => false,
-    },
-    props.derivation.clone(),
+    |(system, rule)| system.is_rule_syntax_valid(rule),
+    (props.system.clone(), props.derivation.clone()),
   );
 
   let handle_sentence_input = {
     let on_change_sentence = props.on_change_sentence.clone();
     Callback::from(move |e: InputEvent| {
       let target: HtmlInputElement = e.target_unchecked_into();
-      on_change_sentence.emit(target.value());
+      on_change_sentence.emit(replace_in_place(&target, replace_exp_aliases));
     })
   };
   let handle_derivation_input = {
     let on_change_derivation = props.on_change_derivation.clone();
     Callback::from(move |e: InputEvent| {
       let target: HtmlInputElement = e.target_unchecked_into();
-      on_change_derivation.emit(target.value());
+      on_change_derivation.emit(replace_in_place(&target, replace_rule_aliases));
     })
   };
   let handle_inputs_keypress = {
@@ -85,7 +123,7 @@ pub fn row(props: &RowProps) -> Html {
     })
   };
 
-  const CLS_CELL: &'static str = "h-full p-[10px] pb-[9px] border-b border-b-gray-300";
+  const CLS_CELL: &str = "h-full p-[10px] pb-[9px] border-b border-b-gray-300";
   let cls_input = classes!(
     "w-full",
     "focus:outline-none",
@@ -99,20 +137,18 @@ pub fn row(props: &RowProps) -> Html {
       "bg-gray-100"
     )),
   );
-  const CLS_INPUT_VALID: &'static str = "focus:border-green-400";
-  const CLS_INPUT_INVALID: &'static str = "border-b-red-400 focus:border-red-400";
-  const CLS_DIV_VALID: &'static str = "";
-  const CLS_DIV_INVALID: &'static str = "border-b-red-400";
-  const CLS_SYNTAX_VALID: &'static str = "";
-  const CLS_SYNTAX_INVALID: &'static str = "focus:underline focus:decoration-wavy focus:decoration-red-400";
+  const CLS_INPUT_VALID: &str = "focus:border-green-400";
+  const CLS_INPUT_INVALID: &str = "border-b-red-400 focus:border-red-400";
+  const CLS_DIV_VALID: &str = "";
+  const CLS_DIV_INVALID: &str = "border-b-red-400";
+  const CLS_SYNTAX_VALID: &str = "";
+  const CLS_SYNTAX_INVALID: &str = "focus:underline focus:decoration-wavy focus:decoration-red-400";
   html! {
     <tr class={classes!("h-fit", "[&>td]:h-full", props.class.to_string())}>
       <td class={classes!("text-gray-400", "break-word")}>
         <div class={classes!(
           CLS_CELL,
-          props.is_dependents_complete
-            .then_some(CLS_DIV_VALID.clone())
-            .unwrap_or(CLS_DIV_INVALID.clone()),
+          if props.is_dependents_complete { CLS_DIV_VALID } else { CLS_DIV_INVALID },
         )}>
           { &props.dependents.iter().sorted_unstable().join(",") }
         </div>
@@ -120,6 +156,27 @@ pub fn row(props: &RowProps) -> Html {
       <td class={classes!("text-gray-400")}>
         <div class={classes!(CLS_CELL, "text-right")}>{ &props.num }</div>
       </td>
+      <td class="h-full">
+        <div class="flex h-full">
+          { for props.scope_bars.iter().map(|bar| html! {
+            <div class="w-3 h-full shrink-0">
+              if let Some(ScopeBar { is_start, is_end }) = bar {
+                <div class={classes!(
+                  "border-l-2",
+                  "border-slate-400",
+                  // 가정 행에서는 막대를 조금 아래에서 시작하고, 가정 아래에 가로선을 긋습니다.
+                  match (is_start, is_end) {
+                    (true, true) => "mt-2 h-[calc(100%-1rem)] border-b-2",
+                    (true, false) => "mt-2 h-[calc(100%-0.5rem)] border-b-2",
+                    (false, true) => "h-[calc(100%-0.5rem)]",
+                    (false, false) => "h-full",
+                  },
+                )} />
+              }
+            </div>
+          }) }
+        </div>
+      </td>
       <td>
         <input
           type="text"
@@ -140,6 +197,22 @@ pub fn row(props: &RowProps) -> Html {
           onfocus={props.on_focus.reform(|_| ())}
           onblur={props.on_blur.reform(|_| ())}
         />
+        if !props.readonly && !props.suggestions.is_empty() {
+          <div class="flex flex-wrap gap-1 px-[10px] pb-1 text-xs" title={t.suggestions_hint()}>
+            { for props.suggestions.iter().map(|suggestion| {
+              let on_change_sentence = props.on_change_sentence.clone();
+              let sentence = suggestion.clone();
+              html! {
+                <button
+                  class="px-1 rounded bg-slate-100 hover:bg-slate-200"
+                  onclick={Callback::from(move |_| on_change_sentence.emit(sentence.clone()))}
+                >
+                  {format!("↳ {}", suggestion)}
+                </button>
+              }
+            }) }
+          </div>
+        }
       </td>
       <td>
         <input
@@ -150,17 +223,34 @@ pub fn row(props: &RowProps) -> Html {
             is_rule_syntax_valid
               .then_some(CLS_SYNTAX_VALID)
               .unwrap_or(CLS_SYNTAX_INVALID),
-            (*is_rule_syntax_valid && props.is_derivation_valid)
-              .then_some(CLS_INPUT_VALID)
-              .unwrap_or(CLS_INPUT_INVALID),
+            if *is_rule_syntax_valid && props.is_derivation_valid { CLS_INPUT_VALID } else { CLS_INPUT_INVALID },
           )}
           value={props.derivation.clone()}
+          title={(!props.citation_errors.is_empty()).then(|| {
+            props.citation_errors.iter().map(|error| t.citation_error(error)).join("\n")
+          })}
           readonly={props.readonly}
           oninput={handle_derivation_input}
           onkeypress={handle_inputs_keypress}
           onfocus={props.on_focus.reform(|_| ())}
           onblur={props.on_blur.reform(|_| ())}
         />
+        if !props.readonly && !props.justifications.is_empty() {
+          <div class="flex flex-wrap gap-1 px-[10px] pb-1 text-xs" title={t.justifications_hint()}>
+            { for props.justifications.iter().map(|justification| {
+              let on_change_derivation = props.on_change_derivation.clone();
+              let derivation = justification.clone();
+              html! {
+                <button
+                  class="px-1 rounded bg-slate-100 hover:bg-slate-200"
+                  onclick={Callback::from(move |_| on_change_derivation.emit(derivation.clone()))}
+                >
+                  {format!("↳ {}", justification)}
+                </button>
+              }
+            }) }
+          </div>
+        }
       </td>
     </tr>
   }
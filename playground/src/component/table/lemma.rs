@@ -0,0 +1,48 @@
+use gloo_storage::{LocalStorage, Storage};
+use language_derivation_rule::{
+  derived::{DerivedRule, Registry},
+  rule_set::Profile,
+};
+use serde::{Deserialize, Serialize};
+
+use super::parser::parse_sequent;
+
+const LEMMAS_KEY: &str = "lemmas";
+
+/// 브라우저에 저장하는 보조정리. 식은 문자열로 저장합니다.
+#[derive(Serialize, Deserialize)]
+struct SavedLemma {
+  name: String,
+  sequent: String,
+  /// 도출이 성립하는 가장 약한 논리. 예전에 저장한 보조정리에는 없으므로 고전 논리로 봅니다.
+  #[serde(default)]
+  profile: Option<String>,
+}
+
+pub fn load() -> Registry {
+  let saved: Vec<SavedLemma> = LocalStorage::get(LEMMAS_KEY).unwrap_or_default();
+  saved
+    .into_iter()
+    .filter_map(|SavedLemma { name, sequent, profile }| {
+      let profile = Profile::ALL
+        .into_iter()
+        .find(|candidate| Some(candidate.name()) == profile.as_deref())
+        .unwrap_or(Profile::Classical);
+      parse_sequent(&sequent)
+        .ok()
+        .map(|sequent| DerivedRule { name, sequent, profile })
+    })
+    .collect()
+}
+
+pub fn save(lemmas: &Registry) {
+  let saved: Vec<SavedLemma> = lemmas
+    .iter()
+    .map(|rule| SavedLemma {
+      name: rule.name.clone(),
+      sequent: rule.sequent.to_string(),
+      profile: Some(rule.profile.name().to_owned()),
+    })
+    .collect();
+  let _ = LocalStorage::set(LEMMAS_KEY, saved);
+}
@@ -1,34 +1,93 @@
 mod component;
-mod parser;
+mod lemma;
+pub mod parser;
+mod spec;
 mod state;
+mod theorem;
 
 use std::collections::HashSet;
 
-use itertools::izip;
-use web_sys::HtmlInputElement;
+use itertools::{izip, Itertools};
+use web_sys::{Element, HtmlInputElement, HtmlSelectElement};
 use yew::{
-  classes, events::InputEvent, function_component, html, html_nested, use_reducer, virtual_dom::AttrValue, Callback,
-  Html, Properties, TargetCast,
+  classes,
+  events::{Event, InputEvent, KeyboardEvent},
+  function_component, html, html_nested, use_effect_with_deps, use_reducer,
+  virtual_dom::AttrValue,
+  Callback, Html, Properties, TargetCast,
 };
 
-pub use self::state::Row;
-use self::state::{Action, State};
+use self::component::row::{replace_in_place, ScopeBar};
+use self::state::{Action, ImportError, State};
+pub use self::state::{Row, RowDependency, Verdict};
+use crate::i18n::use_messages;
+use language::notation::replace_sequent_aliases;
+use language_derivation_rule::{
+  document::ProofDocument,
+  latex::Layout,
+  normalize::is_normal,
+  rule_set::{Profile, System},
+  scope,
+};
+
+/// 표 바깥에서 검증 결과를 볼 수 있도록 `on_change`로 전달하는 값입니다.
+#[derive(Clone, PartialEq)]
+pub struct Checked {
+  pub rows: Vec<Row>,
+  pub system: System,
+  pub deps_list: Vec<RowDependency>,
+  pub rule_validity_list: Vec<bool>,
+}
 
 #[derive(Properties, PartialEq)]
 pub struct TableProps {
   #[prop_or(None)]
   pub default_value: Option<Vec<Row>>,
+  /// 주어지면 `default_value` 대신 이 문서로 시작합니다.
+  #[prop_or(None)]
+  pub default_document: Option<ProofDocument>,
   #[prop_or(false)]
   pub readonly: bool,
+  /// 앞에서부터 이 개수만큼의 행은 수정하거나 사이에 행을 끼워 넣을 수 없습니다.
+  #[prop_or(0)]
+  pub locked: usize,
+  #[prop_or(Callback::noop())]
+  pub on_change: Callback<Checked>,
 }
 
 #[function_component(Table)]
 pub fn table(props: &TableProps) -> Html {
-  let state = use_reducer(|| match &props.default_value {
-    Some(rows) => State::init_from(rows.clone()),
-    None => State::init(),
+  let t = use_messages();
+  let state = use_reducer(|| {
+    let mut state = match (&props.default_document, &props.default_value) {
+      (Some(document), _) => State::init_from_document(document.clone()),
+      (None, Some(rows)) => State::init_from(rows.clone()),
+      (None, None) => State::init(),
+    };
+    state.locked = props.locked;
+    state
   });
 
+  {
+    let on_change = props.on_change.clone();
+    use_effect_with_deps(
+      move |(rows, system, deps_list, rule_validity_list)| {
+        on_change.emit(Checked {
+          rows: rows.clone(),
+          system: system.clone(),
+          deps_list: deps_list.clone(),
+          rule_validity_list: rule_validity_list.clone(),
+        });
+      },
+      (
+        state.rows.clone(),
+        state.system.clone(),
+        state.deps_list.clone(),
+        state.rule_vaildity_list.clone(),
+      ),
+    );
+  }
+
   let focus_deps = {
     let dep = match state.focused_idx {
       Some(idx) => state.deps_list.get(idx),
@@ -40,6 +99,21 @@ pub fn table(props: &TableProps) -> Html {
     }
   };
 
+  let scope_columns = scope::columns(&state.scopes);
+  let scope_width = scope_columns.iter().max().map_or(0, |column| column + 1);
+  let scope_bars = |num: usize| {
+    (0..scope_width)
+      .map(|column| {
+        izip!(state.scopes.iter(), scope_columns.iter())
+          .find(|(scope, &scope_column)| scope_column == column && scope.contains(num))
+          .map(|(scope, _)| ScopeBar {
+            is_start: scope.start == num,
+            is_end: scope.end == num,
+          })
+      })
+      .collect_vec()
+  };
+
   let handle_format = {
     let state = state.clone();
     Callback::from(move |()| state.dispatch(Action::Format))
@@ -53,6 +127,106 @@ pub fn table(props: &TableProps) -> Html {
     })
   };
 
+  let handle_change_sequent = {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+      let target: HtmlInputElement = e.target_unchecked_into();
+      let sequent = replace_in_place(&target, replace_sequent_aliases);
+      state.dispatch(Action::ChangeSequent { sequent })
+    })
+  };
+  let handle_sequent_keypress = {
+    let state = state.clone();
+    Callback::from(move |e: KeyboardEvent| {
+      if e.key() == "Enter" {
+        state.dispatch(Action::Format)
+      }
+    })
+  };
+
+  let handle_change_system = {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+      let target: HtmlSelectElement = e.target_unchecked_into();
+      if let Some(system) = systems(&state).into_iter().nth(target.selected_index() as usize) {
+        state.dispatch(Action::ChangeSystem { system })
+      }
+    })
+  };
+
+  let handle_change_profile = {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+      let target: HtmlSelectElement = e.target_unchecked_into();
+      if let Some(&profile) = Profile::ALL.get(target.selected_index() as usize) {
+        state.dispatch(Action::ChangeProfile { profile })
+      }
+    })
+  };
+
+  let handle_change_lemma_name = {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+      let target: HtmlInputElement = e.target_unchecked_into();
+      state.dispatch(Action::ChangeLemmaName { name: target.value() })
+    })
+  };
+  let handle_click_save_lemma = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::SaveLemma))
+  };
+
+  let handle_change_theorems_text = {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+      let target: HtmlInputElement = e.target_unchecked_into();
+      state.dispatch(Action::ChangeTheoremsText { text: target.value() })
+    })
+  };
+  let handle_click_load_theorems = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::LoadTheorems))
+  };
+
+  let handle_change_spec_text = {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+      let target: HtmlInputElement = e.target_unchecked_into();
+      state.dispatch(Action::ChangeSpecText { text: target.value() })
+    })
+  };
+  let handle_click_load_spec = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::LoadSpec))
+  };
+
+  let handle_toggle_tree = {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+      let target: Element = e.target_unchecked_into();
+      state.dispatch(Action::ToggleTree {
+        open: target.has_attribute("open"),
+      })
+    })
+  };
+  let handle_click_normalize = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::Normalize))
+  };
+
+  let handle_click_minimize = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::PreviewMinimize))
+  };
+  let handle_click_apply_minimize = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ApplyMinimize))
+  };
+  let handle_click_cancel_minimize = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::CancelMinimize))
+  };
+
   let handle_change_textbox = {
     let state = state.clone();
     Callback::from(move |e: InputEvent| {
@@ -68,6 +242,35 @@ pub fn table(props: &TableProps) -> Html {
     let state = state.clone();
     Callback::from(move |_| state.dispatch(Action::ImportFromTextbox))
   };
+  let handle_change_latex_layout = {
+    let state = state.clone();
+    Callback::from(move |e: Event| {
+      let target: HtmlSelectElement = e.target_unchecked_into();
+      if let Some(&layout) = Layout::ALL.get(target.selected_index() as usize) {
+        state.dispatch(Action::ChangeLatexLayout { layout })
+      }
+    })
+  };
+  let handle_click_export_latex = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ExportLatexToTextbox))
+  };
+  let handle_click_export_proof_file = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ExportProofFileToTextbox))
+  };
+  let handle_click_import_proof_file = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ImportProofFileFromTextbox))
+  };
+  let handle_click_export_fitch = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ExportFitchToTextbox))
+  };
+  let handle_click_import_fitch = {
+    let state = state.clone();
+    Callback::from(move |_| state.dispatch(Action::ImportFitchFromTextbox))
+  };
 
   let cls_button = classes!(
     "bg-slate-400",
@@ -81,13 +284,52 @@ pub fn table(props: &TableProps) -> Html {
   );
   html! {
     <div>
+      if !props.readonly {
+        <div class="flex items-center gap-2 mb-2 font-mono">
+          <select
+            class="shrink-0 px-2 py-[6px] bg-gray-100 border-b-2 focus:outline-none"
+            title={t.rule_system()}
+            onchange={handle_change_system}
+          >
+            { for systems(&state).into_iter().map(|system| html_nested! {
+              <option selected={system == state.system}>
+                { match system {
+                  System::Spec(_) => t.rule_spec(),
+                  _ => system.name(),
+                } }
+              </option>
+            }) }
+          </select>
+          <select
+            class="shrink-0 px-2 py-[6px] bg-gray-100 border-b-2 focus:outline-none"
+            title={t.logic()}
+            onchange={handle_change_profile}
+          >
+            { for Profile::ALL.iter().map(|profile| html_nested! {
+              <option selected={*profile == state.profile}>{t.profile(*profile)}</option>
+            }) }
+          </select>
+          <label class="font-bold shrink-0" for="sequent">{t.sequent()}</label>
+          <input
+            id="sequent"
+            type="text"
+            class="w-full px-[10px] py-[6px] bg-gray-100 border-b-2 focus:outline-none"
+            placeholder="P, (P → Q) ⊢ Q"
+            value={state.sequent.clone()}
+            oninput={handle_change_sequent}
+            onkeypress={handle_sequent_keypress}
+          />
+        </div>
+        <VerdictBanner verdict={state.verdict.clone()} />
+      }
       <table class="table-fixed font-mono not-prose h-fit">
         <thead>
           <tr class="[&>th]:p-[10px] border-b border-b-gray-400">
-            <th class="w-20">{"전제번호"}</th>
+            <th class="w-20">{t.column_dependencies()}</th>
             <th class="w-8 text-right">{"#"}</th>
-            <th class="">{"식"}</th>
-            <th class="w-36">{"도출규칙"}</th>
+            <th class="w-fit"></th>
+            <th class="">{t.column_sentence()}</th>
+            <th class="w-36">{t.column_rule()}</th>
           </tr>
         </thead>
         <tbody>
@@ -105,9 +347,15 @@ pub fn table(props: &TableProps) -> Html {
                 state.dispatch(Action::ChangeDerivation { num, derivation });
               })
             };
+            let is_locked = num <= props.locked;
             let handle_append_row = {
               let state = state.clone();
-              Callback::from(move |_| state.dispatch(Action::Add { after_num: num }))
+              let can_append = num >= props.locked;
+              Callback::from(move |_| {
+                if can_append {
+                  state.dispatch(Action::Add { after_num: num })
+                }
+              })
             };
             let handle_focus = {
               let state = state.clone();
@@ -125,13 +373,18 @@ pub fn table(props: &TableProps) -> Html {
                   state.focused_idx.map(|focused_idx| (num == (focused_idx + 1))
                     .then_some("[&>:nth-child(1)]:font-bold [&>:nth-child(1)]:text-black")),
                 )}
-                readonly={props.readonly}
+                readonly={props.readonly || is_locked}
+                system={state.system.clone()}
                 num={num}
                 dependents={dep.nums.clone()}
                 is_dependents_complete={dep.is_complete}
                 sentence={AttrValue::from(row.sentence.clone())}
                 derivation={AttrValue::from(row.derivation.clone())}
                 is_derivation_valid={*is_rule_valid}
+                scope_bars={scope_bars(num)}
+                citation_errors={state.citation_errors_list.get(idx).cloned().unwrap_or_default()}
+                suggestions={state.suggestions_list.get(idx).cloned().unwrap_or_default()}
+                justifications={state.justifications_list.get(idx).cloned().unwrap_or_default()}
                 on_change_sentence={handle_change_sentence}
                 on_change_derivation={handle_change_derivation}
                 on_format={handle_format.clone()}
@@ -144,9 +397,9 @@ pub fn table(props: &TableProps) -> Html {
         if !props.readonly {
           <tfoot>
             <tr>
-              <td colspan="4">
+              <td colspan="5">
                 <button class="w-full py-2 font-bold hover:bg-gray-100" onclick={handle_append_row_at_end}>
-                {"➕ 행 추가하기 (S-Enter)"}
+                {t.add_row()}
                 </button>
               </td>
             </tr>
@@ -154,17 +407,249 @@ pub fn table(props: &TableProps) -> Html {
         }
       </table>
       if !props.readonly {
+        if let Some(minimized) = &state.minimized {
+          <section class="bg-slate-100 p-4 mb-2 rounded-2xl text-sm">
+            <div class="font-bold mb-2">{t.minimize_preview()}</div>
+            <ul class="font-mono m-0 mb-2 list-none pl-0">
+              { for state.rows.iter().enumerate().map(|(idx, row)| {
+                let num = idx + 1;
+                match minimized.iter().position(|(old, _)| *old == num) {
+                  Some(new_idx) => {
+                    let new_row = &minimized[new_idx].1;
+                    html_nested! {
+                      <li>
+                        {format!("{} → {}  {}  ", num, new_idx + 1, row.sentence)}
+                        if new_row.derivation == row.derivation {
+                          {&row.derivation}
+                        } else {
+                          <span class="line-through text-gray-400">{&row.derivation}</span>
+                          {" "}<span class="text-green-700">{&new_row.derivation}</span>
+                        }
+                      </li>
+                    }
+                  }
+                  None => html_nested! {
+                    <li class="line-through text-red-500">{format!("{}  {}  {}", num, row.sentence, row.derivation)}</li>
+                  },
+                }
+              }) }
+            </ul>
+            <div class="flex">
+              <button class={classes!(cls_button.clone(), "rounded-l")} onclick={handle_click_apply_minimize}>{t.apply()}</button>
+              <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_cancel_minimize}>{t.cancel()}</button>
+            </div>
+          </section>
+        } else if !state.verdict.unused.is_empty() {
+          <div class="mb-2">
+            <button class={classes!(cls_button.clone(), "rounded")} onclick={handle_click_minimize}>
+              {t.minimize()}
+            </button>
+          </div>
+        }
+        <component::palette::Palette />
+        if state.system == System::Gentzen {
+          <details class="bg-slate-100 p-4 mb-2 rounded-2xl" open={state.tree_open} ontoggle={handle_toggle_tree}>
+            <summary class="font-bold cursor-pointer">{t.proof_tree()}</summary>
+            { match &state.tree {
+              Some(Ok(tree)) => html! {
+                <>
+                  <div class="overflow-x-auto my-2 p-2 bg-white">
+                    <component::proof_tree::ProofTree tree={tree.clone()} />
+                  </div>
+                  if !is_normal(tree) {
+                    <div class="mb-2">
+                      <button class={classes!(cls_button.clone(), "rounded")} title={t.normalize_hint()} onclick={handle_click_normalize}>
+                        {t.normalize()}
+                      </button>
+                    </div>
+                  }
+                  <p class="text-sm m-0 mb-2">{t.bussproofs_hint()}</p>
+                  { match tree.bussproofs() {
+                    Some(latex) => html! { <textarea class="font-mono text-xs" rows="8" readonly=true value={latex}></textarea> },
+                    None => html! { <div class="text-sm text-red-500">{t.bussproofs_too_wide()}</div> },
+                  } }
+                </>
+              },
+              Some(Err(error)) => html! { <div class="text-sm mt-2 text-red-500">{t.tree_error(error)}</div> },
+              None => html! {},
+            } }
+          </details>
+        }
+        if state.system == System::Gentzen {
+          <section class="flex flex-col bg-slate-100 p-4 mb-2 rounded-2xl">
+            <div class="font-bold mb-2">{t.lemmas()}</div>
+            <p class="text-sm m-0 mb-2">{t.lemmas_hint()}</p>
+            <ul class="font-mono text-sm m-0 mb-2">
+              { for state.lemmas.iter().map(|lemma| {
+                let handle_click_remove = {
+                  let state = state.clone();
+                  let name = lemma.name.clone();
+                  Callback::from(move |_| state.dispatch(Action::RemoveLemma { name: name.clone() }))
+                };
+                html_nested! {
+                  <li>
+                    {lemma.to_string()}
+                    if lemma.profile != Profile::Minimal {
+                      {format!(" ({})", t.profile(lemma.profile))}
+                    }
+                    {" "}
+                    <button class="text-red-500 hover:underline" onclick={handle_click_remove}>{t.remove()}</button>
+                  </li>
+                }
+              }) }
+            </ul>
+            <div class="flex">
+              <input
+                type="text"
+                class="w-40 px-2 font-mono"
+                placeholder={t.lemma_name_placeholder()}
+                value={state.lemma_name.clone()}
+                oninput={handle_change_lemma_name}
+              />
+              <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_save_lemma}>
+                {t.save_lemma()}
+              </button>
+            </div>
+            { match &state.lemma_result {
+              Some(Ok(lemma)) => html! { <div class="text-sm mt-1">{t.lemma_saved(&lemma.to_string())}</div> },
+              Some(Err(error)) => html! { <div class="text-sm mt-1 text-red-500">{t.derive_error(error)}</div> },
+              None => html! {},
+            } }
+          </section>
+          <section class="flex flex-col bg-slate-100 p-4 mb-2 rounded-2xl">
+            <div class="flex justify-between items-start mb-2">
+              <div class="font-bold">{t.theorems()}</div>
+              <button class={classes!(cls_button.clone(), "rounded")} onclick={handle_click_load_theorems}>{t.import()}</button>
+            </div>
+            <p class="text-sm m-0 mb-2">{t.theorems_hint()}</p>
+            <ul class="font-mono text-sm m-0 mb-2">
+              { for state.theorems.iter().map(|theorem| html_nested! {
+                <li>
+                  {format!("{}: {}", theorem.name, theorem.sequent)}
+                  if theorem.profile != Profile::Minimal {
+                    {format!(" ({})", t.profile(theorem.profile))}
+                  }
+                </li>
+              }) }
+            </ul>
+            <textarea class="font-mono text-xs" rows="8" value={state.theorems_text.clone()} oninput={handle_change_theorems_text}></textarea>
+            { match &state.theorems_result {
+              Some(Ok(())) => html! {
+                <div class="text-sm mt-1">{t.theorems_loaded(state.theorems.iter().count())}</div>
+              },
+              Some(Err(error)) => html! { <div class="text-sm mt-1 text-red-500">{t.library_error(error)}</div> },
+              None => html! {},
+            } }
+          </section>
+        }
+        <section class="flex flex-col bg-slate-100 p-4 mb-2 rounded-2xl">
+          <div class="flex justify-between items-start mb-2">
+            <div class="font-bold">{t.rule_spec()}</div>
+            <button class={classes!(cls_button.clone(), "rounded")} onclick={handle_click_load_spec}>{t.import()}</button>
+          </div>
+          <p class="text-sm m-0 mb-2">{t.rule_spec_hint()}</p>
+          <textarea class="font-mono text-xs" rows="8" value={state.spec_text.clone()} oninput={handle_change_spec_text}></textarea>
+          { match (&state.spec_result, &state.specs) {
+            (Some(Ok(())), Some(specs)) => html! {
+              <div class="text-sm mt-1">{t.rule_spec_loaded(specs.rules.len())}</div>
+            },
+            (Some(Err(error)), _) => html! { <div class="text-sm mt-1 text-red-500">{t.spec_error(error)}</div> },
+            _ => html! {},
+          } }
+        </section>
         <section class="flex flex-col bg-slate-100 p-4 rounded-2xl">
           <div class="flex justify-between items-start mb-2">
-            <div class="font-bold">{"텍스트로 내보내기 · 불러오기"}</div>
+            <div class="font-bold">{t.text_export_import()}</div>
             <div class="flex justify-end">
-              <button class={classes!(cls_button.clone(), "rounded-l")} onclick={handle_click_export}>{"내보내기"}</button>
-              <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_import}>{"불러오기"}</button>
+              <button class={classes!(cls_button.clone(), "rounded-l")} onclick={handle_click_export}>{t.export()}</button>
+              <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_import}>{t.import()}</button>
+              <button class={classes!(cls_button.clone(), "rounded-l", "ml-2")} onclick={handle_click_export_proof_file}>
+                {t.export_proof_file()}
+              </button>
+              <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_import_proof_file}>
+                {t.import_proof_file()}
+              </button>
+              if state.system == System::Gentzen {
+                <button class={classes!(cls_button.clone(), "rounded-l", "ml-2")} onclick={handle_click_export_fitch}>
+                  {t.export_fitch()}
+                </button>
+                <button class={classes!(cls_button.clone(), "rounded-r")} onclick={handle_click_import_fitch}>{t.import_fitch()}</button>
+                <select class="ml-2 px-2 bg-gray-100 border-b-2 focus:outline-none" title={t.latex_layout()} onchange={handle_change_latex_layout}>
+                  { for Layout::ALL.iter().map(|layout| html_nested! {
+                    <option selected={*layout == state.latex_layout}>{layout.name()}</option>
+                  }) }
+                </select>
+              }
+              <button
+                class={classes!(cls_button.clone(), "rounded-r", (state.system != System::Gentzen).then_some("rounded-l ml-2"))}
+                onclick={handle_click_export_latex}
+              >
+                {t.export_latex()}
+              </button>
             </div>
           </div>
           <textarea class="font-mono text-xs" rows="4" value={state.textbox.clone()} oninput={handle_change_textbox}></textarea>
+          { match &state.import_error {
+            Some(ImportError::Fitch(error)) => html! { <div class="text-sm mt-1 text-red-500">{t.fitch_error(error)}</div> },
+            Some(ImportError::ProofFile(error)) => html! {
+              <div class="text-sm mt-1 text-red-500">{t.proof_file_error(error)}</div>
+            },
+            Some(ImportError::Locked) => html! { <div class="text-sm mt-1 text-red-500">{t.import_locked()}</div> },
+            None => html! {},
+          } }
         </section>
       }
     </div>
   }
 }
+
+/// 도출 체계 선택 상자의 항목. 규칙 명세를 불러왔으면 맨 뒤에 둡니다.
+fn systems(state: &State) -> Vec<System> {
+  System::ALL
+    .iter()
+    .cloned()
+    .chain(state.specs.clone().map(System::Spec))
+    .collect()
+}
+
+#[derive(Properties, PartialEq)]
+struct VerdictBannerProps {
+  verdict: Verdict,
+}
+
+#[function_component(VerdictBanner)]
+fn verdict_banner(props: &VerdictBannerProps) -> Html {
+  let t = use_messages();
+  let Verdict {
+    sequent,
+    profile,
+    is_proved,
+    undischarged,
+    unused,
+    invalid,
+  } = &props.verdict;
+  let join = |nums: &Vec<usize>| nums.iter().join(", ");
+
+  let (cls, title) = match sequent {
+    None => ("bg-slate-100", t.verdict_undeclared().to_owned()),
+    Some(Err(())) => ("bg-red-100", t.verdict_syntax_error().to_owned()),
+    Some(Ok(sequent)) if *is_proved => ("bg-green-100", t.verdict_proved(&sequent.to_string(), *profile)),
+    Some(Ok(sequent)) => ("bg-amber-100", t.verdict_unproved(&sequent.to_string(), *profile)),
+  };
+  html! {
+    <section class={classes!("px-4", "py-2", "mb-2", "rounded", "not-prose", "text-sm", cls)}>
+      <div class="font-bold">{title}</div>
+      <ul class="list-disc pl-5">
+        if !invalid.is_empty() {
+          <li>{t.verdict_invalid(&join(invalid))}</li>
+        }
+        if !undischarged.is_empty() {
+          <li>{t.verdict_undischarged(&join(undischarged))}</li>
+        }
+        if !unused.is_empty() {
+          <li>{t.verdict_unused(&join(unused))}</li>
+        }
+      </ul>
+    </section>
+  }
+}
@@ -1,6 +1,8 @@
 use cached::proc_macro::cached;
-use language::{ast::exp::Exp, parser::expression::exp as parse_exp_base};
-use language_derivation_rule::{ast::rule::Rule, parser::rule::rule as parse_rule_base};
+use language::{
+  ast::{exp::Exp, sequent::Sequent},
+  parser::{expression::exp as parse_exp_base, sequent::sequent as parse_sequent_base},
+};
 
 #[cached(size = 64, key = "String", convert = r#"{ format!("{}", s) }"#)]
 pub fn parse_exp(s: &str) -> Result<Exp, ()> {
@@ -10,10 +12,10 @@ pub fn parse_exp(s: &str) -> Result<Exp, ()> {
   }
 }
 
-#[cached(size = 64, key = "String", convert = r#"{ format!("{}", s) }"#)]
-pub fn parse_rule(s: &str) -> Result<Rule, ()> {
-  match parse_rule_base(s.trim()) {
-    Ok(("", rule)) => Ok(rule),
+#[cached(size = 8, key = "String", convert = r#"{ format!("{}", s) }"#)]
+pub fn parse_sequent(s: &str) -> Result<Sequent, ()> {
+  match parse_sequent_base(s.trim()) {
+    Ok(("", sequent)) => Ok(sequent),
     Ok(_) | Err(_) => Err(()),
   }
 }
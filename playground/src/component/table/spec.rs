@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use gloo_storage::{LocalStorage, Storage};
+use language_derivation_rule::spec::{SpecSet, GENTZEN};
+
+const SPEC_KEY: &str = "spec";
+
+/// 브라우저에 저장한 규칙 명세의 원문. 저장한 적이 없으면 기본 명세입니다.
+pub fn load_text() -> String {
+  LocalStorage::get(SPEC_KEY).unwrap_or_else(|_| GENTZEN.to_owned())
+}
+
+/// 저장한 규칙 명세. 읽을 수 없으면 없습니다.
+pub fn load() -> Option<Arc<SpecSet>> {
+  load_text().parse().ok().map(Arc::new)
+}
+
+pub fn save(text: &str) {
+  let _ = LocalStorage::set(SPEC_KEY, text);
+}
@@ -1,27 +1,92 @@
-use std::{
-  collections::{BTreeSet, HashSet},
-  ops::{BitOr, Sub},
-};
+use std::sync::Arc;
 
-use super::parser::{parse_exp, parse_rule};
-use itertools::Itertools;
-use language::ast::exp::Exp;
-use language_derivation_rule::ast::rule::Rule;
+use super::{
+  lemma,
+  parser::{parse_exp, parse_sequent},
+  spec, theorem,
+};
+use itertools::{izip, Itertools};
+use language::ast::{exp::Exp, sequent::Sequent};
+pub use language_derivation_rule::dependency::RowDependency;
+use language_derivation_rule::{
+  citation::CitationError,
+  derived::{DeriveError, DerivedRule, Registry},
+  document::{DocumentRow, ProofDocument},
+  fitch::{self, FitchError},
+  latex::{self, Layout, TableRow},
+  normalize::normalize,
+  proof_file::{Entry, ProofFile, ProofFileError},
+  rule_set::{parse_rule, Gentzen, Profile, System},
+  scope::Scope,
+  spec::{SpecError, SpecSet},
+  theorem::{Library, LibraryError},
+  tree::{Tree, TreeError},
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use yew::Reducible;
 
+#[derive(Clone)]
 pub struct State {
   // source of truth
   pub rows: Vec<Row>,
   pub focused_idx: Option<usize>,
+  /// 앞에서부터 이 개수만큼의 행은 잠겨 있어, 불러오기로도 바꿀 수 없습니다.
+  pub locked: usize,
+  pub sequent: String,
+  pub system: System,
+  pub profile: Profile,
+  /// 불러온 문서의 제목. 내보낼 때 그대로 적습니다.
+  pub title: Option<String>,
+  /// 불러온 문서의 작성자. 내보낼 때 그대로 적습니다.
+  pub author: Option<String>,
+  /// 이 브라우저에 저장한 보조정리. `Gentzen` 체계에서 도출규칙으로 인용할 수 있습니다.
+  pub lemmas: Registry,
+  /// 이 브라우저에 저장한 정리 목록. `Gentzen` 체계에서 `TI`와 `SI`로 인용할 수 있습니다.
+  pub theorems: Library,
+  /// 이 브라우저에 저장한 규칙 명세. 불러오면 도출 체계로 고를 수 있습니다.
+  pub specs: Option<Arc<SpecSet>>,
 
   pub textbox: String,
+  /// LaTeX로 내보낼 때의 모양. Fitch 모양은 `Gentzen` 체계에서만 고를 수 있습니다.
+  pub latex_layout: Layout,
+  /// 마지막으로 텍스트 상자에서 불러오다 난 오류
+  pub import_error: Option<ImportError>,
+  pub lemma_name: String,
+  /// 마지막으로 보조정리를 저장한 결과
+  pub lemma_result: Option<Result<DerivedRule, DeriveError>>,
+  pub theorems_text: String,
+  /// 마지막으로 정리 목록을 불러온 결과
+  pub theorems_result: Option<Result<(), LibraryError>>,
+  pub spec_text: String,
+  /// 마지막으로 규칙 명세를 불러온 결과
+  pub spec_result: Option<Result<(), SpecError>>,
 
   // computed properties (memoized)
   pub deps_list: Vec<RowDependency>,
+  pub scopes: Vec<Scope>,
+  pub citation_errors_list: Vec<Vec<CitationError>>,
   pub rule_vaildity_list: Vec<bool>,
+  /// 식이 비어 있는 행에 도출규칙을 적용하여 얻는 식들. 채워 넣도록 제안합니다.
+  pub suggestions_list: Vec<Vec<String>>,
+  /// 도출규칙이 비어 있거나 올바르지 않은 행의 식을 뒷받침하는 도출규칙들. 바꿔 쓰도록 제안합니다.
+  pub justifications_list: Vec<Vec<String>>,
+  /// 쓰이지 않은 행을 지운 결과. 원래 행 번호와 바뀐 행의 쌍이며, 적용하기 전에 미리 보여줍니다.
+  pub minimized: Option<Vec<(usize, Row)>>,
+  /// 도출을 겐첸식 증명 나무로 바꾼 결과. `Gentzen` 체계에서 증명 나무를 펼쳤을 때만 만듭니다.
+  pub tree: Option<Result<Tree, TreeError>>,
+  pub tree_open: bool,
+  pub verdict: Verdict,
+}
+
+/// 텍스트 상자에서 불러오다 난 오류
+#[derive(Clone)]
+pub enum ImportError {
+  Fitch(FitchError),
+  ProofFile(ProofFileError),
+  /// 불러온 행이 잠긴 행과 다릅니다.
+  Locked,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -30,59 +95,177 @@ pub struct Row {
   pub sentence: String,
   #[serde(alias = "d")]
   pub derivation: String,
+  /// 불러온 문서나 `.proof` 파일에 적혀 있던 주석. 표에는 보이지 않고, 내보낼 때 다시 적습니다.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub comment: Option<String>,
 }
 
 pub enum Action {
   Add { after_num: usize },
   ChangeSentence { num: usize, sentence: String },
   ChangeDerivation { num: usize, derivation: String },
+  ChangeSequent { sequent: String },
+  ChangeSystem { system: System },
+  ChangeProfile { profile: Profile },
+  ChangeLemmaName { name: String },
+  SaveLemma,
+  RemoveLemma { name: String },
+  ChangeTheoremsText { text: String },
+  LoadTheorems,
+  ChangeSpecText { text: String },
+  LoadSpec,
   Format,
+  PreviewMinimize,
+  ApplyMinimize,
+  CancelMinimize,
+  Normalize,
+  ToggleTree { open: bool },
   ChangeFocus { idx: Option<usize> },
   ChangeTextbox { value: String },
   ImportFromTextbox,
+  ImportFitchFromTextbox,
+  ExportFitchToTextbox,
+  ImportProofFileFromTextbox,
+  ExportProofFileToTextbox,
+  ChangeLatexLayout { layout: Layout },
+  ExportLatexToTextbox,
   ExportToTextbox,
 }
 
 impl State {
   pub fn init() -> Self {
-    State {
-      rows: vec![Row {
-        sentence: "".to_owned(),
-        derivation: "".to_owned(),
-      }],
-      focused_idx: None,
-      textbox: "".to_owned(),
-      deps_list: vec![RowDependency::new_incomplete()],
-      rule_vaildity_list: vec![false],
-    }
+    State::init_from(vec![Row {
+      sentence: "".to_owned(),
+      derivation: "".to_owned(),
+      comment: None,
+    }])
   }
 
   pub fn init_from(rows: Vec<Row>) -> Self {
     let mut state = State {
       rows,
       focused_idx: None,
+      locked: 0,
+      sequent: "".to_owned(),
+      system: System::default(),
+      profile: Profile::default(),
+      title: None,
+      author: None,
+      lemmas: lemma::load(),
+      theorems: theorem::load(),
+      specs: spec::load(),
       textbox: "".to_owned(),
+      latex_layout: Layout::default(),
+      import_error: None,
+      lemma_name: "".to_owned(),
+      lemma_result: None,
+      theorems_text: theorem::load_text(),
+      theorems_result: None,
+      spec_text: spec::load_text(),
+      spec_result: None,
       deps_list: vec![],
+      scopes: vec![],
+      citation_errors_list: vec![],
       rule_vaildity_list: vec![],
+      suggestions_list: vec![],
+      justifications_list: vec![],
+      minimized: None,
+      tree: None,
+      tree_open: false,
+      verdict: Verdict::default(),
     };
     state.reload_computed_properties();
     state
   }
+
+  /// 문서의 행, 도출 체계, 논증과 메타데이터로 시작합니다.
+  pub fn init_from_document(document: ProofDocument) -> Self {
+    let mut state = State::init_from(document.rows.into_iter().map(Row::from).collect());
+    state.load_document_metadata(document.rule_system, document.sequent, document.title, document.author);
+    state
+  }
+
+  fn load_document_metadata(
+    &mut self,
+    system: System,
+    sequent: Option<String>,
+    title: Option<String>,
+    author: Option<String>,
+  ) {
+    if let System::Spec(specs) = &system {
+      self.specs = Some(specs.clone());
+    }
+    self.system = system;
+    self.sequent = sequent.unwrap_or_default();
+    self.title = title;
+    self.author = author;
+    self.reload_computed_properties();
+  }
+
+  /// 가정은 새 체계의 표기로 바꿔 둡니다. 다른 규칙은 체계마다 달라 그대로 둡니다.
+  fn change_system(&mut self, system: System) {
+    for row in self.rows.iter_mut() {
+      if self.system.is_assumption(&row.derivation) {
+        row.derivation = system.assumption();
+      }
+    }
+    self.system = system;
+    self.reload_computed_properties();
+  }
+
+  /// 잠긴 행을 그대로 두는 행 목록인지 확인합니다. 식은 읽은 결과로, 도출규칙은 앞뒤 공백을 빼고 비교합니다.
+  fn keeps_locked_rows(&self, rows: &[Row]) -> bool {
+    rows.len() >= self.locked
+      && izip!(&self.rows[..self.locked], rows).all(|(locked, row)| {
+        parse_exp(&locked.sentence) == parse_exp(&row.sentence) && locked.derivation.trim() == row.derivation.trim()
+      })
+  }
+
+  fn document(&self) -> ProofDocument {
+    let sequent = self.sequent.trim();
+    ProofDocument {
+      title: self.title.clone(),
+      author: self.author.clone(),
+      sequent: (!sequent.is_empty()).then(|| sequent.to_owned()),
+      ..ProofDocument::new(
+        self.system.clone(),
+        self.rows.iter().cloned().map(DocumentRow::from).collect(),
+      )
+    }
+  }
+}
+
+impl From<DocumentRow> for Row {
+  fn from(row: DocumentRow) -> Self {
+    Row {
+      sentence: row.sentence,
+      derivation: row.derivation,
+      comment: row.comment,
+    }
+  }
+}
+
+impl From<Row> for DocumentRow {
+  fn from(row: Row) -> Self {
+    DocumentRow {
+      sentence: row.sentence,
+      derivation: row.derivation,
+      comment: row.comment,
+    }
+  }
 }
 
 impl Reducible for State {
   type Action = Action;
 
   fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
-    // NOTE: state에서 computed properties 갱신이 필요한지 확인해야 함
+    let mut next = (*self).clone();
     match action {
       Action::Add { after_num } => {
-        let mut rows = self.rows.clone();
-
         lazy_static! {
           static ref NUM: Regex = Regex::new(r"\d+").unwrap();
         }
-        for row in rows.iter_mut() {
+        for row in next.rows.iter_mut() {
           let mut derivation = row.derivation.clone();
 
           let targets = NUM
@@ -99,582 +282,440 @@ impl Reducible for State {
           row.derivation = derivation;
         }
 
-        rows.insert(
+        next.rows.insert(
           after_num,
           Row {
             sentence: "".to_owned(),
             derivation: "".to_owned(),
+            comment: None,
           },
         );
-
-        let mut next = State {
-          rows,
-          focused_idx: self.focused_idx,
-          textbox: self.textbox.clone(),
-          deps_list: vec![],
-          rule_vaildity_list: vec![],
-        };
-        next.deps_list = next.get_deps_for_rows();
-        next.rule_vaildity_list = next.get_rules_validity();
-        next.into()
+        next.reload_computed_properties();
       }
 
       Action::ChangeSentence { num, sentence } => {
-        let mut rows = self.rows.clone();
-        if let Some(row) = rows.get_mut(num - 1) {
+        if let Some(row) = next.rows.get_mut(num - 1) {
           row.sentence = sentence;
         }
-
-        let mut next = State {
-          rows,
-          focused_idx: self.focused_idx,
-          textbox: self.textbox.clone(),
-          deps_list: self.deps_list.clone(),
-          rule_vaildity_list: vec![],
-        };
-        next.rule_vaildity_list = next.get_rules_validity();
-        next.into()
+        next.reload_computed_properties();
       }
 
       Action::ChangeDerivation { num, derivation } => {
-        let mut rows = self.rows.clone();
-        if let Some(row) = rows.get_mut(num - 1) {
+        if let Some(row) = next.rows.get_mut(num - 1) {
           row.derivation = derivation;
         }
-
-        let mut next = State {
-          rows,
-          focused_idx: self.focused_idx,
-          textbox: self.textbox.clone(),
-          deps_list: vec![],
-          rule_vaildity_list: vec![],
-        };
-        next.deps_list = next.get_deps_for_rows();
-        next.rule_vaildity_list = next.get_rules_validity();
-        next.into()
+        next.reload_computed_properties();
       }
 
-      Action::Format => {
-        let rows = self
-          .rows
-          .iter()
-          .map(|row| {
-            let mut row = row.clone();
-            if let Ok(exp) = parse_exp(&row.sentence) {
-              row.sentence = exp.to_string();
-            }
-            if let Ok(rule) = parse_rule(&row.derivation) {
-              row.derivation = rule.to_string();
-            }
-            row
-          })
-          .collect();
-        State {
-          rows,
-          focused_idx: self.focused_idx,
-          textbox: self.textbox.clone(),
-          deps_list: self.deps_list.clone(),
-          rule_vaildity_list: self.rule_vaildity_list.clone(),
-        }
-        .into()
+      Action::ChangeSequent { sequent } => {
+        next.sequent = sequent;
+        next.verdict = next.get_verdict();
       }
 
-      Action::ChangeFocus { idx } => State {
-        rows: self.rows.clone(),
-        focused_idx: idx,
-        textbox: self.textbox.clone(),
-        deps_list: self.deps_list.clone(),
-        rule_vaildity_list: self.rule_vaildity_list.clone(),
-      }
-      .into(),
-
-      Action::ChangeTextbox { value } => State {
-        rows: self.rows.clone(),
-        focused_idx: self.focused_idx,
-        textbox: value,
-        deps_list: self.deps_list.clone(),
-        rule_vaildity_list: self.rule_vaildity_list.clone(),
-      }
-      .into(),
-
-      Action::ImportFromTextbox => serde_yaml::from_str::<Vec<Row>>(&self.textbox)
-        .map(State::init_from)
-        .map(|s| State {
-          textbox: self.textbox.clone(),
-          ..s
-        })
-        .map(|s| s.into())
-        .unwrap_or(self),
-
-      Action::ExportToTextbox => State {
-        rows: self.rows.clone(),
-        focused_idx: self.focused_idx,
-        textbox: serde_yaml::to_string(&self.rows).unwrap_or_else(|_| self.textbox.clone()),
-        deps_list: self.deps_list.clone(),
-        rule_vaildity_list: self.rule_vaildity_list.clone(),
+      Action::ChangeSystem { system } => next.change_system(system),
+
+      Action::ChangeProfile { profile } => {
+        next.profile = profile;
+        next.reload_computed_properties();
       }
-      .into(),
-    }
-  }
-}
 
-impl State {
-  pub fn reload_computed_properties(&mut self) {
-    self.deps_list = self.get_deps_for_rows();
-    self.rule_vaildity_list = self.get_rules_validity();
-  }
+      Action::ChangeLemmaName { name } => next.lemma_name = name,
 
-  pub fn get_deps_for_rows(&self) -> Vec<RowDependency> {
-    fn iton(idx: usize) -> usize {
-      idx + 1
-    }
-    fn ntoi(num: usize) -> usize {
-      num - 1
-    }
-    self.rows.iter().enumerate().fold(vec![], |mut acc, (row_idx, row)| {
-      let row_num = iton(row_idx);
-      // 필요하다면 parse_rule에 lru 캐시를 적용할 수 있을 것
-      let dep = match parse_rule(&row.derivation) {
-        Ok(rule) => match &rule {
-          Rule::Premise => RowDependency::init_from([row_num]),
-          Rule::AndIntro(k, l) => RowDependency::new() | acc.get(ntoi(*k)) | acc.get(ntoi(*l)),
-          Rule::AndExclude(k) => RowDependency::new() | acc.get(ntoi(*k)),
-          Rule::OrIntro(k, l) => RowDependency::new() | acc.get(ntoi(*k)) | l.and_then(|l| acc.get(l + 1)),
-          Rule::OrExclude(k, (l0, l1), (m0, m1)) => {
-            (RowDependency::new() | acc.get(ntoi(*k)) | acc.get(ntoi(*l1)) | acc.get(ntoi(*m1))) - (*l0) - (*m0)
-          }
-          Rule::IfIntro((k0, k1)) => (RowDependency::new() | acc.get(ntoi(*k1))) - *k0,
-          Rule::IfExclude(k, l) => RowDependency::new() | acc.get(ntoi(*k)) | acc.get(ntoi(*l)),
-          Rule::Falsum(k) => RowDependency::new() | acc.get(ntoi(*k)),
-          Rule::NegIntro((k0, k1)) | Rule::NegExclude((k0, k1)) => (RowDependency::new() | acc.get(ntoi(*k1))) - *k0,
-          Rule::IffIntro(k, l) => RowDependency::new() | acc.get(ntoi(*k)) | acc.get(ntoi(*l)),
-          Rule::IffExclude(k) => RowDependency::new() | acc.get(ntoi(*k)),
-          Rule::UnivQuntIntro(k) | Rule::UnivQuntExclude(k) => RowDependency::new() | acc.get(ntoi(*k)),
-          Rule::ExisQuntIntro(k) => RowDependency::new() | acc.get(ntoi(*k)),
-          Rule::ExisQuntExclude(k, (l0, l1)) => (RowDependency::new() | acc.get(ntoi(*k)) | acc.get(ntoi(*l1))) - *l0,
-        },
-        Err(_) => RowDependency::new_incomplete(),
-      };
-      acc.push(dep);
-      acc
-    })
-  }
+      Action::SaveLemma => {
+        let rows = self.parsed_rows();
+        let result = DerivedRule::from_derivation(self.lemma_name.trim(), &rows, &self.gentzen());
+        if let Ok(rule) = &result {
+          next.lemmas.insert(rule.clone());
+          lemma::save(&next.lemmas);
+          next.lemma_name = "".to_owned();
+          next.reload_computed_properties();
+        }
+        next.lemma_result = Some(result);
+      }
 
-  /// NOTE: `self.deps_list`에 의존합니다. `get_deps_for_rows`를 부르도록 하는 게 더 나을지도
-  /// 모릅니다.
-  fn get_rules_validity(&self) -> Vec<bool> {
-    fn ntoi(num: usize) -> usize {
-      num - 1
-    }
-    fn one_or_none(set: &BTreeSet<String>) -> Option<String> {
-      if set.len() == 1 {
-        set.iter().next().map(|e| e.clone())
-      } else {
-        None
+      Action::RemoveLemma { name } => {
+        next.lemmas.remove(&name);
+        lemma::save(&next.lemmas);
+        next.reload_computed_properties();
       }
-    }
-    fn unordered_tuple_eq((a1, a2): (&Exp, &Exp), (b1, b2): (&Exp, &Exp)) -> bool {
-      ((a1 == b1) && (a2 == b2)) || ((a1 == b2) && (a2 == b1))
-    }
 
-    let items = self
-      .rows
-      .iter()
-      .map(|row| match parse_exp(&row.sentence) {
-        Ok(exp) => (Ok(exp), &row.derivation),
-        Err(_) => (Err(()), &row.derivation),
-      })
-      .map(|(exp, derivation)| {
-        (
-          exp,
-          match parse_rule(derivation) {
-            Ok(rule) => (Ok(rule)),
-            Err(_) => (Err(())),
-          },
-        )
-      })
-      .collect_vec();
+      Action::ChangeTheoremsText { text } => next.theorems_text = text,
 
-    items
-      .iter()
-      .map(|(exp_row, rule_row)| {
-        let (exp_row, rule_row) = match (exp_row, rule_row) {
-          (Ok(exp_row), Ok(rule_row)) => (exp_row, rule_row),
-          _ => return false,
-        };
+      Action::LoadTheorems => {
+        let result = self.theorems_text.parse::<Library>();
+        if let Ok(theorems) = &result {
+          next.theorems = theorems.clone();
+          theorem::save(&self.theorems_text);
+          next.reload_computed_properties();
+        }
+        next.theorems_result = Some(result.map(|_| ()));
+      }
 
-        match *rule_row {
-          Rule::Premise => true,
+      Action::ChangeSpecText { text } => next.spec_text = text,
 
-          Rule::AndIntro(k, l) => {
-            let (exp_k, exp_l) = match (items.get(ntoi(k)), items.get(ntoi(l))) {
-              (Some((Ok(exp_k), _)), Some((Ok(exp_l), _))) => (exp_k, exp_l),
-              _ => return false,
-            };
-            match exp_row {
-              Exp::And { lhs, rhs } => (**lhs == *exp_k) && (**rhs == *exp_l),
-              _ => false,
-            }
-          }
+      // 불러온 명세를 바로 도출 체계로 씁니다.
+      Action::LoadSpec => {
+        let result = self.spec_text.parse::<SpecSet>();
+        if let Ok(specs) = &result {
+          let specs = Arc::new(specs.clone());
+          spec::save(&self.spec_text);
+          next.specs = Some(specs.clone());
+          next.change_system(System::Spec(specs));
+        }
+        next.spec_result = Some(result.map(|_| ()));
+      }
 
-          Rule::AndExclude(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            match exp_k {
-              Exp::And {
-                lhs: exp_k_lhs,
-                rhs: exp_k_rhs,
-              } => (*exp_row == **exp_k_lhs) || (*exp_row == **exp_k_rhs),
-              _ => false,
-            }
+      Action::Format => {
+        for row in next.rows.iter_mut() {
+          if let Ok(exp) = parse_exp(&row.sentence) {
+            row.sentence = exp.to_string();
           }
-
-          Rule::OrIntro(k, None) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            match exp_row {
-              Exp::Or { lhs, rhs } => (*exp_k == **lhs) || (*exp_k == **rhs),
-              _ => false,
-            }
+          if let Some(rule) = self.system.format_rule(&row.derivation) {
+            row.derivation = rule;
           }
+        }
+        if let Ok(sequent) = parse_sequent(&self.sequent) {
+          next.sequent = sequent.to_string();
+        }
+      }
 
-          Rule::OrIntro(k, Some(l)) => {
-            let (exp_k, exp_l) = match (items.get(ntoi(k)), items.get(ntoi(l))) {
-              (Some((Ok(exp_k), _)), Some((Ok(exp_l), _))) => (exp_k, exp_l),
-              _ => return false,
+      Action::PreviewMinimize => {
+        let derivations = self.rows.iter().map(|row| row.derivation.as_str()).collect_vec();
+        let minimized = self
+          .system
+          .minimize(&derivations)
+          .into_iter()
+          .map(|(num, derivation)| {
+            let row = Row {
+              derivation,
+              ..self.rows[num - 1].clone()
             };
-            match exp_row {
-              Exp::Or { lhs, rhs } => unordered_tuple_eq((exp_k, exp_l), (lhs, rhs)),
-              _ => false,
-            }
-          }
+            (num, row)
+          })
+          .collect();
+        next.minimized = Some(minimized);
+      }
 
-          Rule::OrExclude(k, (l0, l1), (m0, m1)) => {
-            let (exp_k, exp_l0, rule_l0, exp_l1, exp_m0, rule_m0, exp_m1) = match (
-              items.get(ntoi(k)),
-              items.get(ntoi(l0)),
-              items.get(ntoi(l1)),
-              items.get(ntoi(m0)),
-              items.get(ntoi(m1)),
-            ) {
-              (
-                Some((Ok(exp_k), _)),
-                Some((Ok(exp_l0), Ok(rule_l0))),
-                Some((Ok(exp_l1), _)),
-                Some((Ok(exp_m0), Ok(rule_m0))),
-                Some((Ok(exp_m1), _)),
-              ) => (exp_k, exp_l0, rule_l0, exp_l1, exp_m0, rule_m0, exp_m1),
-              _ => return false,
-            };
-            match (exp_k, rule_l0, rule_m0) {
-              (
-                Exp::Or {
-                  lhs: exp_k_lhs,
-                  rhs: exp_k_rhs,
-                },
-                Rule::Premise,
-                Rule::Premise,
-              ) => {
-                unordered_tuple_eq((exp_k_lhs, exp_k_rhs), (exp_l0, exp_m0))
-                  && (exp_row == exp_l1)
-                  && (exp_row == exp_m1)
-              }
-              _ => false,
-            }
-          }
+      Action::ApplyMinimize => {
+        if let Some(minimized) = next.minimized.take() {
+          next.rows = minimized.into_iter().map(|(_, row)| row).collect();
+          next.focused_idx = None;
+          next.reload_computed_properties();
+        }
+      }
 
-          Rule::IfIntro((Some(k0), k1)) => {
-            let (exp_k0, rule_k0, exp_k1) = match (items.get(ntoi(k0)), items.get(ntoi(k1))) {
-              (Some((Ok(exp_k0), Ok(rule_k0))), Some((Ok(exp_k1), _))) => (exp_k0, rule_k0, exp_k1),
-              _ => return false,
-            };
-            match (exp_row, rule_k0) {
-              (Exp::Cond { antecedent, consequent }, Rule::Premise) => {
-                (*exp_k0 == **antecedent) && (*exp_k1 == **consequent)
+      Action::CancelMinimize => next.minimized = None,
+
+      // 증명 나무의 우회를 모두 없앤 도출로 바꿉니다. 잠긴 행이 바뀌면 그대로 둡니다.
+      Action::Normalize => {
+        if let Some(Ok(tree)) = &self.tree {
+          let rows = normalize(tree)
+            .lines()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, line)| {
+              let sentence = line.exp.map(|exp| exp.to_string()).unwrap_or_default();
+              let derivation = line.rule.map(|rule| rule.to_string()).unwrap_or_default();
+              // 문장과 규칙이 그대로인 행은 주석도 그대로 둡니다.
+              let comment = self
+                .rows
+                .get(idx)
+                .filter(|row| row.sentence.trim() == sentence && row.derivation.trim() == derivation)
+                .and_then(|row| row.comment.clone());
+              Row {
+                sentence,
+                derivation,
+                comment,
               }
-              _ => false,
-            }
+            })
+            .collect_vec();
+          if self.keeps_locked_rows(&rows) {
+            next.rows = rows;
+            next.focused_idx = None;
+            next.reload_computed_properties();
           }
+        }
+      }
 
-          Rule::IfIntro((None, k)) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            match exp_row {
-              Exp::Cond { consequent, .. } => *exp_k == **consequent,
-              _ => false,
-            }
-          }
+      Action::ToggleTree { open } => {
+        next.tree_open = open;
+        next.tree = next.build_tree();
+      }
 
-          Rule::IfExclude(k, l) => {
-            let (exp_k, exp_l) = match (items.get(ntoi(k)), items.get(ntoi(l))) {
-              (Some((Ok(exp_k), _)), Some((Ok(exp_l), _))) => (exp_k, exp_l),
-              _ => return false,
-            };
-            match (exp_row, exp_k) {
-              (Exp::Falsum, _) => (exp_k.negated() == *exp_l) || (*exp_k == exp_l.negated()),
-              (
-                _,
-                Exp::Cond {
-                  antecedent: exp_k_antecedent,
-                  consequent: exp_k_consequent,
-                },
-              ) => (**exp_k_antecedent == *exp_l) && (**exp_k_consequent == *exp_row),
-              _ => false,
-            }
-          }
+      Action::ChangeFocus { idx } => next.focused_idx = idx,
 
-          Rule::IffIntro(k, l) => {
-            let (exp_k, exp_l) = match (items.get(ntoi(k)), items.get(ntoi(l))) {
-              (Some((Ok(exp_k), _)), Some((Ok(exp_l), _))) => (exp_k, exp_l),
-              _ => return false,
-            };
-            match (exp_row, exp_k, exp_l) {
-              (
-                Exp::Iff { lhs, rhs },
-                Exp::Cond {
-                  antecedent: exp_k_antecedent,
-                  consequent: exp_k_consequent,
-                },
-                Exp::Cond {
-                  antecedent: exp_l_antecedent,
-                  consequent: exp_l_consequent,
-                },
-              ) => {
-                unordered_tuple_eq((lhs, rhs), (exp_k_antecedent, exp_k_consequent))
-                  && (*exp_k_antecedent == *exp_l_consequent)
-                  && (*exp_k_consequent == *exp_l_antecedent)
-              }
-              _ => false,
-            }
-          }
+      Action::ChangeTextbox { value } => next.textbox = value,
 
-          Rule::IffExclude(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            match (exp_row, exp_k) {
-              (
-                Exp::Cond { antecedent, consequent },
-                Exp::Iff {
-                  lhs: exp_k_lhs,
-                  rhs: exp_k_rhs,
-                },
-              ) => unordered_tuple_eq((antecedent, consequent), (exp_k_lhs, exp_k_rhs)),
-              _ => false,
-            }
+      // 예전의 행 목록도 문서로 읽습니다.
+      Action::ImportFromTextbox => match serde_yaml::from_str::<ProofDocument>(&self.textbox) {
+        Ok(document) => {
+          let rows = document.rows.into_iter().map(Row::from).collect_vec();
+          if !self.keeps_locked_rows(&rows) {
+            next.import_error = Some(ImportError::Locked);
+            return next.into();
           }
-
-          Rule::Falsum(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            *exp_k == Exp::Falsum
+          next.rows = rows;
+          next.focused_idx = None;
+          next.import_error = None;
+          next.load_document_metadata(document.rule_system, document.sequent, document.title, document.author);
+        }
+        Err(_) => return self,
+      },
+
+      Action::ImportFitchFromTextbox => match fitch::parse(&self.textbox) {
+        Ok(lines) => {
+          let rows = lines
+            .into_iter()
+            .map(|(exp, rule)| Row {
+              sentence: exp.to_string(),
+              derivation: rule.to_string(),
+              comment: None,
+            })
+            .collect_vec();
+          if !self.keeps_locked_rows(&rows) {
+            next.import_error = Some(ImportError::Locked);
+            return next.into();
           }
+          next.rows = rows;
+          next.system = System::Gentzen;
+          next.focused_idx = None;
+          next.import_error = None;
+          next.reload_computed_properties();
+        }
+        Err(error) => next.import_error = Some(ImportError::Fitch(error)),
+      },
 
-          Rule::NegIntro((k0, k1)) => {
-            let (exp_k0, exp_k1) = match (items.get(ntoi(k0)), items.get(ntoi(k1))) {
-              (Some((Ok(exp_k0), _)), Some((Ok(exp_k1), _))) => (exp_k0, exp_k1),
-              _ => return false,
-            };
-            match (exp_row, exp_k1) {
-              (Exp::Neg(negated), Exp::Falsum) => **negated == *exp_k0,
-              _ => false,
-            }
-          }
+      // 모든 행의 식과 도출규칙을 읽을 수 있을 때만 내보냅니다.
+      Action::ExportFitchToTextbox => {
+        let gentzen = self.gentzen();
+        let lines: Option<Vec<_>> = self
+          .rows
+          .iter()
+          .map(|row| Some((parse_exp(&row.sentence).ok()?, parse_rule(&gentzen, &row.derivation)?)))
+          .collect();
+        if let (System::Gentzen, Some(lines)) = (&self.system, lines) {
+          next.textbox = fitch::write(&lines);
+          next.import_error = None;
+        }
+      }
 
-          Rule::NegExclude((k0, k1)) => {
-            let (exp_k0, exp_k1) = match (items.get(ntoi(k0)), items.get(ntoi(k1))) {
-              (Some((Ok(exp_k0), _)), Some((Ok(exp_k1), _))) => (exp_k0, exp_k1),
-              _ => return false,
-            };
-            match (exp_row, exp_k0, exp_k1) {
-              (exp_row, Exp::Neg(exp_k_negated), Exp::Falsum) => *exp_row == **exp_k_negated,
-              _ => false,
-            }
+      Action::ImportProofFileFromTextbox => match self.textbox.parse::<ProofFile>() {
+        Ok(file) => {
+          // 주석만 있는 줄은 표에 행이 없어 버립니다.
+          let rows = file
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+              Entry::Row { exp, rule, comment } => Some(Row {
+                sentence: exp.to_string(),
+                derivation: rule.to_owned(),
+                comment: comment.clone(),
+              }),
+              Entry::Comment(_) => None,
+            })
+            .collect_vec();
+          if !self.keeps_locked_rows(&rows) {
+            next.import_error = Some(ImportError::Locked);
+            return next.into();
           }
-
-          Rule::UnivQuntIntro(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
+          next.rows = rows;
+          if let Some(conclusion) = file.conclusion {
+            let sequent = Sequent {
+              premises: file.premises.unwrap_or_default(),
+              conclusion,
             };
-            match exp_row {
-              Exp::UnivGenr { variable, form: inner } => {
-                let beta = match one_or_none(&(&exp_k.free_variables() - &inner.free_variables())) {
-                  Some(beta) => beta,
-                  None => return false,
-                };
-                let deps = match self.deps_list.get(ntoi(k)) {
-                  Some(deps) => deps,
-                  None => return false,
-                };
-                if deps.nums.iter().any(|&num_dep| {
-                  let exp_dep = match items.get(ntoi(num_dep)) {
-                    Some((Ok(exp_dep), _)) => exp_dep,
-                    _ => return true,
-                  };
-                  exp_dep.free_variables().contains(&beta)
-                }) {
-                  return false;
-                }
-                inner.var_replaced(&variable, &beta) == *exp_k
-              }
-              _ => false,
-            }
+            next.sequent = sequent.to_string();
           }
+          next.focused_idx = None;
+          next.import_error = None;
+          next.reload_computed_properties();
+        }
+        Err(error) => next.import_error = Some(ImportError::ProofFile(error)),
+      },
 
-          Rule::UnivQuntExclude(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-
-            match exp_k {
-              Exp::UnivGenr { variable: alpha, form } => {
-                let beta = match one_or_none(&(&exp_row.free_variables() - &form.free_variables())) {
-                  Some(beta) => beta,
-                  None => return false,
-                };
-                form.var_replaced(&alpha, &beta) == *exp_row
-              }
-              _ => false,
-            }
+      // 모든 행의 식을 읽을 수 있을 때만 내보냅니다. 논증을 선언했으면 머리말에 적습니다.
+      Action::ExportProofFileToTextbox => {
+        let entries: Option<Vec<Entry>> = self
+          .rows
+          .iter()
+          .map(|row| {
+            Some(Entry::Row {
+              exp: parse_exp(&row.sentence).ok()?,
+              rule: row.derivation.trim().to_owned(),
+              comment: row.comment.clone(),
+            })
+          })
+          .collect();
+        let sequent = parse_sequent(self.sequent.trim()).ok();
+        if let Some(entries) = entries {
+          next.textbox = ProofFile {
+            premises: sequent.as_ref().map(|sequent| sequent.premises.clone()),
+            conclusion: sequent.map(|sequent| sequent.conclusion),
+            entries,
           }
+          .to_string();
+          next.import_error = None;
+        }
+      }
 
-          Rule::ExisQuntIntro(k) => {
-            let exp_k = match items.get(ntoi(k)) {
-              Some((Ok(exp_k), _)) => exp_k,
-              _ => return false,
-            };
-            match exp_row {
-              Exp::ExistGenr { variable: alpha, form } => {
-                let beta = match one_or_none(&(&exp_k.free_variables() - &form.free_variables())) {
-                  Some(beta) => beta,
-                  None => return false,
-                };
-                form.var_replaced(&alpha, &beta) == *exp_k
-              }
-              _ => false,
-            }
-          }
+      Action::ChangeLatexLayout { layout } => next.latex_layout = layout,
 
-          Rule::ExisQuntExclude(k, (l, m)) => {
-            let (exp_k, exp_l, rule_l, exp_m) = match (items.get(ntoi(k)), items.get(ntoi(l)), items.get(ntoi(m))) {
-              (Some((Ok(exp_k), _)), Some((Ok(exp_l), Ok(rule_l))), Some((Ok(exp_m), _))) => {
-                (exp_k, exp_l, rule_l, exp_m)
-              }
-              _ => return false,
-            };
-            match (exp_k, rule_l) {
-              (Exp::ExistGenr { variable: alpha, form }, Rule::Premise) => {
-                let beta =
-                  match one_or_none(&(&(&exp_l.free_variables() - &form.free_variables()) - &exp_m.free_variables())) {
-                    Some(beta) => beta,
-                    None => return false,
-                  };
-                let deps = match self.deps_list.get(ntoi(m)) {
-                  Some(deps) => deps,
-                  _ => return false,
-                };
-                if deps.nums.iter().filter(|&n| *n != l).any(|&num_dep| {
-                  let exp_dep = match items.get(ntoi(num_dep)) {
-                    Some((Ok(exp_dep), _)) => exp_dep,
-                    _ => return true,
-                  };
-                  exp_dep.free_variables().contains(&beta)
-                }) {
-                  return false;
-                }
-                (form.var_replaced(&alpha, &beta) == *exp_l) && (exp_m == exp_row)
-              }
-              _ => false,
-            }
-          }
+      Action::ExportLatexToTextbox => {
+        let rows = izip!(&self.rows, &self.deps_list)
+          .map(|(row, deps)| TableRow {
+            deps: deps.nums.iter().copied().collect(),
+            sentence: row.sentence.clone(),
+            rule: row.derivation.clone(),
+          })
+          .collect_vec();
+        let layout = match self.system {
+          System::Gentzen => self.latex_layout,
+          System::Lemmon | System::Spec(_) => Layout::Tabular,
+        };
+        next.textbox = latex::export(&rows, layout);
+      }
+
+      Action::ExportToTextbox => {
+        if let Ok(textbox) = serde_yaml::to_string(&self.document()) {
+          next.textbox = textbox;
         }
-      })
-      .collect_vec()
+      }
+    }
+    next.into()
   }
 }
 
-#[derive(Clone)]
-pub struct RowDependency {
-  pub is_complete: bool,
-  pub nums: HashSet<usize>,
-}
+impl State {
+  fn parsed_rows(&self) -> Vec<(Option<Exp>, &str)> {
+    self
+      .rows
+      .iter()
+      .map(|row| (parse_exp(&row.sentence).ok(), row.derivation.as_str()))
+      .collect_vec()
+  }
 
-impl RowDependency {
-  fn new() -> Self {
-    RowDependency {
-      is_complete: true,
-      nums: HashSet::new(),
+  fn gentzen(&self) -> Gentzen {
+    Gentzen {
+      lemmas: self.lemmas.clone(),
+      theorems: self.theorems.clone(),
+      profile: self.profile,
     }
   }
 
-  fn new_incomplete() -> Self {
-    RowDependency {
-      is_complete: false,
-      nums: HashSet::new(),
+  /// 증명 나무는 행이 많으면 만드는 데 오래 걸려, 펼쳐 볼 때만 만듭니다.
+  fn build_tree(&self) -> Option<Result<Tree, TreeError>> {
+    match self.tree_open {
+      true => self
+        .system
+        .tree(&self.parsed_rows(), &self.lemmas, &self.theorems, self.profile),
+      false => None,
     }
   }
 
-  fn init_from<const N: usize>(nums: [usize; N]) -> Self {
-    RowDependency {
-      is_complete: true,
-      nums: HashSet::from(nums),
-    }
+  pub fn reload_computed_properties(&mut self) {
+    self.minimized = None;
+    let rows = self.parsed_rows();
+    let report = self.system.check(&rows, &self.lemmas, &self.theorems, self.profile);
+    let suggestions_list = (1..=rows.len())
+      .map(|num| match self.rows[num - 1].sentence.trim() {
+        "" => self
+          .system
+          .apply(&rows, num, &self.lemmas, &self.theorems)
+          .iter()
+          .map(|licensed| licensed.to_string())
+          .collect(),
+        _ => vec![],
+      })
+      .collect();
+    let justifications_list = (1..=rows.len())
+      .map(|num| match (&rows[num - 1].0, report.validity[num - 1]) {
+        (Some(_), false) => self
+          .system
+          .justify(&rows, num, &self.lemmas, &self.theorems, self.profile)
+          .into_iter()
+          .take(3)
+          .collect(),
+        _ => vec![],
+      })
+      .collect();
+    self.deps_list = report.deps_list;
+    self.scopes = report.scopes;
+    self.citation_errors_list = report.citation_errors;
+    self.rule_vaildity_list = report.validity;
+    self.suggestions_list = suggestions_list;
+    self.justifications_list = justifications_list;
+    self.tree = self.build_tree();
+    self.verdict = self.get_verdict();
   }
 }
 
-impl BitOr<&RowDependency> for RowDependency {
-  type Output = RowDependency;
-
-  fn bitor(self, rhs: &RowDependency) -> Self::Output {
-    RowDependency {
-      is_complete: self.is_complete && rhs.is_complete,
-      nums: &self.nums | &rhs.nums,
+impl State {
+  /// 도출 전체가 선언한 논증을 증명하는지 판정합니다. `deps_list`와 `rule_vaildity_list`에 의존합니다.
+  pub fn get_verdict(&self) -> Verdict {
+    fn ntoi(num: usize) -> usize {
+      num - 1
     }
-  }
-}
 
-impl BitOr<Option<&RowDependency>> for RowDependency {
-  type Output = RowDependency;
+    let last_num = self.rows.len();
+    let sequent = match self.sequent.trim() {
+      "" => None,
+      sequent => Some(parse_sequent(sequent)),
+    };
 
-  fn bitor(self, rhs: Option<&RowDependency>) -> Self::Output {
-    match rhs {
-      Some(rhs) => self | rhs,
-      None => self,
-    }
-  }
-}
+    let invalid = (1..=last_num)
+      .filter(|&num| !self.rule_vaildity_list.get(ntoi(num)).copied().unwrap_or(false))
+      .collect_vec();
 
-impl Sub<usize> for RowDependency {
-  type Output = RowDependency;
+    let derivations = self.rows.iter().map(|row| row.derivation.as_str()).collect_vec();
+    let used = self.system.used(&derivations);
+    let unused = (1..last_num).filter(|num| !used.contains(num)).collect_vec();
+
+    let last_deps = self.deps_list.last();
+    let undischarged = last_deps
+      .map(|deps| deps.nums.iter().copied().sorted_unstable().collect_vec())
+      .unwrap_or_default()
+      .into_iter()
+      .filter(
+        |&num| match (&sequent, self.rows.get(ntoi(num)).map(|row| parse_exp(&row.sentence))) {
+          (Some(Ok(Sequent { premises, .. })), Some(Ok(exp))) => !premises.contains(&exp),
+          _ => true,
+        },
+      )
+      .collect_vec();
 
-  fn sub(self, rhs: usize) -> Self::Output {
-    let mut nums = self.nums.clone();
-    nums.remove(&rhs);
-    RowDependency {
-      is_complete: self.is_complete,
-      nums,
+    let is_proved = match (&sequent, self.rows.last().map(|row| parse_exp(&row.sentence))) {
+      (Some(Ok(Sequent { conclusion, .. })), Some(Ok(last))) => {
+        *conclusion == last
+          && invalid.is_empty()
+          && undischarged.is_empty()
+          && self.deps_list.iter().all(|deps| deps.is_complete)
+      }
+      _ => false,
+    };
+
+    Verdict {
+      sequent,
+      profile: self.profile,
+      is_proved,
+      undischarged,
+      unused,
+      invalid,
     }
   }
 }
 
-impl Sub<Option<usize>> for RowDependency {
-  type Output = RowDependency;
-
-  fn sub(self, rhs: Option<usize>) -> Self::Output {
-    match rhs {
-      Some(rhs) => self - rhs,
-      None => self,
-    }
-  }
+/// 도출 전체에 대한 판정 결과입니다.
+#[derive(Clone, PartialEq, Default)]
+pub struct Verdict {
+  /// 선언한 논증. 선언하지 않았으면 `None`입니다.
+  pub sequent: Option<Result<Sequent, ()>>,
+  /// 검사에 쓴 논리
+  pub profile: Profile,
+  pub is_proved: bool,
+  /// 마지막 행이 의존하지만 선언한 전제가 아닌 가정의 행 번호
+  pub undischarged: Vec<usize>,
+  /// 마지막 행이 (간접적으로도) 인용하지 않는 행 번호
+  pub unused: Vec<usize>,
+  /// 도출규칙이 올바르지 않은 행 번호
+  pub invalid: Vec<usize>,
 }
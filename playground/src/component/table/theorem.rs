@@ -0,0 +1,18 @@
+use gloo_storage::{LocalStorage, Storage};
+use language_derivation_rule::theorem::{Library, BASIC};
+
+const THEOREMS_KEY: &str = "theorems";
+
+/// 브라우저에 저장한 정리 목록의 원문. 저장한 적이 없으면 기본 정리 목록입니다.
+pub fn load_text() -> String {
+  LocalStorage::get(THEOREMS_KEY).unwrap_or_else(|_| BASIC.to_owned())
+}
+
+/// 저장한 정리 목록. 검증에 실패하면 비어 있습니다.
+pub fn load() -> Library {
+  load_text().parse().unwrap_or_default()
+}
+
+pub fn save(text: &str) {
+  let _ = LocalStorage::set(THEOREMS_KEY, text);
+}
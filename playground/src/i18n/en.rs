@@ -0,0 +1,468 @@
+use language_derivation_rule::{
+  citation::CitationError,
+  derived::DeriveError,
+  fitch::FitchError,
+  proof_file::{ProofFileError, ProofFileErrorKind},
+  rule_set::Profile,
+  spec::SpecError,
+  theorem::LibraryError,
+  tree::{TreeError, MAX_SIZE},
+};
+use yew::{html, Html};
+
+use super::Messages;
+
+pub struct En;
+
+impl Messages for En {
+  fn site_title(&self) -> &'static str {
+    "First-Order Logic Validator"
+  }
+  fn nav_validator(&self) -> &'static str {
+    "Validator"
+  }
+  fn nav_exercises(&self) -> &'static str {
+    "Exercises"
+  }
+  fn nav_help(&self) -> &'static str {
+    "About & Help"
+  }
+  fn language(&self) -> &'static str {
+    "Language"
+  }
+  fn page_not_found(&self) -> &'static str {
+    "Page Not Found"
+  }
+
+  fn shortcuts(&self) -> &'static str {
+    "Shortcuts"
+  }
+  fn shortcut_insert_row(&self) -> &'static str {
+    "Shift + Enter: insert a row below"
+  }
+  fn shortcut_format(&self) -> &'static str {
+    "Enter: format everything"
+  }
+  fn validation_errors(&self) -> &'static str {
+    "Validation errors"
+  }
+  fn legend_syntax(&self) -> &'static str {
+    "Any field: red wavy underline — syntax error"
+  }
+  fn legend_sentence(&self) -> &'static str {
+    "Sentence: red bottom border — syntax error"
+  }
+  fn legend_rule(&self) -> &'static str {
+    "Rule: red bottom border — syntax error or the rule's conditions are not met"
+  }
+  fn legend_dependencies(&self) -> &'static str {
+    "Dependencies: red bottom border — the dependencies could not be fully worked out"
+  }
+
+  fn rule_system(&self) -> &'static str {
+    "Rule system"
+  }
+  fn logic(&self) -> &'static str {
+    "Logic"
+  }
+  fn sequent(&self) -> &'static str {
+    "Sequent"
+  }
+  fn column_dependencies(&self) -> &'static str {
+    "Deps"
+  }
+  fn column_sentence(&self) -> &'static str {
+    "Sentence"
+  }
+  fn column_rule(&self) -> &'static str {
+    "Rule"
+  }
+  fn add_row(&self) -> &'static str {
+    "➕ Add a row (S-Enter)"
+  }
+  fn suggestions_hint(&self) -> &'static str {
+    "Fill in a sentence the rule yields. Replace φ and β with what you choose."
+  }
+  fn justifications_hint(&self) -> &'static str {
+    "Use a rule that justifies this sentence."
+  }
+  fn minimize(&self) -> &'static str {
+    "Remove unused lines"
+  }
+  fn minimize_preview(&self) -> &'static str {
+    "Preview of removing unused lines"
+  }
+  fn apply(&self) -> &'static str {
+    "Apply"
+  }
+  fn cancel(&self) -> &'static str {
+    "Cancel"
+  }
+  fn proof_tree(&self) -> &'static str {
+    "Proof tree"
+  }
+  fn normalize(&self) -> &'static str {
+    "Normalize"
+  }
+  fn normalize_hint(&self) -> &'static str {
+    "Replace the derivation with one where no introduction is immediately eliminated"
+  }
+  fn bussproofs_hint(&self) -> Html {
+    html! {
+      <>{"Copy the code below to typeset the tree with the LaTeX "}<code>{"bussproofs"}</code>{" package."}</>
+    }
+  }
+  fn bussproofs_too_wide(&self) -> &'static str {
+    "bussproofs cannot draw an inference with more than five premises"
+  }
+  fn lemmas(&self) -> &'static str {
+    "Lemmas"
+  }
+  fn lemmas_hint(&self) -> Html {
+    html! {
+      <>
+        {"Save a valid derivation under a name to use it as a rule in other derivations, citing a line for each premise as in "}
+        <code>{"3, 1 MT"}</code>
+        {". Any sentences may be substituted for the sentence letters (P, Q, …), as long as it is done consistently."}
+      </>
+    }
+  }
+  fn remove(&self) -> &'static str {
+    "Remove"
+  }
+  fn lemma_name_placeholder(&self) -> &'static str {
+    "Name (e.g. MT)"
+  }
+  fn save_lemma(&self) -> &'static str {
+    "Save this derivation"
+  }
+  fn lemma_saved(&self, lemma: &str) -> String {
+    format!("Saved: {}", lemma)
+  }
+  fn theorems(&self) -> &'static str {
+    "Theorems"
+  }
+  fn theorems_hint(&self) -> Html {
+    html! {
+      <>
+        {"Theorems written with their proofs can be cited as "}<code>{"TI (T4)"}</code>
+        {", and sequents with premises as "}<code>{"2, 3 SI (T5)"}</code>
+        {", citing a line for each premise. Each proof may only cite the theorems written before it. The logic in parentheses is the one the proof needs; the theorem cannot be cited in a weaker logic."}
+      </>
+    }
+  }
+  fn theorems_loaded(&self, count: usize) -> String {
+    match count {
+      1 => "Loaded 1 theorem".to_owned(),
+      _ => format!("Loaded {} theorems", count),
+    }
+  }
+  fn rule_spec(&self) -> &'static str {
+    "Rule spec"
+  }
+  fn rule_spec_hint(&self) -> Html {
+    html! {
+      <>
+        {"Write one rule per line, as in "}<code>{"&I: φ @k, ψ @l ⊢ (φ & ψ)"}</code>{", and the assumption as "}
+        <code>{"P: assumption"}</code>{". Once loaded, the spec can be chosen as the rule system. Quantifier rules cannot be written in a spec."}
+      </>
+    }
+  }
+  fn rule_spec_loaded(&self, count: usize) -> String {
+    match count {
+      1 => "Loaded 1 rule".to_owned(),
+      _ => format!("Loaded {} rules", count),
+    }
+  }
+  fn text_export_import(&self) -> &'static str {
+    "Export · import as text"
+  }
+  fn export(&self) -> &'static str {
+    "Export"
+  }
+  fn import(&self) -> &'static str {
+    "Import"
+  }
+  fn export_proof_file(&self) -> &'static str {
+    "Export .proof"
+  }
+  fn import_proof_file(&self) -> &'static str {
+    "Import .proof"
+  }
+  fn export_fitch(&self) -> &'static str {
+    "Export Fitch"
+  }
+  fn import_fitch(&self) -> &'static str {
+    "Import Fitch"
+  }
+  fn latex_layout(&self) -> &'static str {
+    "LaTeX layout"
+  }
+  fn export_latex(&self) -> &'static str {
+    "Export LaTeX"
+  }
+  fn import_locked(&self) -> &'static str {
+    "Cannot import a derivation that changes the locked premise rows"
+  }
+
+  fn verdict_undeclared(&self) -> &'static str {
+    "Declare a sequent to check whether the whole derivation proves it."
+  }
+  fn verdict_syntax_error(&self) -> &'static str {
+    "Syntax error in the sequent"
+  }
+  fn verdict_proved(&self, sequent: &str, profile: Profile) -> String {
+    format!("✅ {} is proved ({})", sequent, self.profile(profile))
+  }
+  fn verdict_unproved(&self, sequent: &str, profile: Profile) -> String {
+    format!("{} is not proved ({})", sequent, self.profile(profile))
+  }
+  fn verdict_invalid(&self, nums: &str) -> String {
+    format!("Invalid lines: {}", nums)
+  }
+  fn verdict_undischarged(&self, nums: &str) -> String {
+    format!("Open assumptions that are not premises: {}", nums)
+  }
+  fn verdict_unused(&self, nums: &str) -> String {
+    format!("Unused lines: {}", nums)
+  }
+
+  fn profile(&self, profile: Profile) -> &'static str {
+    match profile {
+      Profile::Minimal => "minimal logic",
+      Profile::Intuitionistic => "intuitionistic logic",
+      Profile::Classical => "classical logic",
+    }
+  }
+  fn citation_error(&self, error: &CitationError) -> String {
+    match error {
+      CitationError::OutOfRange { cited } => format!("There is no line {}", cited),
+      CitationError::SelfCitation => "A line cannot cite itself".to_owned(),
+      CitationError::Forward { cited } => format!("Line {} comes later and cannot be cited", cited),
+      CitationError::InvalidRange { start, end } => format!("The range {}-{} ends before it starts", start, end),
+      CitationError::NotAnAssumption { discharged } => {
+        format!(
+          "Line {} is not an assumption (P), so it cannot start a range",
+          discharged
+        )
+      }
+      CitationError::ClosedScope { cited, scope } => format!(
+        "Line {} is inside the assumption's scope ({}-{}), which ends before this line",
+        cited, scope.start, scope.end
+      ),
+      CitationError::CrossesScope { start, end, assumption } => format!(
+        "The range {}-{} contains the assumption on line {} but not the end of its scope",
+        start, end, assumption
+      ),
+    }
+  }
+  fn derive_error(&self, error: &DeriveError) -> String {
+    match error {
+      DeriveError::InvalidName => {
+        "A name must be letters and digits starting with a letter, and cannot be P".to_owned()
+      }
+      DeriveError::Empty => "The derivation is empty".to_owned(),
+      DeriveError::InvalidLine { num } => format!("Line {} is invalid", num),
+      DeriveError::IncompleteDependency { num } => format!("The dependencies of line {} are incomplete", num),
+    }
+  }
+  fn tree_error(&self, error: &TreeError) -> String {
+    match error {
+      TreeError::Empty => "The derivation is empty".to_owned(),
+      TreeError::InvalidLine { num } => format!("Line {} is invalid", num),
+      TreeError::IncompleteDependency { num } => format!("The dependencies of line {} are incomplete", num),
+      TreeError::TooLarge => format!("The proof tree is too large to draw (more than {} nodes)", MAX_SIZE),
+    }
+  }
+  fn library_error(&self, error: &LibraryError) -> String {
+    match error {
+      LibraryError::Syntax { line } => format!("Line {}: syntax error", line),
+      LibraryError::DuplicateName { line, name } => format!("Line {}: {} already exists", line, name),
+      LibraryError::InvalidProof { line, name, error } => {
+        format!("Line {}: the proof of {} — {}", line, name, self.derive_error(error))
+      }
+      LibraryError::ProofMismatch { line, name, proven } => {
+        format!("Line {}: the proof of {} proves {} instead", line, name, proven)
+      }
+    }
+  }
+  fn spec_error(&self, error: &SpecError) -> String {
+    match error {
+      SpecError::Syntax { line } => format!("Line {}: syntax error", line),
+      SpecError::DuplicateLabel { line, label } => format!("Line {}: label {} is used twice", line, label),
+      SpecError::UnknownLabel { line, label } => format!("Line {}: label {} is not a premise", line, label),
+      SpecError::NotAnAssumption { line, label } => {
+        format!("Line {}: label {} is discharged but is not an assumption", line, label)
+      }
+      SpecError::MissingAssumption => "No assumption rule is defined".to_owned(),
+    }
+  }
+  fn fitch_error(&self, error: &FitchError) -> String {
+    match error {
+      FitchError::Syntax { line } => format!("Line {}: syntax error", line),
+      FitchError::Numbering { line, expected } => format!("Line {}: the line number should be {}", line, expected),
+      FitchError::Depth { line } => format!("Line {}: a subproof starts without an assumption (AS)", line),
+      FitchError::Range { line, start, end } => {
+        format!(
+          "Line {}: {}-{} is not a subproof this line can discharge",
+          line, start, end
+        )
+      }
+    }
+  }
+  fn proof_file_error(&self, error: &ProofFileError) -> String {
+    let reason = match error.kind {
+      ProofFileErrorKind::Sentence => "not a sentence".to_owned(),
+      ProofFileErrorKind::MissingRule => "the rule is missing".to_owned(),
+      ProofFileErrorKind::Numbering { expected } => format!("the line number should be {}", expected),
+      ProofFileErrorKind::Header => "each header line must appear once, before the rows".to_owned(),
+    };
+    format!("Line {}, column {}: {}", error.line, error.column, reason)
+  }
+
+  fn exercise_not_found(&self) -> &'static str {
+    "There is no such exercise."
+  }
+  fn exercise_list(&self) -> &'static str {
+    "All exercises"
+  }
+  fn exercise_instructions(&self, premises: Html, goal: Html) -> Html {
+    html! {
+      <>
+        {"Derive "}{goal}{" from the premises "}{premises}
+        {". The last line must be the goal, and its dependencies must be among the given premises."}
+      </>
+    }
+  }
+  fn exercise_solved(&self) -> &'static str {
+    "✅ Solved!"
+  }
+  fn exercise_solved_before(&self) -> &'static str {
+    "You have solved this exercise before."
+  }
+
+  fn help(&self) -> Html {
+    html! {
+      <>
+        <section>
+          <p>
+            {"This page checks, as you type, whether a derivation in the formal language ℒ follows Gentzen's rules of inference."}
+          </p>
+          <p>
+            {"It is based on the Symbolic Logic course at Korea University and its textbook, "}
+            <a href="https://product.kyobobook.co.kr/detail/S000000548655" target="_blank" rel="noopener noreferrer">{"the Korean translation"}</a>
+            {" of Benson Mates's "}<i>{"Elementary Logic"}</i>{" (tr. Kim Youngjung and Sunwoo Hwan, Moonye, 1995). "}
+            {"It was built while taking the Symbolic Logic and Programming Languages courses."}
+          </p>
+          <p>
+            {"The source code of the parser, the validator and this website is available at "}<a href="https://github.com/jangjunha/elementary-logic-validator" target="_blank" rel="noopener noreferrer">{"https://github.com/jangjunha/elementary-logic-validator"}</a>{"."}
+          </p>
+          <p>
+            {"Made by "}<a href="https://jangjunha.me/">{"jangjunha"}</a>{"."}
+          </p>
+        </section>
+
+        <section>
+          <h2>{"The language L"}</h2>
+          <p>{"TODO:"}</p>
+        </section>
+
+        <section>
+          <h2>{"Typing symbols"}</h2>
+          <p>{"Symbols that are not on the keyboard can be typed with aliases, which turn into the symbols as soon as they are typed. A "}<code>{"-"}</code>{" in a range of a rule (e.g. 3-5) is left as it is."}</p>
+          <p>{"On touch devices, the symbol buttons below the table insert a symbol at the cursor."}</p>
+          <table>
+            <thead>
+              <tr>
+                <th>{"Name"}</th>
+                <th>{"Symbol"}</th>
+                <th>{"Alias"}</th>
+              </tr>
+            </thead>
+            <tbody>
+              <tr>
+                <td>{"Conjunction"}</td>
+                <td><code>{"&"}</code></td>
+                <td></td>
+              </tr>
+              <tr>
+                <td>{"Disjunction"}</td>
+                <td><code>{"∨"}</code>{"(U+2228 Logical Or)"}</td>
+                <td>
+                  <code>{"|"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Negation"}</td>
+                <td><code>{"¬"}</code></td>
+                <td>
+                  <code>{"-"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Arrow"}</td>
+                <td><code>{"→"}</code></td>
+                <td>
+                  <code>{"->"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Double arrow"}</td>
+                <td><code>{"↔"}</code></td>
+                <td>
+                  <code>{"<->"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Existential quantifier"}</td>
+                <td><code>{"∃"}</code></td>
+                <td>
+                  <code>{"]"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Falsum"}</td>
+                <td><code>{"⊥"}</code></td>
+                <td>
+                  <code>{"\\bot"}</code>
+                </td>
+              </tr>
+            </tbody>
+          </table>
+        </section>
+
+        <section>
+          <h2>{"Rule systems"}</h2>
+          <p>{"The select box above the table chooses the rule system. The default is the Gentzen system described below."}</p>
+          <p>
+            {"The Lemmon system ("}<i>{"Beginning Logic"}</i>{") writes assumptions as "}<code>{"A"}</code>{" and uses "}
+            <code>{"MPP"}</code>{", "}<code>{"MTT"}</code>{", "}<code>{"DN"}</code>{", "}<code>{"CP"}</code>{", "}
+            <code>{"&I"}</code>{", "}<code>{"&E"}</code>{", "}<code>{"∨I"}</code>{", "}<code>{"∨E"}</code>{", "}
+            <code>{"RAA"}</code>{", "}<code>{"UI"}</code>{", "}<code>{"UG"}</code>{", "}<code>{"EI"}</code>{" and "}
+            <code>{"EG"}</code>{". A discharged assumption is cited by its line number instead of a range (e.g. "}
+            <code>{"2, 4 CP"}</code>{", "}<code>{"1, 2, 3, 4, 5 ∨E"}</code>{", "}<code>{"1, 2, 4 EI"}</code>{"). "}
+            {"A contradiction is written as "}<code>{"(B & ¬B)"}</code>{"."}
+          </p>
+          <p>
+            {"The select box next to it chooses the logic. Intuitionistic logic does without "}<code>{"¬E"}</code>
+            {" (removing a double negation with "}<code>{"DN"}</code>{" in the Lemmon system), and minimal logic does without the "}
+            <code>{"⊥"}</code>{" rule as well. Use them to see which theorems need classical logic."}
+          </p>
+          <p>
+            {"In the Gentzen system, open the "}<b>{"Proof tree"}</b>{" below the table to see a valid derivation as a Gentzen-style tree. "}
+            {"Discharged assumptions are put in brackets, and both they and the inference discharging them get the assumption's line number as a superscript. "}
+            {"A line cited more than once is drawn again wherever it is cited. The tree can also be exported as LaTeX "}<code>{"bussproofs"}</code>{" code. "}
+            {"If an introduction is immediately eliminated, "}<b>{"Normalize"}</b>{" replaces the derivation with one without such detours."}
+          </p>
+          <p>
+            {"Write propositional rules of your own in the "}<b>{"Rule spec"}</b>{" box below the table and load them to check derivations with them. "}
+            {"The spec is saved in this browser and written into exported documents."}
+          </p>
+        </section>
+      </>
+    }
+  }
+  fn help_rules(&self) -> &'static str {
+    "Rules of inference and the rule specification language:"
+  }
+}
@@ -0,0 +1,461 @@
+use language_derivation_rule::{
+  citation::CitationError,
+  derived::DeriveError,
+  fitch::FitchError,
+  proof_file::{ProofFileError, ProofFileErrorKind},
+  rule_set::Profile,
+  spec::SpecError,
+  theorem::LibraryError,
+  tree::{TreeError, MAX_SIZE},
+};
+use yew::{html, Html};
+
+use super::Messages;
+
+pub struct Ko;
+
+impl Messages for Ko {
+  fn site_title(&self) -> &'static str {
+    "1차논리 검증기"
+  }
+  fn nav_validator(&self) -> &'static str {
+    "검증기"
+  }
+  fn nav_exercises(&self) -> &'static str {
+    "연습문제"
+  }
+  fn nav_help(&self) -> &'static str {
+    "정보 및 도움말"
+  }
+  fn language(&self) -> &'static str {
+    "언어"
+  }
+  fn page_not_found(&self) -> &'static str {
+    "페이지를 찾을 수 없습니다"
+  }
+
+  fn shortcuts(&self) -> &'static str {
+    "단축키"
+  }
+  fn shortcut_insert_row(&self) -> &'static str {
+    "Shift + Enter : 아래에 행 삽입"
+  }
+  fn shortcut_format(&self) -> &'static str {
+    "Enter: 전체 포맷팅"
+  }
+  fn validation_errors(&self) -> &'static str {
+    "검증 오류"
+  }
+  fn legend_syntax(&self) -> &'static str {
+    "공통: 빨간 물결표 밑줄— 문법 오류"
+  }
+  fn legend_sentence(&self) -> &'static str {
+    "식: 상자 빨간 밑줄 — 문법 오류"
+  }
+  fn legend_rule(&self) -> &'static str {
+    "도출규칙: 상자 빨간 밑줄 — 문법 오류 또는 도출규칙 조건 위반"
+  }
+  fn legend_dependencies(&self) -> &'static str {
+    "전제번호: 상자 빨간 밑줄 — 불완전한 전제번호 도출 결과"
+  }
+
+  fn rule_system(&self) -> &'static str {
+    "도출규칙 체계"
+  }
+  fn logic(&self) -> &'static str {
+    "논리"
+  }
+  fn sequent(&self) -> &'static str {
+    "논증"
+  }
+  fn column_dependencies(&self) -> &'static str {
+    "전제번호"
+  }
+  fn column_sentence(&self) -> &'static str {
+    "식"
+  }
+  fn column_rule(&self) -> &'static str {
+    "도출규칙"
+  }
+  fn add_row(&self) -> &'static str {
+    "➕ 행 추가하기 (S-Enter)"
+  }
+  fn suggestions_hint(&self) -> &'static str {
+    "도출규칙으로부터 얻는 식으로 채웁니다. φ와 β는 골라서 바꿔 쓰세요."
+  }
+  fn justifications_hint(&self) -> &'static str {
+    "이 식을 뒷받침하는 도출규칙으로 바꿉니다."
+  }
+  fn minimize(&self) -> &'static str {
+    "쓰이지 않은 행 지우기"
+  }
+  fn minimize_preview(&self) -> &'static str {
+    "쓰이지 않은 행 지우기 미리 보기"
+  }
+  fn apply(&self) -> &'static str {
+    "적용"
+  }
+  fn cancel(&self) -> &'static str {
+    "취소"
+  }
+  fn proof_tree(&self) -> &'static str {
+    "증명 나무"
+  }
+  fn normalize(&self) -> &'static str {
+    "정규화"
+  }
+  fn normalize_hint(&self) -> &'static str {
+    "도입한 식을 곧바로 제거하는 우회를 모두 없앤 도출로 바꿉니다"
+  }
+  fn bussproofs_hint(&self) -> Html {
+    html! {
+      <>{"LaTeX "}<code>{"bussproofs"}</code>{" 패키지로 조판하려면 아래 코드를 복사하세요."}</>
+    }
+  }
+  fn bussproofs_too_wide(&self) -> &'static str {
+    "전제가 다섯 개보다 많은 추론은 bussproofs로 그릴 수 없습니다"
+  }
+  fn lemmas(&self) -> &'static str {
+    "보조정리"
+  }
+  fn lemmas_hint(&self) -> Html {
+    html! {
+      <>
+        {"올바른 도출을 이름을 붙여 저장하면 다른 도출에서 "}<code>{"3, 1 MT"}</code>
+        {"처럼 전제마다 행을 인용하여 도출규칙으로 쓸 수 있습니다. 문장 기호(P, Q, …)에는 어떤 식이든 일관되게 대입할 수 있습니다."}
+      </>
+    }
+  }
+  fn remove(&self) -> &'static str {
+    "삭제"
+  }
+  fn lemma_name_placeholder(&self) -> &'static str {
+    "이름 (예: MT)"
+  }
+  fn save_lemma(&self) -> &'static str {
+    "현재 도출을 저장"
+  }
+  fn lemma_saved(&self, lemma: &str) -> String {
+    format!("저장했습니다: {}", lemma)
+  }
+  fn theorems(&self) -> &'static str {
+    "정리 목록"
+  }
+  fn theorems_hint(&self) -> Html {
+    html! {
+      <>
+        {"증명과 함께 적은 정리는 "}<code>{"TI (T4)"}</code>{"로, 전제가 있는 논증은 "}<code>{"2, 3 SI (T5)"}</code>
+        {"처럼 전제마다 행을 인용하여 쓸 수 있습니다. 각 증명은 앞에 적은 정리만 인용할 수 있습니다. 괄호 안은 증명에 필요한 논리로, 더 약한 논리에서는 인용할 수 없습니다."}
+      </>
+    }
+  }
+  fn theorems_loaded(&self, count: usize) -> String {
+    format!("정리 {}개를 불러왔습니다", count)
+  }
+  fn rule_spec(&self) -> &'static str {
+    "규칙 명세"
+  }
+  fn rule_spec_hint(&self) -> Html {
+    html! {
+      <>
+        {"한 줄에 규칙 하나를 "}<code>{"&I: φ @k, ψ @l ⊢ (φ & ψ)"}</code>{"처럼 적고, 가정은 "}<code>{"P: assumption"}</code>
+        {"으로 적습니다. 불러오면 도출규칙 체계로 고를 수 있습니다. 명세로는 양화사 규칙을 적을 수 없습니다."}
+      </>
+    }
+  }
+  fn rule_spec_loaded(&self, count: usize) -> String {
+    format!("규칙 {}개를 불러왔습니다", count)
+  }
+  fn text_export_import(&self) -> &'static str {
+    "텍스트로 내보내기 · 불러오기"
+  }
+  fn export(&self) -> &'static str {
+    "내보내기"
+  }
+  fn import(&self) -> &'static str {
+    "불러오기"
+  }
+  fn export_proof_file(&self) -> &'static str {
+    ".proof로 내보내기"
+  }
+  fn import_proof_file(&self) -> &'static str {
+    ".proof 불러오기"
+  }
+  fn export_fitch(&self) -> &'static str {
+    "Fitch로 내보내기"
+  }
+  fn import_fitch(&self) -> &'static str {
+    "Fitch 불러오기"
+  }
+  fn latex_layout(&self) -> &'static str {
+    "LaTeX 모양"
+  }
+  fn export_latex(&self) -> &'static str {
+    "LaTeX로 내보내기"
+  }
+  fn import_locked(&self) -> &'static str {
+    "잠긴 전제 행을 바꾸는 도출은 불러올 수 없습니다"
+  }
+
+  fn verdict_undeclared(&self) -> &'static str {
+    "논증을 선언하면 도출 전체가 그 논증을 증명하는지 확인합니다."
+  }
+  fn verdict_syntax_error(&self) -> &'static str {
+    "논증 문법 오류"
+  }
+  fn verdict_proved(&self, sequent: &str, profile: Profile) -> String {
+    format!("✅ {} 증명 완료 ({})", sequent, self.profile(profile))
+  }
+  fn verdict_unproved(&self, sequent: &str, profile: Profile) -> String {
+    format!("{} 증명되지 않음 ({})", sequent, self.profile(profile))
+  }
+  fn verdict_invalid(&self, nums: &str) -> String {
+    format!("올바르지 않은 행: {}", nums)
+  }
+  fn verdict_undischarged(&self, nums: &str) -> String {
+    format!("전제가 아닌 열린 가정: {}", nums)
+  }
+  fn verdict_unused(&self, nums: &str) -> String {
+    format!("쓰이지 않은 행: {}", nums)
+  }
+
+  fn profile(&self, profile: Profile) -> &'static str {
+    match profile {
+      Profile::Minimal => "최소 논리",
+      Profile::Intuitionistic => "직관주의 논리",
+      Profile::Classical => "고전 논리",
+    }
+  }
+  fn citation_error(&self, error: &CitationError) -> String {
+    match error {
+      CitationError::OutOfRange { cited } => format!("{}번 행은 없습니다", cited),
+      CitationError::SelfCitation => "자기 자신을 인용할 수 없습니다".to_owned(),
+      CitationError::Forward { cited } => format!("뒤에 있는 {}번 행을 인용할 수 없습니다", cited),
+      CitationError::InvalidRange { start, end } => format!("범위 {}-{}의 끝이 시작보다 앞에 있습니다", start, end),
+      CitationError::NotAnAssumption { discharged } => {
+        format!(
+          "{}번 행은 가정(P)이 아니므로 범위의 시작으로 쓸 수 없습니다",
+          discharged
+        )
+      }
+      CitationError::ClosedScope { cited, scope } => format!(
+        "{}번 행은 이 행보다 앞에서 끝난 가정 범위({}-{}) 안에 있습니다",
+        cited, scope.start, scope.end
+      ),
+      CitationError::CrossesScope { start, end, assumption } => format!(
+        "범위 {}-{}에 {}번 행의 가정이 들어 있지만, 그 가정 범위의 끝은 들어 있지 않습니다",
+        start, end, assumption
+      ),
+    }
+  }
+  fn derive_error(&self, error: &DeriveError) -> String {
+    match error {
+      DeriveError::InvalidName => "이름은 영문자로 시작하는 영문자·숫자여야 하고 P일 수 없습니다".to_owned(),
+      DeriveError::Empty => "도출이 비어 있습니다".to_owned(),
+      DeriveError::InvalidLine { num } => format!("{}번 행이 올바르지 않습니다", num),
+      DeriveError::IncompleteDependency { num } => format!("{}번 행의 전제번호가 불완전합니다", num),
+    }
+  }
+  fn tree_error(&self, error: &TreeError) -> String {
+    match error {
+      TreeError::Empty => "도출이 비어 있습니다".to_owned(),
+      TreeError::InvalidLine { num } => format!("{}번 행이 올바르지 않습니다", num),
+      TreeError::IncompleteDependency { num } => format!("{}번 행의 전제번호가 불완전합니다", num),
+      TreeError::TooLarge => format!("증명 나무가 너무 커서 그릴 수 없습니다 (마디 {}개 초과)", MAX_SIZE),
+    }
+  }
+  fn library_error(&self, error: &LibraryError) -> String {
+    match error {
+      LibraryError::Syntax { line } => format!("{}번째 줄: 문법 오류", line),
+      LibraryError::DuplicateName { line, name } => format!("{}번째 줄: {}이(가) 이미 있습니다", line, name),
+      LibraryError::InvalidProof { line, name, error } => {
+        format!("{}번째 줄: {}의 증명 — {}", line, name, self.derive_error(error))
+      }
+      LibraryError::ProofMismatch { line, name, proven } => {
+        format!("{}번째 줄: {}의 증명은 {}을(를) 증명합니다", line, name, proven)
+      }
+    }
+  }
+  fn spec_error(&self, error: &SpecError) -> String {
+    match error {
+      SpecError::Syntax { line } => format!("{}번째 줄: 문법 오류", line),
+      SpecError::DuplicateLabel { line, label } => format!("{}번째 줄: 표지 {}을(를) 두 번 썼습니다", line, label),
+      SpecError::UnknownLabel { line, label } => format!("{}번째 줄: 표지 {}은(는) 전제가 아닙니다", line, label),
+      SpecError::NotAnAssumption { line, label } => {
+        format!(
+          "{}번째 줄: 표지 {}은(는) 가정이 아니어서 소거할 수 없습니다",
+          line, label
+        )
+      }
+      SpecError::MissingAssumption => "가정 규칙이 없습니다".to_owned(),
+    }
+  }
+  fn fitch_error(&self, error: &FitchError) -> String {
+    match error {
+      FitchError::Syntax { line } => format!("{}번째 줄: 문법 오류", line),
+      FitchError::Numbering { line, expected } => format!("{}번째 줄: 행 번호는 {}이어야 합니다", line, expected),
+      FitchError::Depth { line } => format!("{}번째 줄: 가정(AS) 없이 하위 증명이 시작됩니다", line),
+      FitchError::Range { line, start, end } => {
+        format!(
+          "{}번째 줄: {}-{}은(는) 이 행이 해소할 수 있는 하위 증명이 아닙니다",
+          line, start, end
+        )
+      }
+    }
+  }
+  fn proof_file_error(&self, error: &ProofFileError) -> String {
+    let reason = match error.kind {
+      ProofFileErrorKind::Sentence => "올바른 식이 아닙니다".to_owned(),
+      ProofFileErrorKind::MissingRule => "도출규칙이 없습니다".to_owned(),
+      ProofFileErrorKind::Numbering { expected } => format!("행 번호는 {}이어야 합니다", expected),
+      ProofFileErrorKind::Header => "머리말은 한 번씩, 행보다 앞에 적어야 합니다".to_owned(),
+    };
+    format!("{}번째 줄 {}번째 글자: {}", error.line, error.column, reason)
+  }
+
+  fn exercise_not_found(&self) -> &'static str {
+    "문제를 찾을 수 없습니다."
+  }
+  fn exercise_list(&self) -> &'static str {
+    "연습문제 목록"
+  }
+  fn exercise_instructions(&self, premises: Html, goal: Html) -> Html {
+    html! {
+      <>
+        {"전제 "}{premises}{"로부터 "}{goal}
+        {"을(를) 도출하세요. 마지막 행이 목표 식이고 그 전제번호가 주어진 전제들만으로 이루어져야 합니다."}
+      </>
+    }
+  }
+  fn exercise_solved(&self) -> &'static str {
+    "✅ 해결했습니다!"
+  }
+  fn exercise_solved_before(&self) -> &'static str {
+    "이전에 해결한 문제입니다."
+  }
+
+  fn help(&self) -> Html {
+    html! {
+      <>
+        <section>
+          <p>
+            {"형식언어 ℒ과 Gentzen의 추론 규칙에 따른 논증이 타당한지 실시간으로 검증하는 기능을 가진 웹페이지입니다."}
+          </p>
+          <p>
+            {"고려대학교 〈기호논리학〉 수업과 교재 "}
+            <a href="https://product.kyobobook.co.kr/detail/S000000548655" target="_blank" rel="noopener noreferrer">{"〈기호논리학〉"}</a>
+            {"(Benson Mates, 김영정·선우환 역, 문예출판사, 1995)를 바탕으로 합니다."}
+            {"〈기호논리학〉, 〈프로그래밍언어〉 수업을 듣고 실습해보면서 만든 사이트입니다."}
+          </p>
+          <p>
+            {"파서, 검증기 및 웹사이트 소스코드는 "}<a href="https://github.com/jangjunha/elementary-logic-validator" target="_blank" rel="noopener noreferrer">{"https://github.com/jangjunha/elementary-logic-validator"}</a>{"에 공개되어 있습니다."}
+          </p>
+          <p>
+            {"만든이는 "}<a href="https://jangjunha.me/">{"jangjunha"}</a>{"입니다."}
+          </p>
+        </section>
+
+        <section>
+          <h2>{"언어 L"}</h2>
+          <p>{"TODO:"}</p>
+        </section>
+
+        <section>
+          <h2>{"기호 입력 대체"}</h2>
+          <p>{"키보드로 입력할 수 없는 문자들을 대체 기호로 바꿔 입력할 수 있습니다. 대체 기호는 입력하는 즉시 대표 기호로 변환됩니다. 도출규칙의 범위(예: 3-5)에 쓰인 "}<code>{"-"}</code>{"는 변환되지 않습니다."}</p>
+          <p>{"터치 기기에서는 표 아래의 기호 버튼을 눌러 커서 위치에 대표 기호를 넣을 수 있습니다."}</p>
+          <table>
+            <thead>
+              <tr>
+                <th>{"이름"}</th>
+                <th>{"대표 기호"}</th>
+                <th>{"대체 기호"}</th>
+              </tr>
+            </thead>
+            <tbody>
+              <tr>
+                <td>{"연언"}</td>
+                <td><code>{"&"}</code></td>
+                <td></td>
+              </tr>
+              <tr>
+                <td>{"선언"}</td>
+                <td><code>{"∨"}</code>{"(U+2228 Logical Or)"}</td>
+                <td>
+                  <code>{"|"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"부정"}</td>
+                <td><code>{"¬"}</code></td>
+                <td>
+                  <code>{"-"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"화살표"}</td>
+                <td><code>{"→"}</code></td>
+                <td>
+                  <code>{"->"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"쌍화살표"}</td>
+                <td><code>{"↔"}</code></td>
+                <td>
+                  <code>{"<->"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"존재양화사"}</td>
+                <td><code>{"∃"}</code></td>
+                <td>
+                  <code>{"]"}</code>
+                </td>
+              </tr>
+              <tr>
+                <td>{"Falsum"}</td>
+                <td><code>{"⊥"}</code></td>
+                <td>
+                  <code>{"\\bot"}</code>
+                </td>
+              </tr>
+            </tbody>
+          </table>
+        </section>
+
+        <section>
+          <h2>{"도출규칙 체계"}</h2>
+          <p>{"표 위의 선택 상자에서 도출규칙 체계를 고를 수 있습니다. 기본은 아래에 설명한 Gentzen 체계입니다."}</p>
+          <p>
+            {"Lemmon 체계("}<i>{"Beginning Logic"}</i>{")에서는 가정을 "}<code>{"A"}</code>{"로 적고, "}
+            <code>{"MPP"}</code>{", "}<code>{"MTT"}</code>{", "}<code>{"DN"}</code>{", "}<code>{"CP"}</code>{", "}
+            <code>{"&I"}</code>{", "}<code>{"&E"}</code>{", "}<code>{"∨I"}</code>{", "}<code>{"∨E"}</code>{", "}
+            <code>{"RAA"}</code>{", "}<code>{"UI"}</code>{", "}<code>{"UG"}</code>{", "}<code>{"EI"}</code>{", "}
+            <code>{"EG"}</code>{"를 씁니다. 해소하는 가정은 범위 대신 가정의 행 번호로 인용합니다(예: "}
+            <code>{"2, 4 CP"}</code>{", "}<code>{"1, 2, 3, 4, 5 ∨E"}</code>{", "}<code>{"1, 2, 4 EI"}</code>{"). "}
+            {"모순은 "}<code>{"(B & ¬B)"}</code>{" 꼴로 적습니다."}
+          </p>
+          <p>
+            {"옆의 선택 상자에서 논리를 고를 수 있습니다. 직관주의 논리에서는 "}<code>{"¬E"}</code>
+            {"(Lemmon 체계에서는 "}<code>{"DN"}</code>{"으로 이중부정을 없애는 것)를, 최소 논리에서는 여기에 더해 "}
+            <code>{"⊥"}</code>{" 규칙을 쓸 수 없습니다. 고전 논리가 필요한 정리가 무엇인지 확인할 때 쓰세요."}
+          </p>
+          <p>
+            {"Gentzen 체계에서는 표 아래의 "}<b>{"증명 나무"}</b>{"를 펼쳐 올바른 도출을 겐첸식 나무로 볼 수 있습니다. "}
+            {"소거된 가정은 대괄호로 묶고, 그 가정과 가정을 소거한 추론에 가정의 행 번호를 위첨자로 붙입니다. "}
+            {"여러 번 인용한 행은 인용한 곳마다 되풀이해 그립니다. LaTeX "}<code>{"bussproofs"}</code>{" 코드로도 내보낼 수 있습니다. "}
+            {"도입한 식을 곧바로 제거하는 우회가 있으면 "}<b>{"정규화"}</b>{" 버튼으로 우회를 없앤 도출로 바꿀 수 있습니다."}
+          </p>
+          <p>
+            {"표 아래의 "}<b>{"규칙 명세"}</b>{"에 명제 논리의 규칙을 직접 적어 불러오면, 그 규칙으로 도출을 검증합니다. "}
+            {"불러온 명세는 이 브라우저에 저장되고, 문서로 내보낼 때 함께 적힙니다."}
+          </p>
+        </section>
+      </>
+    }
+  }
+  fn help_rules(&self) -> &'static str {
+    "추론 규칙들과 추론 규칙 명세 언어:"
+  }
+}
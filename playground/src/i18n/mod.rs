@@ -0,0 +1,185 @@
+//! 화면에 보이는 문구의 언어별 목록. 언어는 `App`이 `ContextProvider<Lang>`으로 내려주고, 컴포넌트는
+//! `use_messages`로 그 언어의 목록을 얻습니다.
+
+mod en;
+mod ko;
+
+use gloo_storage::{LocalStorage, Storage};
+use language_derivation_rule::{
+  citation::CitationError, derived::DeriveError, fitch::FitchError, proof_file::ProofFileError, rule_set::Profile,
+  spec::SpecError, theorem::LibraryError, tree::TreeError,
+};
+use serde::{Deserialize, Serialize};
+use yew::{hook, use_context, Html};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Lang {
+  #[default]
+  Ko,
+  En,
+}
+
+const LANG_KEY: &str = "settings.lang";
+
+impl Lang {
+  pub const ALL: [Lang; 2] = [Lang::Ko, Lang::En];
+
+  /// `<html lang>`에 쓰는 언어 코드
+  pub fn code(self) -> &'static str {
+    match self {
+      Lang::Ko => "ko",
+      Lang::En => "en",
+    }
+  }
+
+  /// 언어 선택 상자에 보이는, 그 언어로 쓴 이름
+  pub fn name(self) -> &'static str {
+    match self {
+      Lang::Ko => "한국어",
+      Lang::En => "English",
+    }
+  }
+
+  pub fn messages(self) -> &'static dyn Messages {
+    match self {
+      Lang::Ko => &ko::Ko,
+      Lang::En => &en::En,
+    }
+  }
+
+  /// 전에 고른 언어. 고른 적이 없으면 브라우저 언어가 한국어일 때만 한국어입니다.
+  pub fn load() -> Self {
+    if let Ok(lang) = LocalStorage::get(LANG_KEY) {
+      return lang;
+    }
+    let browser = web_sys::window().and_then(|window| window.navigator().language());
+    match browser {
+      Some(code) if !code.starts_with("ko") => Lang::En,
+      _ => Lang::Ko,
+    }
+  }
+
+  pub fn store(self) {
+    let _ = LocalStorage::set(LANG_KEY, self);
+  }
+}
+
+/// 언어마다 하나씩 있는 문구 목록
+#[derive(Clone, Deserialize)]
+pub struct Localized<S = String> {
+  pub ko: S,
+  pub en: S,
+}
+
+impl<S: AsRef<str>> Localized<S> {
+  pub fn get(&self, lang: Lang) -> &str {
+    match lang {
+      Lang::Ko => self.ko.as_ref(),
+      Lang::En => self.en.as_ref(),
+    }
+  }
+}
+
+#[hook]
+pub fn use_lang() -> Lang {
+  use_context::<Lang>().unwrap_or_default()
+}
+
+#[hook]
+pub fn use_messages() -> &'static dyn Messages {
+  use_lang().messages()
+}
+
+/// 한 언어의 문구. 식이나 코드를 문장 가운데에 넣어야 하는 문구는 어순이 언어마다 다르므로 `Html`로
+/// 돌려줍니다.
+pub trait Messages {
+  // 머리말
+  fn site_title(&self) -> &'static str;
+  fn nav_validator(&self) -> &'static str;
+  fn nav_exercises(&self) -> &'static str;
+  fn nav_help(&self) -> &'static str;
+  fn language(&self) -> &'static str;
+  fn page_not_found(&self) -> &'static str;
+
+  // 검증기 첫 화면의 단축키와 검증 오류 안내
+  fn shortcuts(&self) -> &'static str;
+  fn shortcut_insert_row(&self) -> &'static str;
+  fn shortcut_format(&self) -> &'static str;
+  fn validation_errors(&self) -> &'static str;
+  fn legend_syntax(&self) -> &'static str;
+  fn legend_sentence(&self) -> &'static str;
+  fn legend_rule(&self) -> &'static str;
+  fn legend_dependencies(&self) -> &'static str;
+
+  // 표
+  fn rule_system(&self) -> &'static str;
+  fn logic(&self) -> &'static str;
+  fn sequent(&self) -> &'static str;
+  fn column_dependencies(&self) -> &'static str;
+  fn column_sentence(&self) -> &'static str;
+  fn column_rule(&self) -> &'static str;
+  fn add_row(&self) -> &'static str;
+  fn suggestions_hint(&self) -> &'static str;
+  fn justifications_hint(&self) -> &'static str;
+  fn minimize(&self) -> &'static str;
+  fn minimize_preview(&self) -> &'static str;
+  fn apply(&self) -> &'static str;
+  fn cancel(&self) -> &'static str;
+  fn proof_tree(&self) -> &'static str;
+  fn normalize(&self) -> &'static str;
+  fn normalize_hint(&self) -> &'static str;
+  fn bussproofs_hint(&self) -> Html;
+  fn bussproofs_too_wide(&self) -> &'static str;
+  fn lemmas(&self) -> &'static str;
+  fn lemmas_hint(&self) -> Html;
+  fn remove(&self) -> &'static str;
+  fn lemma_name_placeholder(&self) -> &'static str;
+  fn save_lemma(&self) -> &'static str;
+  fn lemma_saved(&self, lemma: &str) -> String;
+  fn theorems(&self) -> &'static str;
+  fn theorems_hint(&self) -> Html;
+  fn theorems_loaded(&self, count: usize) -> String;
+  fn rule_spec(&self) -> &'static str;
+  fn rule_spec_hint(&self) -> Html;
+  fn rule_spec_loaded(&self, count: usize) -> String;
+  fn text_export_import(&self) -> &'static str;
+  fn export(&self) -> &'static str;
+  fn import(&self) -> &'static str;
+  fn export_proof_file(&self) -> &'static str;
+  fn import_proof_file(&self) -> &'static str;
+  fn export_fitch(&self) -> &'static str;
+  fn import_fitch(&self) -> &'static str;
+  fn latex_layout(&self) -> &'static str;
+  fn export_latex(&self) -> &'static str;
+  fn import_locked(&self) -> &'static str;
+
+  // 논증 판정
+  fn verdict_undeclared(&self) -> &'static str;
+  fn verdict_syntax_error(&self) -> &'static str;
+  fn verdict_proved(&self, sequent: &str, profile: Profile) -> String;
+  fn verdict_unproved(&self, sequent: &str, profile: Profile) -> String;
+  fn verdict_invalid(&self, nums: &str) -> String;
+  fn verdict_undischarged(&self, nums: &str) -> String;
+  fn verdict_unused(&self, nums: &str) -> String;
+
+  // 검증 오류
+  fn profile(&self, profile: Profile) -> &'static str;
+  fn citation_error(&self, error: &CitationError) -> String;
+  fn derive_error(&self, error: &DeriveError) -> String;
+  fn tree_error(&self, error: &TreeError) -> String;
+  fn library_error(&self, error: &LibraryError) -> String;
+  fn spec_error(&self, error: &SpecError) -> String;
+  fn fitch_error(&self, error: &FitchError) -> String;
+  fn proof_file_error(&self, error: &ProofFileError) -> String;
+
+  // 연습문제
+  fn exercise_not_found(&self) -> &'static str;
+  fn exercise_list(&self) -> &'static str;
+  fn exercise_instructions(&self, premises: Html, goal: Html) -> Html;
+  fn exercise_solved(&self) -> &'static str;
+  fn exercise_solved_before(&self) -> &'static str;
+
+  // 도움말
+  fn help(&self) -> Html;
+  fn help_rules(&self) -> &'static str;
+}
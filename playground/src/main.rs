@@ -1,14 +1,24 @@
 mod component;
+mod i18n;
 mod pages;
 
-use self::pages::{Help, Home, NotFound};
-use yew::{function_component, html, Html};
+use self::i18n::Lang;
+use self::pages::{Exercise, Exercises, Help, Home, NotFound};
+use web_sys::HtmlSelectElement;
+use yew::{
+  events::Event, function_component, html, html_nested, use_effect_with_deps, use_state, Callback, ContextProvider,
+  Html, TargetCast,
+};
 use yew_router::prelude::*;
 
 #[derive(Clone, Routable, PartialEq)]
 pub enum Route {
   #[at("/")]
   Home,
+  #[at("/exercises/")]
+  Exercises,
+  #[at("/exercises/:id")]
+  Exercise { id: String },
   #[at("/help/")]
   HelpHome,
   #[at("/help/:s")]
@@ -23,6 +33,12 @@ fn switch(routes: Route) -> Html {
     Route::Home => html! {
       <Home />
     },
+    Route::Exercises => html! {
+      <Exercises />
+    },
+    Route::Exercise { id } => html! {
+      <Exercise id={id} />
+    },
     Route::HelpHome | Route::Help => html! {
       <Help />
     },
@@ -34,22 +50,57 @@ fn switch(routes: Route) -> Html {
 
 #[function_component(App)]
 fn app() -> Html {
+  let lang = use_state(Lang::load);
+  let t = lang.messages();
+
+  // 스크린 리더와 브라우저 번역이 언어를 알 수 있도록 문서에도 반영합니다.
+  use_effect_with_deps(
+    |&lang| {
+      if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        document.set_title(lang.messages().site_title());
+        if let Some(root) = document.document_element() {
+          let _ = root.set_attribute("lang", lang.code());
+        }
+      }
+    },
+    *lang,
+  );
+
+  let handle_change_lang = {
+    let lang = lang.clone();
+    Callback::from(move |e: Event| {
+      let target: HtmlSelectElement = e.target_unchecked_into();
+      if let Some(&next) = Lang::ALL.get(target.selected_index() as usize) {
+        next.store();
+        lang.set(next);
+      }
+    })
+  };
+
   html! {
-    <BrowserRouter>
-      <div class="prose prose-a:text-blue-500 max-w-2xl mx-auto my-16">
-        <header class="flex items-baseline justify-between">
-          <h1 class="leading-none">{"1차논리 검증기"}</h1>
-          <nav>
-            <ul class="list-none p-0 flex gap-4">
-              <li><Link<Route> to={Route::Home}>{"검증기"}</Link<Route>>{" "}</li>
-              <li><Link<Route> to={Route::HelpHome}>{"정보 및 도움말"}</Link<Route>></li>
-            </ul>
-          </nav>
-        </header>
-
-        <Switch<Route> render={switch} />
-      </div>
-    </BrowserRouter>
+    <ContextProvider<Lang> context={*lang}>
+      <BrowserRouter>
+        <div class="prose prose-a:text-blue-500 max-w-2xl mx-auto my-16">
+          <header class="flex items-baseline justify-between">
+            <h1 class="leading-none">{t.site_title()}</h1>
+            <nav class="flex items-baseline gap-4">
+              <ul class="list-none p-0 flex gap-4">
+                <li><Link<Route> to={Route::Home}>{t.nav_validator()}</Link<Route>>{" "}</li>
+                <li><Link<Route> to={Route::Exercises}>{t.nav_exercises()}</Link<Route>>{" "}</li>
+                <li><Link<Route> to={Route::HelpHome}>{t.nav_help()}</Link<Route>></li>
+              </ul>
+              <select class="px-1 bg-gray-100 text-sm focus:outline-none" title={t.language()} onchange={handle_change_lang}>
+                { for Lang::ALL.iter().map(|option| html_nested! {
+                  <option selected={*option == *lang}>{option.name()}</option>
+                }) }
+              </select>
+            </nav>
+          </header>
+
+          <Switch<Route> render={switch} />
+        </div>
+      </BrowserRouter>
+    </ContextProvider<Lang>>
   }
 }
 
@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+
+use gloo_storage::{LocalStorage, Storage};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use yew::{function_component, html, html_nested, use_effect_with_deps, use_state, Callback, Html, Properties};
+use yew_router::prelude::Link;
+
+use crate::{
+  component::table::{parser::parse_exp, Checked, Row, Table},
+  i18n::{use_lang, Localized},
+  Route,
+};
+
+#[derive(Deserialize)]
+struct Problem {
+  id: String,
+  title: Localized,
+  premises: Vec<String>,
+  goal: String,
+}
+
+lazy_static! {
+  static ref PROBLEMS: Vec<Problem> =
+    serde_yaml::from_str(include_str!("problems.yaml")).expect("bundled problem set must be valid");
+}
+
+const SOLVED_KEY: &str = "exercises.solved";
+
+fn load_solved() -> BTreeSet<String> {
+  LocalStorage::get(SOLVED_KEY).unwrap_or_default()
+}
+
+fn mark_solved(id: &str) {
+  let mut solved = load_solved();
+  if solved.insert(id.to_owned()) {
+    let _ = LocalStorage::set(SOLVED_KEY, solved);
+  }
+}
+
+impl Problem {
+  fn rows(&self) -> Vec<Row> {
+    self
+      .premises
+      .iter()
+      .map(|premise| Row {
+        sentence: premise.clone(),
+        derivation: "P".to_owned(),
+        comment: None,
+      })
+      .chain([Row {
+        sentence: "".to_owned(),
+        derivation: "".to_owned(),
+        comment: None,
+      }])
+      .collect()
+  }
+
+  /// 모든 행이 올바르고, 마지막 행이 목표 식이며, 그 전제번호가 주어진 전제들 안에 있으면 풀린 것입니다.
+  fn is_solved_by(&self, checked: &Checked) -> bool {
+    let n = self.premises.len();
+    let goal = match parse_exp(&self.goal) {
+      Ok(goal) => goal,
+      Err(()) => return false,
+    };
+    let premises_intact = self.premises.iter().zip(checked.rows.iter()).all(|(premise, row)| {
+      parse_exp(premise).is_ok()
+        && parse_exp(premise) == parse_exp(&row.sentence)
+        && checked.system.is_assumption(&row.derivation)
+    });
+    let (last, last_deps) = match (checked.rows.last(), checked.deps_list.last()) {
+      (Some(last), Some(last_deps)) => (last, last_deps),
+      _ => return false,
+    };
+    premises_intact
+      && checked.rows.len() > n
+      && checked.rule_validity_list.iter().all(|&is_valid| is_valid)
+      && checked.deps_list.iter().all(|deps| deps.is_complete)
+      && parse_exp(&last.sentence) == Ok(goal)
+      && last_deps.nums.iter().all(|&num| (1..=n).contains(&num))
+  }
+}
+
+#[function_component(Exercises)]
+pub fn exercises() -> Html {
+  let lang = use_lang();
+  let t = lang.messages();
+  let solved = load_solved();
+  html! {
+    <>
+      <h2>{t.nav_exercises()}</h2>
+      <ul>
+        { for PROBLEMS.iter().map(|problem| html_nested! {
+          <li>
+            <Link<Route> to={Route::Exercise { id: problem.id.clone() }}>{problem.title.get(lang)}</Link<Route>>
+            {" "}<code>{format!("{} ⊢ {}", problem.premises.join(", "), problem.goal)}</code>
+            if solved.contains(&problem.id) {
+              {" ✅"}
+            }
+          </li>
+        }) }
+      </ul>
+    </>
+  }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ExerciseProps {
+  pub id: String,
+}
+
+#[function_component(Exercise)]
+pub fn exercise(props: &ExerciseProps) -> Html {
+  let lang = use_lang();
+  let t = lang.messages();
+  let is_solved = use_state(|| false);
+  let was_solved = use_state(|| false);
+  {
+    let is_solved = is_solved.clone();
+    let was_solved = was_solved.clone();
+    use_effect_with_deps(
+      move |id| {
+        is_solved.set(false);
+        was_solved.set(load_solved().contains(id));
+      },
+      props.id.clone(),
+    );
+  }
+
+  let problem: &'static Problem = match PROBLEMS.iter().find(|problem| problem.id == props.id) {
+    Some(problem) => problem,
+    None => {
+      return html! {
+        <p>{t.exercise_not_found()}{" "}<Link<Route> to={Route::Exercises}>{t.exercise_list()}</Link<Route>></p>
+      }
+    }
+  };
+
+  let handle_change = {
+    let is_solved = is_solved.clone();
+    Callback::from(move |checked: Checked| {
+      let solved = problem.is_solved_by(&checked);
+      if solved {
+        mark_solved(&problem.id);
+      }
+      is_solved.set(solved);
+    })
+  };
+
+  html! {
+    <>
+      <h2>{problem.title.get(lang)}</h2>
+      <p>
+        {t.exercise_instructions(
+          html! { <code>{problem.premises.join(", ")}</code> },
+          html! { <code>{&problem.goal}</code> },
+        )}
+      </p>
+      if *is_solved {
+        <p class="bg-green-100 px-4 py-2 rounded">{t.exercise_solved()}</p>
+      } else if *was_solved {
+        <p class="bg-slate-100 px-4 py-2 rounded">{t.exercise_solved_before()}</p>
+      }
+      <section>
+        <Table
+          key={problem.id.clone()}
+          default_value={problem.rows()}
+          locked={problem.premises.len()}
+          on_change={handle_change}
+        />
+      </section>
+    </>
+  }
+}
@@ -1,7 +1,9 @@
+mod exercise;
 mod help;
 mod home;
 mod not_found;
 
+pub use exercise::*;
 pub use help::*;
 pub use home::*;
 pub use not_found::*;
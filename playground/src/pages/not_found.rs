@@ -1,10 +1,13 @@
 use yew::{function_component, html, Html};
 
+use crate::i18n::use_messages;
+
 #[function_component(NotFound)]
 pub fn not_found() -> Html {
+  let t = use_messages();
   html! {
     <>
-      <h1>{"Page Not Found"}</h1>
+      <h1>{t.page_not_found()}</h1>
     </>
   }
 }
@@ -0,0 +1,371 @@
+//! What the server knows about a `.proof` file: diagnostics, hovers, definitions and completions,
+//! computed by the same reader and checker as the playground.
+//!
+//! Columns are counted in chars. The symbols of ℒ are all in the Basic Multilingual Plane, so they
+//! agree with the UTF-16 columns of the protocol.
+
+use language::{ast::sequent::Sequent, notation::ALIASES};
+use language_derivation_rule::{
+  document::{DocumentReport, DocumentRow, ProofDocument},
+  proof_file::{read_lines, Content, SourceLine},
+  rule_set::{Profile, System},
+  spec::{SpecSet, GENTZEN},
+};
+use lsp_types::{
+  CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic, DiagnosticSeverity, Position, Range, TextEdit,
+};
+
+/// Rules whose conditions the rule-specification language cannot state, with their schemas.
+const QUANTIFIER_RULES: [(&str, &str); 4] = [
+  (
+    "()I",
+    "()I: φa @k ⊢ (x)φx, where a occurs neither in (x)φx nor in the assumptions k depends on",
+  ),
+  ("()E", "()E: (x)φx @k ⊢ φa"),
+  ("∃I", "∃I: φa @k ⊢ (∃x)φx"),
+  (
+    "∃E",
+    "∃E: (∃x)φx @k, [φa]@l … ψ @m ⊢ ψ discharging l, where a occurs neither in (∃x)φx, ψ nor the other assumptions m depends on",
+  ),
+];
+
+pub struct Analysis {
+  lines: Vec<SourceLine>,
+  /// The number of chars on each line
+  widths: Vec<usize>,
+  /// The index in `lines` of each row
+  rows: Vec<usize>,
+  report: DocumentReport,
+}
+
+impl Analysis {
+  pub fn new(text: &str) -> Self {
+    let lines = read_lines(text);
+    let mut rows = vec![];
+    let mut document_rows = vec![];
+    let (mut premises, mut conclusion) = (vec![], None);
+    for (idx, line) in lines.iter().enumerate() {
+      let (sentence, derivation) = match &line.content {
+        Content::Row { exp, rule, .. } => (exp.to_string(), rule.clone()),
+        Content::Invalid { is_row: true, .. } => ("".to_owned(), "".to_owned()),
+        Content::Premises(exps) => {
+          premises = exps.clone();
+          continue;
+        }
+        Content::Conclusion(exp) => {
+          conclusion = Some(exp.clone());
+          continue;
+        }
+        Content::Blank | Content::Invalid { is_row: false, .. } => continue,
+      };
+      rows.push(idx);
+      document_rows.push(DocumentRow {
+        sentence,
+        derivation,
+        comment: None,
+      });
+    }
+    let document = ProofDocument {
+      sequent: conclusion.map(|conclusion| Sequent { premises, conclusion }.to_string()),
+      ..ProofDocument::new(System::Gentzen, document_rows)
+    };
+    // the sequent is printed from parsed sentences, so it reads back
+    let report = document.check(Profile::default()).unwrap_or_default();
+    let widths = text.lines().map(|line| line.chars().count()).collect();
+    Analysis {
+      lines,
+      widths,
+      rows,
+      report,
+    }
+  }
+
+  /// Lines that do not read, and rows whose rule does not apply.
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for line in &self.lines {
+      if let Content::Invalid { error, .. } = &line.content {
+        diagnostics.push(error_diagnostic(
+          range(line.line, error.column, self.widths[line.line - 1] + 1),
+          error.kind.to_string(),
+        ));
+      }
+    }
+    for (row, &idx) in self.report.rows.iter().zip(&self.rows) {
+      let line = &self.lines[idx];
+      if let (Content::Row { rule, rule_column, .. }, false) = (&line.content, row.errors.is_empty()) {
+        diagnostics.push(error_diagnostic(
+          range(line.line, *rule_column, rule_column + rule.chars().count()),
+          row.errors.join("; "),
+        ));
+      }
+    }
+    diagnostics
+  }
+
+  /// The dependencies of the row at `position` and the schemas of its rule, in Markdown.
+  pub fn hover(&self, position: Position) -> Option<String> {
+    let (num, rule) = self.row_at(position.line)?;
+    let report = &self.report.rows[num - 1];
+    let dependencies: Vec<String> = report.dependencies.iter().map(|num| num.to_string()).collect();
+    let mut text = match report.complete {
+      true => format!("**Depends on** {{{}}}", dependencies.join(", ")),
+      false => "**Depends on** unknown".to_owned(),
+    };
+    let schemas = schemas(rule_name(rule));
+    if !schemas.is_empty() {
+      text.push_str(&format!("\n\n```\n{}\n```", schemas.join("\n")));
+    }
+    Some(text)
+  }
+
+  /// The row cited by the line number at `position`.
+  pub fn definition(&self, position: Position) -> Option<Range> {
+    let line = self.lines.get(position.line as usize)?;
+    let (rule, rule_column) = match &line.content {
+      Content::Row { rule, rule_column, .. } => (rule, *rule_column),
+      _ => return None,
+    };
+    let offset = (position.character as usize + 1).checked_sub(rule_column)?;
+    let chars: Vec<char> = rule.chars().collect();
+    let is_digit = |idx: usize| chars.get(idx).is_some_and(|c| c.is_ascii_digit());
+    // the digits around the cursor, or just before it
+    let at = (0..=offset).rev().take(2).find(|&idx| is_digit(idx))?;
+    let start = (0..=at).rev().take_while(|&idx| is_digit(idx)).last()?;
+    let end = (at..chars.len()).take_while(|&idx| is_digit(idx)).last()? + 1;
+    let num: usize = chars[start..end].iter().collect::<String>().parse().ok()?;
+    let cited = &self.lines[*self.rows.get(num.checked_sub(1)?)?];
+    Some(range(cited.line, 1, self.widths[cited.line - 1] + 1))
+  }
+
+  /// The row number and rule of the row on `line`, counting from 0.
+  fn row_at(&self, line: u32) -> Option<(usize, &str)> {
+    let idx = self.rows.iter().position(|&idx| idx == line as usize)?;
+    match &self.lines[line as usize].content {
+      Content::Row { rule, .. } => Some((idx + 1, rule)),
+      _ => None,
+    }
+  }
+}
+
+/// Completions for the word before `position` on `line_text`: the symbol an ASCII alias stands for,
+/// and the rule names.
+pub fn completions(line_text: &str, position: Position) -> Vec<CompletionItem> {
+  let before: String = line_text.chars().take(position.character as usize).collect();
+  let word = before
+    .split_whitespace()
+    .last()
+    .filter(|_| !before.ends_with(char::is_whitespace));
+  let word_len = word.map_or(0, |word| word.chars().count()) as u32;
+  let replace = |len: u32, new_text: &str| {
+    Some(CompletionTextEdit::Edit(TextEdit {
+      range: Range {
+        start: Position {
+          character: position.character - len,
+          ..position
+        },
+        end: position,
+      },
+      new_text: new_text.to_owned(),
+    }))
+  };
+
+  let mut items = vec![];
+  // `-` after a digit is a range of lines, not a negation
+  let alias = ALIASES.iter().find(|(alias, _)| {
+    before.ends_with(alias) && !before[..before.len() - alias.len()].ends_with(|c: char| c.is_ascii_digit())
+  });
+  if let Some(&(alias, symbol)) = alias {
+    items.push(CompletionItem {
+      label: symbol.to_owned(),
+      kind: Some(CompletionItemKind::OPERATOR),
+      detail: Some(alias.to_owned()),
+      filter_text: Some(alias.to_owned()),
+      text_edit: replace(alias.chars().count() as u32, symbol),
+      ..CompletionItem::default()
+    });
+  }
+  for name in rule_names() {
+    items.push(CompletionItem {
+      label: name.clone(),
+      kind: Some(CompletionItemKind::KEYWORD),
+      detail: schemas(&name).first().cloned(),
+      filter_text: Some(ascii(&name)),
+      text_edit: replace(word_len, &name),
+      ..CompletionItem::default()
+    });
+  }
+  items
+}
+
+fn error_diagnostic(range: Range, message: String) -> Diagnostic {
+  Diagnostic {
+    range,
+    severity: Some(DiagnosticSeverity::ERROR),
+    source: Some("proof".to_owned()),
+    message,
+    ..Diagnostic::default()
+  }
+}
+
+/// The chars `start..end` of `line`, all counting from 1.
+fn range(line: usize, start: usize, end: usize) -> Range {
+  let position = |column: usize| Position {
+    line: line as u32 - 1,
+    character: column as u32 - 1,
+  };
+  Range {
+    start: position(start),
+    end: position(end),
+  }
+}
+
+fn spec_set() -> SpecSet {
+  GENTZEN.parse().expect("the bundled spec reads")
+}
+
+/// The name of a rule, after its citations.
+fn rule_name(rule: &str) -> &str {
+  rule.trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, ',' | '-' | ' '))
+}
+
+/// Every rule name of the Gentzen system, in the order of the spec.
+fn rule_names() -> Vec<String> {
+  let specs = spec_set();
+  let mut names = vec![specs.assumption];
+  for rule in specs.rules {
+    if !names.contains(&rule.name) {
+      names.push(rule.name);
+    }
+  }
+  names.extend(QUANTIFIER_RULES.iter().map(|(name, _)| name.to_string()));
+  names
+}
+
+fn schemas(name: &str) -> Vec<String> {
+  let specs = spec_set();
+  if name == specs.assumption {
+    return vec![format!("{}: assumption", name)];
+  }
+  let mut schemas: Vec<String> = specs
+    .rules
+    .iter()
+    .filter(|rule| rule.name == name)
+    .map(|rule| rule.to_string())
+    .collect();
+  schemas.extend(
+    QUANTIFIER_RULES
+      .iter()
+      .filter(|(rule, _)| *rule == name)
+      .map(|(_, schema)| schema.to_string()),
+  );
+  schemas
+}
+
+/// `name` with its symbols spelled by their ASCII aliases, for matching what is typed.
+fn ascii(name: &str) -> String {
+  name
+    .chars()
+    .map(|c| {
+      let symbol = c.to_string();
+      ALIASES
+        .iter()
+        .find(|(_, s)| *s == symbol)
+        .map_or(symbol.clone(), |(alias, _)| alias.to_string())
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  const PROOF: &str = "\
+conclusion: ¬P
+1. (P → Q)  P
+2. ¬Q       P
+3. P        P
+4. Q        1, 3 →E
+5. ⊥        2, 4 →E
+6. ¬P       3-5 ¬I
+";
+
+  fn position(line: u32, character: u32) -> Position {
+    Position { line, character }
+  }
+
+  #[test]
+  fn analysis_diagnostics() {
+    assert_eq!(Analysis::new(PROOF).diagnostics(), vec![]);
+
+    let text = "1. P  P\n2. (Q  P\n3. Q  1 &E\n4. P  1 &E";
+    let diagnostics = Analysis::new(text).diagnostics();
+    let found: Vec<(u32, u32, &str)> = diagnostics
+      .iter()
+      .map(|diagnostic| {
+        (
+          diagnostic.range.start.line,
+          diagnostic.range.start.character,
+          diagnostic.message.as_str(),
+        )
+      })
+      .collect();
+    assert_eq!(
+      found,
+      vec![
+        (1, 7, "invalid sentence"),
+        (2, 6, "the rule does not derive this sentence"),
+        (3, 6, "the rule does not derive this sentence"),
+      ]
+    );
+    assert_eq!(diagnostics[2].range.end.character, 10);
+  }
+
+  #[test]
+  fn analysis_hover() {
+    let analysis = Analysis::new(PROOF);
+    let hover = analysis.hover(position(6, 0)).unwrap();
+    assert!(hover.starts_with("**Depends on** {1, 2}"));
+    assert!(hover.contains("¬I: [φ]@k … ⊥ @l ⊢ ¬φ discharging k"));
+    assert_eq!(analysis.hover(position(0, 0)), None);
+  }
+
+  #[rstest]
+  #[case(position(4, 12), Some(1))]
+  #[case(position(4, 13), Some(1))]
+  #[case(position(4, 15), Some(3))]
+  #[case(position(6, 12), Some(3))]
+  #[case(position(6, 14), Some(5))]
+  #[case(position(4, 18), None)]
+  #[case(position(4, 3), None)]
+  fn analysis_definition(#[case] at: Position, #[case] line: Option<u32>) {
+    let found = Analysis::new(PROOF).definition(at);
+    assert_eq!(found.map(|range| range.start.line), line);
+    let width = |line: u32| PROOF.lines().nth(line as usize).unwrap().chars().count() as u32;
+    assert!(found.is_none_or(|range| range.end.character == width(range.start.line)));
+  }
+
+  #[test]
+  fn analysis_completions() {
+    let items = completions("4. (P ->", position(0, 8));
+    assert_eq!(items[0].label, "→");
+    assert_eq!(
+      items[0].text_edit,
+      Some(CompletionTextEdit::Edit(TextEdit {
+        range: Range {
+          start: position(0, 6),
+          end: position(0, 8),
+        },
+        new_text: "→".to_owned(),
+      }))
+    );
+
+    let items = completions("4. Q   1, 3-", position(0, 12));
+    assert!(items.iter().all(|item| item.label != "¬"));
+    let items = completions("4. Q   1, 3 ->E", position(0, 15));
+    let rule = items.iter().find(|item| item.label == "→E").unwrap();
+    assert_eq!(rule.filter_text.as_deref(), Some("->E"));
+    assert_eq!(rule.detail.as_deref(), Some("→E: (φ → ψ) @k, φ @l ⊢ ψ"));
+  }
+}
@@ -0,0 +1,186 @@
+//! A language server for `.proof` files, speaking LSP over stdio.
+
+mod analysis;
+
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+  notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, PublishDiagnostics},
+  request::{Completion, GotoDefinition, HoverRequest},
+  CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+  DidOpenTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+  HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+  TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use self::analysis::{completions, Analysis};
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+  let (connection, io_threads) = Connection::stdio();
+  let capabilities = ServerCapabilities {
+    text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+    hover_provider: Some(HoverProviderCapability::Simple(true)),
+    definition_provider: Some(OneOf::Left(true)),
+    completion_provider: Some(CompletionOptions::default()),
+    ..ServerCapabilities::default()
+  };
+  connection.initialize(serde_json::to_value(capabilities)?)?;
+  Server::default().run(&connection)?;
+  // the writer thread ends once the connection is gone
+  drop(connection);
+  io_threads.join()?;
+  Ok(())
+}
+
+/// The open documents, as last sent by the client.
+#[derive(Default)]
+struct Server {
+  documents: HashMap<Url, String>,
+}
+
+impl Server {
+  fn run(&mut self, connection: &Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+    for message in &connection.receiver {
+      match message {
+        Message::Request(request) => {
+          if connection.handle_shutdown(&request)? {
+            return Ok(());
+          }
+          let response = self.respond(request);
+          connection.sender.send(Message::Response(response))?;
+        }
+        Message::Notification(notification) => {
+          if let Some(uri) = self.update(notification) {
+            let diagnostics = match self.documents.get(&uri) {
+              Some(text) => Analysis::new(text).diagnostics(),
+              None => vec![],
+            };
+            let params = PublishDiagnosticsParams {
+              uri,
+              diagnostics,
+              version: None,
+            };
+            connection.sender.send(Message::Notification(Notification::new(
+              <PublishDiagnostics as lsp_types::notification::Notification>::METHOD.to_owned(),
+              params,
+            )))?;
+          }
+        }
+        Message::Response(_) => {}
+      }
+    }
+    Ok(())
+  }
+
+  /// Apply a change to the documents, returning the document whose diagnostics should be published.
+  fn update(&mut self, notification: Notification) -> Option<Url> {
+    let notification = match cast_notification::<DidOpenTextDocument>(notification) {
+      Ok(DidOpenTextDocumentParams { text_document }) => {
+        self.documents.insert(text_document.uri.clone(), text_document.text);
+        return Some(text_document.uri);
+      }
+      Err(notification) => notification,
+    };
+    let notification = match cast_notification::<DidChangeTextDocument>(notification) {
+      // the whole text is sent each time
+      Ok(DidChangeTextDocumentParams {
+        text_document,
+        content_changes,
+      }) => {
+        let text = content_changes.into_iter().last()?.text;
+        self.documents.insert(text_document.uri.clone(), text);
+        return Some(text_document.uri);
+      }
+      Err(notification) => notification,
+    };
+    match cast_notification::<DidCloseTextDocument>(notification) {
+      Ok(DidCloseTextDocumentParams { text_document }) => {
+        self.documents.remove(&text_document.uri);
+        Some(text_document.uri)
+      }
+      Err(_) => None,
+    }
+  }
+
+  fn respond(&self, request: Request) -> Response {
+    let request = match cast_request::<HoverRequest>(request) {
+      Ok(Ok((id, params))) => return Response::new_ok(id, self.hover(params)),
+      Ok(Err(response)) => return response,
+      Err(request) => request,
+    };
+    let request = match cast_request::<GotoDefinition>(request) {
+      Ok(Ok((id, params))) => return Response::new_ok(id, self.definition(params)),
+      Ok(Err(response)) => return response,
+      Err(request) => request,
+    };
+    match cast_request::<Completion>(request) {
+      Ok(Ok((id, params))) => Response::new_ok(id, self.completion(params)),
+      Ok(Err(response)) => response,
+      Err(request) => Response::new_err(
+        request.id,
+        lsp_server::ErrorCode::MethodNotFound as i32,
+        format!("unsupported request {}", request.method),
+      ),
+    }
+  }
+
+  fn hover(&self, params: HoverParams) -> Option<Hover> {
+    let position = params.text_document_position_params;
+    let text = self.documents.get(&position.text_document.uri)?;
+    let value = Analysis::new(text).hover(position.position)?;
+    Some(Hover {
+      contents: HoverContents::Markup(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+      }),
+      range: None,
+    })
+  }
+
+  fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+    let position = params.text_document_position_params;
+    let text = self.documents.get(&position.text_document.uri)?;
+    let range = Analysis::new(text).definition(position.position)?;
+    Some(GotoDefinitionResponse::Scalar(Location {
+      uri: position.text_document.uri,
+      range,
+    }))
+  }
+
+  fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+    let position = params.text_document_position;
+    let text = self.documents.get(&position.text_document.uri)?;
+    let line = text.lines().nth(position.position.line as usize).unwrap_or("");
+    Some(CompletionResponse::Array(completions(line, position.position)))
+  }
+}
+
+/// The id and parameters of a request for `R`, an error response when its parameters are malformed,
+/// or the request back when it is for another method.
+fn cast_request<R: lsp_types::request::Request>(
+  request: Request,
+) -> Result<Result<(RequestId, R::Params), Response>, Request> {
+  let id = request.id.clone();
+  match request.extract(R::METHOD) {
+    Ok(extracted) => Ok(Ok(extracted)),
+    Err(lsp_server::ExtractError::MethodMismatch(request)) => Err(request),
+    Err(lsp_server::ExtractError::JsonError { error, .. }) => Ok(Err(Response::new_err(
+      id,
+      lsp_server::ErrorCode::InvalidParams as i32,
+      error.to_string(),
+    ))),
+  }
+}
+
+fn cast_notification<N: lsp_types::notification::Notification>(
+  notification: Notification,
+) -> Result<N::Params, Notification> {
+  notification.extract(N::METHOD).map_err(|err| match err {
+    lsp_server::ExtractError::MethodMismatch(notification) => notification,
+    lsp_server::ExtractError::JsonError { method, .. } => Notification {
+      method,
+      params: serde_json::Value::Null,
+    },
+  })
+}